@@ -42,6 +42,7 @@ export const enum ErrorCode {
     BufferTooSmall = 35,
     HostBufferEmpty = 36,
     HostBufferFull = 37,
+    GasLimit = 38,
 }
 
 export const enum PosErrorCode {
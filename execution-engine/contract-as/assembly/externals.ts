@@ -19,7 +19,7 @@ export declare function get_arg_size(index: u32, dest_size: u32): i32;
 @external("env", "ret")
 export declare function ret(value_ptr: usize, value_size: usize): void;
 @external("env", "call_contract")
-export declare function call_contract(key_ptr: usize, key_size: u32, args_ptr: usize, args_size: u32, result_size: usize): i32;
+export declare function call_contract(key_ptr: usize, key_size: u32, entry_point_ptr: usize, entry_point_size: u32, args_ptr: usize, args_size: u32, gas_limit_ptr: usize, gas_limit_size: u32, result_size: usize): i32;
 @external("env", "get_key")
 export declare function get_key(
     name_ptr: usize,
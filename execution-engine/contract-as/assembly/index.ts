@@ -102,9 +102,15 @@ export function storeFunctionAtHash(name: String, namedKeysBytes: u8[]): Key | n
   return Key.fromHash(addr);
 }
 
-export function callContract(key: Key, args: CLValue[]): Uint8Array | null {
+export function callContract(key: Key, args: CLValue[], entryPoint: String = "call", gasLimit: U512 | null = null): Uint8Array | null {
   let keyBytes = key.toBytes();
+  let entryPointBytes = toBytesString(entryPoint);
   let argBytes = toBytesVecT(args);
+  // Serialized as an `Option<U512>`
+  let gasLimitBytes: u8[] = [0];
+  if (gasLimit !== null) {
+    gasLimitBytes = [<u8>1].concat((<U512>gasLimit).toBytes());
+  }
 
   let resultSize = new Uint32Array(1);
   resultSize.fill(0);
@@ -112,8 +118,12 @@ export function callContract(key: Key, args: CLValue[]): Uint8Array | null {
   let ret = externals.call_contract(
       <usize>keyBytes.dataStart,
       keyBytes.length,
+      entryPointBytes.dataStart,
+      entryPointBytes.length,
       argBytes.dataStart,
       argBytes.length,
+      gasLimitBytes.dataStart,
+      gasLimitBytes.length,
       resultSize.dataStart,
   );
   if (ret > 0) {
@@ -9,7 +9,7 @@ use casperlabs_types::{
     api_error,
     bytesrepr::{self, FromBytes},
     ApiError, BlockTime, CLTyped, CLValue, ContractRef, Key, Phase, URef,
-    BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH, U512,
 };
 
 use crate::{args_parser::ArgsParser, contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
@@ -25,6 +25,15 @@ pub fn ret(value: CLValue) -> ! {
     }
 }
 
+/// Emits an event with the given name and value, to be returned in the result of the deploy.
+/// Events emitted by a deploy whose session code fails, or by a contract which reverts, are
+/// dropped.
+pub fn emit_event(name: &str, value: CLValue) {
+    let (name_ptr, name_size, _bytes1) = contract_api::to_ptr(name);
+    let (value_ptr, value_size, _bytes2) = contract_api::to_ptr(value);
+    unsafe { ext_ffi::emit_event(name_ptr, name_size, value_ptr, value_size) }
+}
+
 /// Stops execution of a contract and reverts execution effects with a given reason.
 pub fn revert<T: Into<ApiError>>(error: T) -> ! {
     unsafe {
@@ -32,30 +41,120 @@ pub fn revert<T: Into<ApiError>>(error: T) -> ! {
     }
 }
 
-/// Call the given contract, passing the given (serialized) arguments to
+/// Call the given entry point of the given contract, passing the given (serialized) arguments to
 /// the host in order to have them available to the called contract during its
 /// execution. The value returned from the contract call (see `ret` above) is
 /// returned from this function.
+///
+/// Contracts stored with a single function expose it under
+/// [`DEFAULT_ENTRY_POINT`](casperlabs_types::DEFAULT_ENTRY_POINT).
 #[allow(clippy::ptr_arg)]
-pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef, args: A) -> T {
+pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(
+    c_ptr: ContractRef,
+    entry_point: &str,
+    args: A,
+) -> T {
+    call_contract_with(
+        ext_ffi::call_contract_entry_point,
+        c_ptr,
+        entry_point,
+        args,
+        None,
+    )
+    .unwrap_or_revert()
+}
+
+/// Like [`call_contract`], except that the called contract may use at most `gas_limit` gas.  If it
+/// goes over the limit, its changes to global state are discarded and [`ApiError::GasLimit`] is
+/// returned, leaving this contract to carry on with the rest of its gas.  Gas left unused by the
+/// called contract is not charged.
+pub fn call_contract_with_gas_limit<A: ArgsParser, T: CLTyped + FromBytes>(
+    c_ptr: ContractRef,
+    entry_point: &str,
+    args: A,
+    gas_limit: U512,
+) -> Result<T, ApiError> {
+    call_contract_with(
+        ext_ffi::call_contract_entry_point,
+        c_ptr,
+        entry_point,
+        args,
+        Some(gas_limit),
+    )
+}
+
+/// Like [`call_contract`], except that if the called contract reverts, its changes to global state
+/// are discarded and the error it reverted with is returned, rather than reverting this contract
+/// too.  The gas used by the called contract is charged either way.
+pub fn try_call_contract<A: ArgsParser, T: CLTyped + FromBytes>(
+    c_ptr: ContractRef,
+    entry_point: &str,
+    args: A,
+) -> Result<T, ApiError> {
+    call_contract_with(ext_ffi::try_call_contract, c_ptr, entry_point, args, None)
+}
+
+/// Like [`try_call_contract`], except that the called contract may use at most `gas_limit` gas.  If
+/// it goes over the limit, its changes to global state are discarded and [`ApiError::GasLimit`] is
+/// returned.  Gas left unused by the called contract is not charged.
+pub fn try_call_contract_with_gas_limit<A: ArgsParser, T: CLTyped + FromBytes>(
+    c_ptr: ContractRef,
+    entry_point: &str,
+    args: A,
+    gas_limit: U512,
+) -> Result<T, ApiError> {
+    call_contract_with(
+        ext_ffi::try_call_contract,
+        c_ptr,
+        entry_point,
+        args,
+        Some(gas_limit),
+    )
+}
+
+type CallContractFn = unsafe extern "C" fn(
+    *const u8,
+    usize,
+    *const u8,
+    usize,
+    *const u8,
+    usize,
+    *const u8,
+    usize,
+    *mut usize,
+) -> i32;
+
+fn call_contract_with<A: ArgsParser, T: CLTyped + FromBytes>(
+    call_fn: CallContractFn,
+    c_ptr: ContractRef,
+    entry_point: &str,
+    args: A,
+    gas_limit: Option<U512>,
+) -> Result<T, ApiError> {
     let contract_key: Key = c_ptr.into();
     let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
-    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args)
+    let (entry_point_ptr, entry_point_size, _bytes2) = contract_api::to_ptr(entry_point);
+    let (args_ptr, args_size, _bytes3) = ArgsParser::parse(args)
         .map(contract_api::to_ptr)
         .unwrap_or_revert();
+    let (gas_limit_ptr, gas_limit_size, _bytes4) = contract_api::to_ptr(gas_limit);
 
     let bytes_written = {
         let mut bytes_written = MaybeUninit::uninit();
         let ret = unsafe {
-            ext_ffi::call_contract(
+            call_fn(
                 key_ptr,
                 key_size,
+                entry_point_ptr,
+                entry_point_size,
                 args_ptr,
                 args_size,
+                gas_limit_ptr,
+                gas_limit_size,
                 bytes_written.as_mut_ptr(),
             )
         };
-        api_error::result_from(ret).unwrap_or_revert();
+        api_error::result_from(ret)?;
         unsafe { bytes_written.assume_init() }
     };
 
@@ -64,7 +163,7 @@ pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef,
     let bytes_ptr = contract_api::alloc_bytes(bytes_written);
     let mut dest: Vec<u8> = unsafe { Vec::from_raw_parts(bytes_ptr, bytes_written, bytes_written) };
     read_host_buffer_into(&mut dest).unwrap_or_revert();
-    bytesrepr::deserialize(dest).unwrap_or_revert()
+    Ok(bytesrepr::deserialize(dest).unwrap_or_revert())
 }
 
 /// Takes the name of a function to store and a contract URef, and overwrites the value under
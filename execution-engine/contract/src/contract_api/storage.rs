@@ -4,7 +4,8 @@ use core::{convert::From, mem::MaybeUninit};
 use casperlabs_types::{
     api_error,
     bytesrepr::{self, FromBytes, ToBytes},
-    AccessRights, ApiError, CLTyped, CLValue, ContractRef, Key, URef, KEY_UREF_SERIALIZED_LENGTH,
+    AccessRights, ApiError, CLTyped, CLValue, ContractAbi, ContractRef, Key, URef,
+    KEY_UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -128,6 +129,91 @@ pub fn store_function_at_hash(name: &str, named_keys: BTreeMap<String, Key>) ->
     ContractRef::Hash(addr)
 }
 
+/// Stores the serialized bytes of the given exported functions as a single contract at an
+/// unforgeable reference, making each of them callable as a named entry point.
+pub fn store_contract(entry_points: &[&str], named_keys: BTreeMap<String, Key>) -> ContractRef {
+    let entry_points = entry_points.iter().map(|name| (*name).into()).collect();
+    let addr = store_contract_with(ext_ffi::store_contract, entry_points, named_keys, None);
+    ContractRef::URef(URef::new(addr, AccessRights::READ_ADD_WRITE))
+}
+
+/// Stores the serialized bytes of the given exported functions as a single contract at an
+/// immutable address generated by the host, making each of them callable as a named entry point.
+pub fn store_contract_at_hash(
+    entry_points: &[&str],
+    named_keys: BTreeMap<String, Key>,
+) -> ContractRef {
+    let entry_points = entry_points.iter().map(|name| (*name).into()).collect();
+    let addr = store_contract_with(
+        ext_ffi::store_contract_at_hash,
+        entry_points,
+        named_keys,
+        None,
+    );
+    ContractRef::Hash(addr)
+}
+
+/// Like [`store_contract`], storing the exported functions described by `abi` along with it.  The
+/// host then checks that the arguments passed to and the value returned by each entry point match
+/// the types given in `abi`.  The entry points described by `abi` must have distinct names.
+pub fn store_contract_with_abi(abi: ContractAbi, named_keys: BTreeMap<String, Key>) -> ContractRef {
+    let entry_points = abi_entry_points(&abi);
+    let addr = store_contract_with(ext_ffi::store_contract, entry_points, named_keys, Some(abi));
+    ContractRef::URef(URef::new(addr, AccessRights::READ_ADD_WRITE))
+}
+
+/// Like [`store_contract_at_hash`], storing the exported functions described by `abi` along with
+/// it.  The host then checks that the arguments passed to and the value returned by each entry
+/// point match the types given in `abi`.  The entry points described by `abi` must have distinct
+/// names.
+pub fn store_contract_at_hash_with_abi(
+    abi: ContractAbi,
+    named_keys: BTreeMap<String, Key>,
+) -> ContractRef {
+    let entry_points = abi_entry_points(&abi);
+    let addr = store_contract_with(
+        ext_ffi::store_contract_at_hash,
+        entry_points,
+        named_keys,
+        Some(abi),
+    );
+    ContractRef::Hash(addr)
+}
+
+fn abi_entry_points(abi: &ContractAbi) -> Vec<String> {
+    abi.entry_points()
+        .iter()
+        .map(|entry_point| entry_point.name().into())
+        .collect()
+}
+
+type StoreContractFn =
+    unsafe extern "C" fn(*const u8, usize, *const u8, usize, *const u8, usize, *const u8);
+
+fn store_contract_with(
+    store_fn: StoreContractFn,
+    entry_points: Vec<String>,
+    named_keys: BTreeMap<String, Key>,
+    maybe_abi: Option<ContractAbi>,
+) -> [u8; 32] {
+    let (entry_points_ptr, entry_points_size, _bytes1) = contract_api::to_ptr(entry_points);
+    let (keys_ptr, keys_size, _bytes2) = contract_api::to_ptr(named_keys);
+    let (abi_ptr, abi_size, _bytes3) = contract_api::to_ptr(maybe_abi);
+    let mut addr = [0u8; 32];
+    unsafe {
+        store_fn(
+            entry_points_ptr,
+            entry_points_size,
+            keys_ptr,
+            keys_size,
+            abi_ptr,
+            abi_size,
+            addr.as_mut_ptr(),
+        );
+    }
+    addr
+}
+
 /// Returns a new unforgable pointer, where value is initialized to `init`
 pub fn new_turef<T: CLTyped + ToBytes>(init: T) -> TURef<T> {
     let key_ptr = contract_api::alloc_bytes(KEY_UREF_SERIALIZED_LENGTH);
@@ -40,6 +40,17 @@ extern "C" {
         args_size: usize,
         result_size: *mut usize,
     ) -> i32;
+    pub fn call_contract_entry_point(
+        key_ptr: *const u8,
+        key_size: usize,
+        entry_point_ptr: *const u8,
+        entry_point_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        gas_limit_ptr: *const u8,
+        gas_limit_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
     pub fn get_key(
         name_ptr: *const u8,
         name_size: usize,
@@ -100,4 +111,39 @@ extern "C" {
     ) -> i32;
     pub fn get_main_purse(dest_ptr: *mut u8);
     pub fn read_host_buffer(dest_ptr: *mut u8, dest_size: usize, bytes_written: *mut usize) -> i32;
+    pub fn emit_event(
+        name_ptr: *const u8,
+        name_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+    pub fn try_call_contract(
+        key_ptr: *const u8,
+        key_size: usize,
+        entry_point_ptr: *const u8,
+        entry_point_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        gas_limit_ptr: *const u8,
+        gas_limit_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
+    pub fn store_contract(
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        abi_ptr: *const u8,
+        abi_size: usize,
+        uref_addr_ptr: *const u8,
+    );
+    pub fn store_contract_at_hash(
+        entry_points_ptr: *const u8,
+        entry_points_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        abi_ptr: *const u8,
+        abi_size: usize,
+        hash_ptr: *const u8,
+    );
 }
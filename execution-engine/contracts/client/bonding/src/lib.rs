@@ -4,7 +4,7 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, U512};
+use types::{ApiError, DEFAULT_ENTRY_POINT, U512};
 
 const BOND_METHOD_NAME: &str = "bond";
 
@@ -24,5 +24,9 @@ pub extern "C" fn call() {
 
     system::transfer_from_purse_to_purse(source_purse, bonding_purse, bond_amount)
         .unwrap_or_revert();
-    runtime::call_contract(pos_pointer, (BOND_METHOD_NAME, bond_amount, bonding_purse))
+    runtime::call_contract(
+        pos_pointer,
+        DEFAULT_ENTRY_POINT,
+        (BOND_METHOD_NAME, bond_amount, bonding_purse),
+    )
 }
@@ -4,7 +4,7 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PurseId, ApiError, U512};
+use types::{account::PurseId, ApiError, DEFAULT_ENTRY_POINT, U512};
 
 const GET_PAYMENT_PURSE: &str = "get_payment_purse";
 
@@ -21,7 +21,8 @@ pub fn delegate() {
 
     let pos_pointer = system::get_proof_of_stake();
 
-    let payment_purse: PurseId = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+    let payment_purse: PurseId =
+        runtime::call_contract(pos_pointer, DEFAULT_ENTRY_POINT, (GET_PAYMENT_PURSE,));
 
     system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert();
 }
@@ -4,7 +4,7 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, U512};
+use types::{ApiError, DEFAULT_ENTRY_POINT, U512};
 
 const UNBOND_METHOD_NAME: &str = "unbond";
 
@@ -22,5 +22,9 @@ pub extern "C" fn call() {
         .unwrap_or_revert_with(ApiError::InvalidArgument);
     let unbond_amount: Option<U512> = arg_0.map(Into::into);
 
-    runtime::call_contract(pos_pointer, (UNBOND_METHOD_NAME, unbond_amount))
+    runtime::call_contract(
+        pos_pointer,
+        DEFAULT_ENTRY_POINT,
+        (UNBOND_METHOD_NAME, unbond_amount),
+    )
 }
@@ -1,7 +1,7 @@
 #![no_std]
 
 use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
-use types::ApiError;
+use types::{ApiError, DEFAULT_ENTRY_POINT};
 
 const COUNTER_KEY: &str = "counter";
 const GET_METHOD: &str = "get";
@@ -16,11 +16,11 @@ pub extern "C" fn call() {
 
     {
         let args = (INC_METHOD,);
-        runtime::call_contract::<_, ()>(contract_ref.clone(), args);
+        runtime::call_contract::<_, ()>(contract_ref.clone(), DEFAULT_ENTRY_POINT, args);
     }
 
     let _result: i32 = {
         let args = (GET_METHOD,);
-        runtime::call_contract(contract_ref, args)
+        runtime::call_contract(contract_ref, DEFAULT_ENTRY_POINT, args)
     };
 }
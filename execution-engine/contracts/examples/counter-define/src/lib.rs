@@ -9,7 +9,7 @@ use contract::{
     contract_api::{runtime, storage, TURef},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, CLValue, Key};
+use types::{ApiError, CLValue, Key, DEFAULT_ENTRY_POINT};
 
 const COUNT_KEY: &str = "count";
 const COUNTER_EXT: &str = "counter_ext";
@@ -69,7 +69,7 @@ pub extern "C" fn counter_increment() {
         .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
 
     let args = (INC_METHOD,);
-    runtime::call_contract(contract_ref, args)
+    runtime::call_contract(contract_ref, DEFAULT_ENTRY_POINT, args)
 }
 
 #[no_mangle]
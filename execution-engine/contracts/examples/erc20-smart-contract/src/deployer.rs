@@ -5,7 +5,7 @@ use crate::{
     error::Error,
 };
 use contract::contract_api::{runtime, storage, system, TURef};
-use types::{ContractRef, Key, U512};
+use types::{ContractRef, Key, DEFAULT_ENTRY_POINT, U512};
 
 // ERC20 smart contract.
 #[allow(unused_imports)]
@@ -38,7 +38,11 @@ fn deploy_token(name: &str, initial_balance: U512) {
     let token_ref: ContractRef = storage::store_function_at_hash(ERC20_CONTRACT_NAME, token_urefs);
 
     // Initialize erc20 contract.
-    runtime::call_contract::<_, ()>(token_ref.clone(), (api::INIT_ERC20, initial_balance));
+    runtime::call_contract::<_, ()>(
+        token_ref.clone(),
+        DEFAULT_ENTRY_POINT,
+        (api::INIT_ERC20, initial_balance),
+    );
 
     // Save it under a new TURef.
     let token_turef: TURef<Key> = storage::new_turef(token_ref.into());
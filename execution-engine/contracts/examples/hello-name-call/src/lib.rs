@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::ApiError;
+use types::{ApiError, DEFAULT_ENTRY_POINT};
 
 const HELLO_NAME_KEY: &str = "hello_name";
 const HELLOWORLD_KEY: &str = "helloworld";
@@ -21,7 +21,7 @@ pub extern "C" fn call() {
         .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
 
     let args = ("World",);
-    let result: String = runtime::call_contract(contract_ref, args);
+    let result: String = runtime::call_contract(contract_ref, DEFAULT_ENTRY_POINT, args);
     assert_eq!("Hello, World", result);
 
     // Store the result at a uref so it can be seen as an effect on the global state
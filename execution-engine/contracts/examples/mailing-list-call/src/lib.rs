@@ -9,7 +9,7 @@ use contract::{
     contract_api::{runtime, storage, TURef},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, Key};
+use types::{ApiError, Key, DEFAULT_ENTRY_POINT};
 
 const MAIL_FEED_KEY: &str = "mail_feed";
 const MAILING_KEY: &str = "mailing";
@@ -41,8 +41,9 @@ pub extern "C" fn call() {
 
     let name = "CasperLabs";
     let args = (SUB_METHOD, name);
-    let sub_key = runtime::call_contract::<_, Option<Key>>(contract_ref.clone(), args)
-        .unwrap_or_revert_with(Error::NoSubKey);
+    let sub_key =
+        runtime::call_contract::<_, Option<Key>>(contract_ref.clone(), DEFAULT_ENTRY_POINT, args)
+            .unwrap_or_revert_with(Error::NoSubKey);
 
     runtime::put_key(MAIL_FEED_KEY, sub_key);
 
@@ -54,7 +55,7 @@ pub extern "C" fn call() {
 
     let message = "Hello, World!";
     let args = (PUB_METHOD, message);
-    runtime::call_contract::<_, ()>(contract_ref, args);
+    runtime::call_contract::<_, ()>(contract_ref, DEFAULT_ENTRY_POINT, args);
 
     let list_key: TURef<Vec<String>> = sub_key.try_into().unwrap_or_revert();
     let messages = storage::read(list_key)
@@ -1,7 +1,7 @@
 #![no_std]
 
 use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
-use types::{ApiError, ContractRef, Key};
+use types::{ApiError, ContractRef, Key, DEFAULT_ENTRY_POINT};
 
 const LIST_NAMED_KEYS_KEY: &str = "list_named_keys";
 
@@ -15,5 +15,5 @@ pub extern "C" fn call() {
     };
 
     // Call `define` part of the contract.
-    runtime::call_contract(contract_ref, ())
+    runtime::call_contract(contract_ref, DEFAULT_ENTRY_POINT, ())
 }
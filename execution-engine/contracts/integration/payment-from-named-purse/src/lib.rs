@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PurseId, ApiError, U512};
+use types::{account::PurseId, ApiError, DEFAULT_ENTRY_POINT, U512};
 
 const GET_PAYMENT_PURSE: &str = "get_payment_purse";
 const SET_REFUND_PURSE: &str = "set_refund_purse";
@@ -41,9 +41,13 @@ pub extern "C" fn call() {
     let purse: PurseId = get_named_purse(&name).unwrap_or_revert_with(Error::PosNotFound);
 
     let pos_pointer = system::get_proof_of_stake();
-    let payment_purse: PurseId = runtime::call_contract(pos_pointer.clone(), (GET_PAYMENT_PURSE,));
+    let payment_purse: PurseId = runtime::call_contract(
+        pos_pointer.clone(),
+        DEFAULT_ENTRY_POINT,
+        (GET_PAYMENT_PURSE,),
+    );
 
-    runtime::call_contract::<_, ()>(pos_pointer, (SET_REFUND_PURSE, purse));
+    runtime::call_contract::<_, ()>(pos_pointer, DEFAULT_ENTRY_POINT, (SET_REFUND_PURSE, purse));
 
     system::transfer_from_purse_to_purse(purse, payment_purse, amount).unwrap_or_revert();
 }
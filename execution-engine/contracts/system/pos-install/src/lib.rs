@@ -12,7 +12,7 @@ use contract::{
 use types::{
     account::{PublicKey, PurseId},
     system_contract_errors::mint,
-    AccessRights, ApiError, CLValue, ContractRef, Key, URef, U512,
+    AccessRights, ApiError, CLValue, ContractRef, Key, URef, DEFAULT_ENTRY_POINT, U512,
 };
 
 const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
@@ -89,7 +89,8 @@ pub extern "C" fn call() {
 }
 
 fn mint_purse(mint: &ContractRef, amount: U512) -> PurseId {
-    let result: Result<URef, mint::Error> = runtime::call_contract(mint.clone(), ("mint", amount));
+    let result: Result<URef, mint::Error> =
+        runtime::call_contract(mint.clone(), DEFAULT_ENTRY_POINT, ("mint", amount));
 
     result.map(PurseId::new).unwrap_or_revert()
 }
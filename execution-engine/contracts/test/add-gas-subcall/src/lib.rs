@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::ApiError;
+use types::{ApiError, DEFAULT_ENTRY_POINT};
 
 // This is making use of the undocumented "FFI" function `gas()` which is used by the Wasm
 // interpreter to charge gas for upcoming interpreted instructions.  For further info on this, see
@@ -53,7 +53,7 @@ pub extern "C" fn call() {
         },
         ADD_GAS_VIA_SUBCALL => {
             let reference = storage::store_function_at_hash(SUBCALL_NAME, BTreeMap::new());
-            runtime::call_contract::<_, ()>(reference, (amount,));
+            runtime::call_contract::<_, ()>(reference, DEFAULT_ENTRY_POINT, (amount,));
         }
         _ => runtime::revert(ApiError::InvalidArgument),
     }
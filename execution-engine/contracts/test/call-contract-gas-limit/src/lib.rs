@@ -0,0 +1,75 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{account, runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, U512};
+
+const ENTRY_POINT_DO_NOTHING: &str = "do_nothing";
+const ENTRY_POINT_DO_SOMETHING: &str = "do_something";
+const ENTRY_POINT_ENDLESS_LOOP: &str = "endless_loop";
+const SOMETHING_ITERATIONS: u32 = 10;
+const UNEXPECTED_RESULT: u16 = 1;
+
+#[no_mangle]
+pub extern "C" fn do_nothing() {}
+
+#[no_mangle]
+pub extern "C" fn do_something() {
+    for _ in 0..SOMETHING_ITERATIONS {
+        let _main_purse = account::get_main_purse();
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn endless_loop() {
+    loop {
+        let _main_purse = account::get_main_purse();
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let entry_point: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let gas_limit: Option<U512> = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let try_call: bool = runtime::get_arg(2)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let contract_ref = storage::store_contract(
+        &[
+            ENTRY_POINT_DO_NOTHING,
+            ENTRY_POINT_DO_SOMETHING,
+            ENTRY_POINT_ENDLESS_LOOP,
+        ],
+        BTreeMap::new(),
+    );
+
+    let result: Result<(), ApiError> = match (gas_limit, try_call) {
+        (Some(gas_limit), false) => {
+            runtime::call_contract_with_gas_limit(contract_ref, &entry_point, (), gas_limit)
+        }
+        (Some(gas_limit), true) => {
+            runtime::try_call_contract_with_gas_limit(contract_ref, &entry_point, (), gas_limit)
+        }
+        (None, false) => Ok(runtime::call_contract(contract_ref, &entry_point, ())),
+        (None, true) => runtime::try_call_contract(contract_ref, &entry_point, ()),
+    };
+    let expected_result = if entry_point == ENTRY_POINT_ENDLESS_LOOP {
+        Err(ApiError::GasLimit)
+    } else {
+        Ok(())
+    };
+    if result != expected_result {
+        runtime::revert(ApiError::User(UNEXPECTED_RESULT));
+    }
+}
@@ -0,0 +1,57 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::mem::MaybeUninit;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{bytesrepr::ToBytes, ApiError, CLValue, Key};
+
+const ENTRY_FUNCTION_NAME: &str = "delegate";
+const CALL_FAILED: u16 = 1;
+
+mod legacy_ffi {
+    // The `call_contract` import of contracts compiled before it took an entry point and gas limit
+    extern "C" {
+        pub fn call_contract(
+            key_ptr: *const u8,
+            key_size: usize,
+            args_ptr: *const u8,
+            args_size: usize,
+            result_size: *mut usize,
+        ) -> i32;
+    }
+}
+
+fn to_bytes<T: ToBytes>(t: T) -> Vec<u8> {
+    t.into_bytes().unwrap_or_revert()
+}
+
+#[no_mangle]
+pub extern "C" fn delegate() {}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_key: Key =
+        storage::store_function_at_hash(ENTRY_FUNCTION_NAME, BTreeMap::new()).into();
+    let key_bytes = to_bytes(contract_key);
+    let args_bytes = to_bytes(Vec::<CLValue>::new());
+
+    let mut result_size = MaybeUninit::uninit();
+    let ret = unsafe {
+        legacy_ffi::call_contract(
+            key_bytes.as_ptr(),
+            key_bytes.len(),
+            args_bytes.as_ptr(),
+            args_bytes.len(),
+            result_size.as_mut_ptr(),
+        )
+    };
+    if ret != 0 {
+        runtime::revert(ApiError::User(CALL_FAILED));
+    }
+}
@@ -0,0 +1,76 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLType, CLValue, ContractAbi, EntryPointAbi, Parameter};
+
+const CONTRACT_NAME: &str = "contract_abi";
+const ENTRY_POINT_DOUBLE: &str = "double";
+const ENTRY_POINT_MISDESCRIBED: &str = "misdescribed";
+const VALUE: u64 = 21;
+const WRONG_VALUE: &str = "twenty one";
+const UNEXPECTED_RESULT: u16 = 1;
+const UNKNOWN_CASE: u16 = 2;
+
+const CASE_MATCHING: u8 = 0;
+const CASE_WRONG_ARGS: u8 = 1;
+const CASE_WRONG_RET: u8 = 2;
+const CASE_DUPLICATE_ENTRY_POINT: u8 = 3;
+
+fn double_abi() -> EntryPointAbi {
+    EntryPointAbi::new(
+        ENTRY_POINT_DOUBLE,
+        vec![Parameter::new("value", CLType::U64)],
+        CLType::U64,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn double() {
+    let value: u64 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    runtime::ret(CLValue::from_t(value * 2).unwrap_or_revert());
+}
+
+/// Returns a string although its ABI says it returns a `u64`.
+#[no_mangle]
+pub extern "C" fn misdescribed() {
+    runtime::ret(CLValue::from_t(WRONG_VALUE).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let case: u8 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    if case == CASE_DUPLICATE_ENTRY_POINT {
+        let abi = ContractAbi::new(vec![double_abi(), double_abi()]);
+        storage::store_contract_with_abi(abi, BTreeMap::new());
+        return;
+    }
+
+    let abi = ContractAbi::new(vec![
+        double_abi(),
+        EntryPointAbi::new(ENTRY_POINT_MISDESCRIBED, vec![], CLType::U64),
+    ]);
+    let contract_ref = storage::store_contract_with_abi(abi, BTreeMap::new());
+    runtime::put_key(CONTRACT_NAME, contract_ref.clone().into());
+
+    let result: u64 = match case {
+        CASE_MATCHING => runtime::call_contract(contract_ref, ENTRY_POINT_DOUBLE, (VALUE,)),
+        CASE_WRONG_ARGS => runtime::call_contract(contract_ref, ENTRY_POINT_DOUBLE, (WRONG_VALUE,)),
+        CASE_WRONG_RET => runtime::call_contract(contract_ref, ENTRY_POINT_MISDESCRIBED, ()),
+        _ => runtime::revert(ApiError::User(UNKNOWN_CASE)),
+    };
+    if result != VALUE * 2 {
+        runtime::revert(ApiError::User(UNEXPECTED_RESULT));
+    }
+}
@@ -5,7 +5,7 @@ extern crate alloc;
 use alloc::string::String;
 
 use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
-use types::{AccessRights, ApiError, ContractRef, URef};
+use types::{AccessRights, ApiError, ContractRef, URef, DEFAULT_ENTRY_POINT};
 
 #[repr(u16)]
 enum Args {
@@ -30,5 +30,5 @@ pub extern "C" fn call() {
         .unwrap_or_revert_with(ApiError::InvalidArgument);
     let do_nothing = ContractRef::URef(URef::new(arg.addr(), AccessRights::READ));
 
-    runtime::call_contract(do_nothing, (new_purse_name,))
+    runtime::call_contract(do_nothing, DEFAULT_ENTRY_POINT, (new_purse_name,))
 }
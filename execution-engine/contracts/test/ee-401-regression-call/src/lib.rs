@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, storage, TURef},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, ContractRef, Key, URef};
+use types::{ApiError, ContractRef, Key, URef, DEFAULT_ENTRY_POINT};
 
 #[no_mangle]
 pub extern "C" fn call() {
@@ -18,7 +18,7 @@ pub extern "C" fn call() {
         _ => runtime::revert(ApiError::UnexpectedKeyVariant),
     };
 
-    let result: URef = runtime::call_contract(contract_pointer, ());
+    let result: URef = runtime::call_contract(contract_pointer, DEFAULT_ENTRY_POINT, ());
 
     let value = storage::read(TURef::from_uref(result).unwrap());
 
@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, CLValue, ContractRef, Key, URef, U512};
+use types::{ApiError, CLValue, ContractRef, Key, URef, DEFAULT_ENTRY_POINT, U512};
 
 #[no_mangle]
 pub extern "C" fn do_nothing() {
@@ -44,7 +44,7 @@ pub extern "C" fn call() {
         let uref1: URef = storage::new_turef(U512::from(0)).into();
         runtime::put_key("uref1", Key::URef(uref1));
         // do_nothing doesn't do anything. It SHOULD not forward the internal RNG.
-        let result: String = runtime::call_contract(do_nothing, ());
+        let result: String = runtime::call_contract(do_nothing, DEFAULT_ENTRY_POINT, ());
         assert_eq!(result, "Hello, world!");
         let uref2: URef = storage::new_turef(U512::from(1)).into();
         runtime::put_key("uref2", Key::URef(uref2));
@@ -52,7 +52,7 @@ pub extern "C" fn call() {
         let uref1: URef = storage::new_turef(U512::from(0)).into();
         runtime::put_key("uref1", Key::URef(uref1));
         // do_something returns a new uref, and it should forward the internal RNG.
-        let uref2: URef = runtime::call_contract(do_something, ());
+        let uref2: URef = runtime::call_contract(do_something, DEFAULT_ENTRY_POINT, ());
         runtime::put_key("uref2", Key::URef(uref2));
     }
 }
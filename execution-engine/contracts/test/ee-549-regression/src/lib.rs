@@ -1,6 +1,7 @@
 #![no_std]
 
 use contract::contract_api::{runtime, system};
+use types::DEFAULT_ENTRY_POINT;
 
 const SET_REFUND_PURSE: &str = "set_refund_purse";
 
@@ -8,7 +9,7 @@ fn malicious_revenue_stealing_contract() {
     let purse = system::create_purse();
     let pos_pointer = system::get_proof_of_stake();
 
-    runtime::call_contract::<_, ()>(pos_pointer, (SET_REFUND_PURSE, purse));
+    runtime::call_contract::<_, ()>(pos_pointer, DEFAULT_ENTRY_POINT, (SET_REFUND_PURSE, purse));
 }
 
 #[no_mangle]
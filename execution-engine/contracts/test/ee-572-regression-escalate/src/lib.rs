@@ -4,7 +4,7 @@ use contract::{
     contract_api::{runtime, storage, TURef},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{AccessRights, ApiError, Key, URef};
+use types::{AccessRights, ApiError, Key, URef, DEFAULT_ENTRY_POINT};
 
 const CONTRACT_POINTER: u32 = 0;
 
@@ -25,7 +25,7 @@ pub extern "C" fn call() {
         .to_contract_ref()
         .unwrap_or_revert_with(ApiError::User(Error::GetArgument as u16));
 
-    let reference: URef = runtime::call_contract(contract_pointer, ());
+    let reference: URef = runtime::call_contract(contract_pointer, DEFAULT_ENTRY_POINT, ());
 
     let forged_reference: TURef<&str> = {
         let ret = URef::new(reference.addr(), AccessRights::READ_ADD_WRITE);
@@ -1,12 +1,12 @@
 #![no_std]
 
 use contract::contract_api::{account, runtime, system};
-use types::{account::PurseId, ContractRef, U512};
+use types::{account::PurseId, ContractRef, DEFAULT_ENTRY_POINT, U512};
 
 const POS_BOND: &str = "bond";
 
 fn bond(pos: ContractRef, amount: &U512, source: PurseId) {
-    runtime::call_contract::<_, ()>(pos, (POS_BOND, *amount, source));
+    runtime::call_contract::<_, ()>(pos, DEFAULT_ENTRY_POINT, (POS_BOND, *amount, source));
 }
 
 #[no_mangle]
@@ -4,17 +4,17 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PurseId, ApiError, ContractRef, U512};
+use types::{account::PurseId, ApiError, ContractRef, DEFAULT_ENTRY_POINT, U512};
 
 const POS_BOND: &str = "bond";
 const POS_UNBOND: &str = "unbond";
 
 fn bond(pos: ContractRef, amount: U512, source: PurseId) {
-    runtime::call_contract::<_, ()>(pos, (POS_BOND, amount, source));
+    runtime::call_contract::<_, ()>(pos, DEFAULT_ENTRY_POINT, (POS_BOND, amount, source));
 }
 
 fn unbond(pos: ContractRef, amount: Option<U512>) {
-    runtime::call_contract::<_, ()>(pos, (POS_UNBOND, amount));
+    runtime::call_contract::<_, ()>(pos, DEFAULT_ENTRY_POINT, (POS_UNBOND, amount));
 }
 
 #[no_mangle]
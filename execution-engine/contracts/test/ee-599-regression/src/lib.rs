@@ -10,7 +10,7 @@ use contract::{
 };
 use types::{
     account::{PublicKey, PurseId},
-    ApiError, Key, U512,
+    ApiError, Key, DEFAULT_ENTRY_POINT, U512,
 };
 
 const DONATION_AMOUNT: u64 = 1;
@@ -158,7 +158,7 @@ fn delegate() -> Result<(), ApiError> {
                     .map_err(|_| ApiError::InvalidArgument)?;
 
             let subcontract_args = (subcontract_method,);
-            runtime::call_contract::<_, ()>(contract_ref, subcontract_args);
+            runtime::call_contract::<_, ()>(contract_ref, DEFAULT_ENTRY_POINT, subcontract_args);
         }
         _ => return Err(ContractError::InvalidDelegateMethod.into()),
     }
@@ -8,7 +8,7 @@ use contract::{
     contract_api::{account, runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PurseId, ApiError, Phase, U512};
+use types::{account::PurseId, ApiError, Phase, DEFAULT_ENTRY_POINT, U512};
 
 const GET_PAYMENT_PURSE: &str = "get_payment_purse";
 const NEW_UREF_RESULT_UREF_NAME: &str = "new_uref_result";
@@ -34,7 +34,8 @@ pub extern "C" fn call() {
 
         let pos_pointer = system::get_proof_of_stake();
 
-        let payment_purse: PurseId = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+        let payment_purse: PurseId =
+            runtime::call_contract(pos_pointer, DEFAULT_ENTRY_POINT, (GET_PAYMENT_PURSE,));
 
         system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert()
     }
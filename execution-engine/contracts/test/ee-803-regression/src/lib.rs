@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PurseId, ApiError, ContractRef, U512};
+use types::{account::PurseId, ApiError, ContractRef, DEFAULT_ENTRY_POINT, U512};
 
 const POS_BOND: &str = "bond";
 const POS_UNBOND: &str = "unbond";
@@ -17,11 +17,15 @@ const COMMAND_BOND: &str = "bond";
 const COMMAND_UNBOND: &str = "unbond";
 
 fn bond(pos: &ContractRef, amount: &U512, source: PurseId) {
-    runtime::call_contract::<_, ()>(pos.clone(), (POS_BOND, *amount, source));
+    runtime::call_contract::<_, ()>(
+        pos.clone(),
+        DEFAULT_ENTRY_POINT,
+        (POS_BOND, *amount, source),
+    );
 }
 
 fn unbond(pos: &ContractRef, amount: Option<U512>) {
-    runtime::call_contract::<_, ()>(pos.clone(), (POS_UNBOND, amount));
+    runtime::call_contract::<_, ()>(pos.clone(), DEFAULT_ENTRY_POINT, (POS_UNBOND, amount));
 }
 
 #[no_mangle]
@@ -0,0 +1,51 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, CLValue};
+
+const CONTRACT_NAME: &str = "emit_event";
+const ENTRY_POINT_EMIT: &str = "emit";
+const FIRST_EVENT_NAME: &str = "first";
+const SECOND_EVENT_NAME: &str = "second";
+const CALLEE_ERROR: u16 = 2;
+const UNEXPECTED_RESULT: u16 = 1;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let callee_reverts: bool = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let contract_ref = runtime::get_key(CONTRACT_NAME)
+        .unwrap_or_revert_with(ApiError::GetKey)
+        .to_contract_ref()
+        .unwrap_or_revert();
+
+    runtime::emit_event(FIRST_EVENT_NAME, CLValue::from_t(1u64).unwrap_or_revert());
+
+    if callee_reverts {
+        let result: Result<(), ApiError> = runtime::try_call_contract(
+            contract_ref,
+            ENTRY_POINT_EMIT,
+            (value.clone(), callee_reverts),
+        );
+        if result != Err(ApiError::User(CALLEE_ERROR)) {
+            runtime::revert(ApiError::User(UNEXPECTED_RESULT));
+        }
+    } else {
+        runtime::call_contract::<_, ()>(
+            contract_ref,
+            ENTRY_POINT_EMIT,
+            (value.clone(), callee_reverts),
+        );
+    }
+
+    runtime::emit_event(SECOND_EVENT_NAME, CLValue::from_t(value).unwrap_or_revert());
+}
@@ -0,0 +1,38 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue};
+
+const CONTRACT_NAME: &str = "emit_event";
+const ENTRY_POINT_EMIT: &str = "emit";
+const EVENT_NAME: &str = "callee";
+const REVERT_CODE: u16 = 2;
+
+#[no_mangle]
+pub extern "C" fn emit() {
+    let value: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let should_revert: bool = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    runtime::emit_event(EVENT_NAME, CLValue::from_t(value).unwrap_or_revert());
+
+    if should_revert {
+        runtime::revert(ApiError::User(REVERT_CODE));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_ref = storage::store_contract_at_hash(&[ENTRY_POINT_EMIT], BTreeMap::new());
+    runtime::put_key(CONTRACT_NAME, contract_ref.into());
+}
@@ -0,0 +1,29 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, CLValue};
+
+const FIRST_EVENT_NAME: &str = "first";
+const SECOND_EVENT_NAME: &str = "second";
+const REVERT_CODE: u16 = 1;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let should_revert: bool = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    runtime::emit_event(FIRST_EVENT_NAME, CLValue::from_t(1u64).unwrap_or_revert());
+    runtime::emit_event(SECOND_EVENT_NAME, CLValue::from_t(value).unwrap_or_revert());
+
+    if should_revert {
+        runtime::revert(ApiError::User(REVERT_CODE));
+    }
+}
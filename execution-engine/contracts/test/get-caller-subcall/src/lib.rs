@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PublicKey, ApiError, CLValue};
+use types::{account::PublicKey, ApiError, CLValue, DEFAULT_ENTRY_POINT};
 
 #[no_mangle]
 pub extern "C" fn check_caller_ext() {
@@ -29,7 +29,7 @@ pub extern "C" fn call() {
     );
 
     let pointer = storage::store_function_at_hash("check_caller_ext", BTreeMap::new());
-    let subcall_public_key: PublicKey = runtime::call_contract(pointer, ());
+    let subcall_public_key: PublicKey = runtime::call_contract(pointer, DEFAULT_ENTRY_POINT, ());
     assert_eq!(
         subcall_public_key, known_public_key,
         "subcall public key was not known public key"
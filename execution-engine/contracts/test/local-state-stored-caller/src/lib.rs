@@ -1,7 +1,7 @@
 #![no_std]
 
 use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
-use types::{AccessRights, ApiError, ContractRef, URef};
+use types::{AccessRights, ApiError, ContractRef, URef, DEFAULT_ENTRY_POINT};
 
 #[repr(u32)]
 enum Args {
@@ -23,5 +23,5 @@ pub extern "C" fn call() {
         ContractRef::URef(URef::new(local_state_uref.addr(), AccessRights::READ));
 
     // call do_nothing_stored
-    runtime::call_contract(local_state_contract_pointer, ())
+    runtime::call_contract(local_state_contract_pointer, DEFAULT_ENTRY_POINT, ())
 }
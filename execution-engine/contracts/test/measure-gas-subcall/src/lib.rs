@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, CLValue, Phase};
+use types::{ApiError, CLValue, Phase, DEFAULT_ENTRY_POINT};
 
 #[repr(u16)]
 enum CustomError {
@@ -44,11 +44,11 @@ pub extern "C" fn call() {
         }
         "do-nothing" => {
             let reference = storage::store_function_at_hash(NOOP_EXT, BTreeMap::new());
-            runtime::call_contract::<_, ()>(reference, ());
+            runtime::call_contract::<_, ()>(reference, DEFAULT_ENTRY_POINT, ());
         }
         "do-something" => {
             let reference = storage::store_function_at_hash(GET_PHASE_EXT, BTreeMap::new());
-            let phase: Phase = runtime::call_contract(reference, ());
+            let phase: Phase = runtime::call_contract(reference, DEFAULT_ENTRY_POINT, ());
             if phase != Phase::Session {
                 runtime::revert(ApiError::User(CustomError::UnexpectedPhaseSub as u16))
             }
@@ -4,7 +4,9 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PurseId, system_contract_errors::mint, ApiError, URef, U512};
+use types::{
+    account::PurseId, system_contract_errors::mint, ApiError, URef, DEFAULT_ENTRY_POINT, U512,
+};
 
 #[repr(u16)]
 enum Error {
@@ -15,7 +17,8 @@ enum Error {
 fn mint_purse(amount: U512) -> Result<PurseId, mint::Error> {
     let mint = system::get_mint();
 
-    let result: Result<URef, mint::Error> = runtime::call_contract(mint, ("mint", amount));
+    let result: Result<URef, mint::Error> =
+        runtime::call_contract(mint, DEFAULT_ENTRY_POINT, ("mint", amount));
 
     result.map(PurseId::new)
 }
@@ -27,7 +30,8 @@ pub extern "C" fn call() {
 
     let mint = system::get_mint();
 
-    let balance: Option<U512> = runtime::call_contract(mint, ("balance", new_purse));
+    let balance: Option<U512> =
+        runtime::call_contract(mint, DEFAULT_ENTRY_POINT, ("balance", new_purse));
 
     match balance {
         None => runtime::revert(ApiError::User(Error::BalanceNotFound as u16)),
@@ -5,7 +5,7 @@ extern crate alloc;
 use alloc::string::String;
 
 use contract::contract_api::{runtime, storage, system};
-use types::Key;
+use types::{Key, DEFAULT_ENTRY_POINT};
 
 const NEW_ENDPOINT_NAME: &str = "version";
 const RESULT_TUREF_NAME: &str = "output_version";
@@ -13,7 +13,8 @@ const RESULT_TUREF_NAME: &str = "output_version";
 #[no_mangle]
 pub extern "C" fn call() {
     let mint_pointer = system::get_mint();
-    let value: String = runtime::call_contract(mint_pointer, (NEW_ENDPOINT_NAME,));
+    let value: String =
+        runtime::call_contract(mint_pointer, DEFAULT_ENTRY_POINT, (NEW_ENDPOINT_NAME,));
     let value_turef = storage::new_turef(value);
     let key = Key::URef(value_turef.into());
     runtime::put_key(RESULT_TUREF_NAME, key);
@@ -0,0 +1,28 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::contract_api::{runtime, storage};
+
+const CONTRACT_NAME: &str = "named_entry_points";
+const ENTRY_POINT_FIRST: &str = "first";
+const ENTRY_POINT_SECOND: &str = "second";
+
+#[no_mangle]
+pub extern "C" fn first() {
+    runtime::put_key(ENTRY_POINT_FIRST, storage::new_turef(()).into());
+}
+
+#[no_mangle]
+pub extern "C" fn second() {
+    runtime::put_key(ENTRY_POINT_SECOND, storage::new_turef(()).into());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_ref =
+        storage::store_contract(&[ENTRY_POINT_FIRST, ENTRY_POINT_SECOND], BTreeMap::new());
+    runtime::put_key(CONTRACT_NAME, contract_ref.into());
+}
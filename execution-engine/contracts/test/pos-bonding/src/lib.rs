@@ -10,7 +10,7 @@ use contract::{
 };
 use types::{
     account::{PublicKey, PurseId},
-    ApiError, ContractRef, U512,
+    ApiError, ContractRef, DEFAULT_ENTRY_POINT, U512,
 };
 
 #[repr(u16)]
@@ -20,11 +20,15 @@ enum Error {
 }
 
 fn bond(pos: &ContractRef, amount: &U512, source: PurseId) {
-    runtime::call_contract::<_, ()>(pos.clone(), (POS_BOND, *amount, source));
+    runtime::call_contract::<_, ()>(
+        pos.clone(),
+        DEFAULT_ENTRY_POINT,
+        (POS_BOND, *amount, source),
+    );
 }
 
 fn unbond(pos: &ContractRef, amount: Option<U512>) {
-    runtime::call_contract::<_, ()>(pos.clone(), (POS_UNBOND, amount));
+    runtime::call_contract::<_, ()>(pos.clone(), DEFAULT_ENTRY_POINT, (POS_UNBOND, amount));
 }
 
 const POS_BOND: &str = "bond";
@@ -6,15 +6,15 @@ use contract::{
 };
 use types::{
     account::{PublicKey, PurseId},
-    ApiError, ContractRef, Key, U512,
+    ApiError, ContractRef, Key, DEFAULT_ENTRY_POINT, U512,
 };
 
 fn set_refund_purse(pos: &ContractRef, p: &PurseId) {
-    runtime::call_contract(pos.clone(), ("set_refund_purse", *p))
+    runtime::call_contract(pos.clone(), DEFAULT_ENTRY_POINT, ("set_refund_purse", *p))
 }
 
 fn get_payment_purse(pos: &ContractRef) -> PurseId {
-    runtime::call_contract(pos.clone(), ("get_payment_purse",))
+    runtime::call_contract(pos.clone(), DEFAULT_ENTRY_POINT, ("get_payment_purse",))
 }
 
 fn submit_payment(pos: &ContractRef, amount: U512) {
@@ -24,7 +24,11 @@ fn submit_payment(pos: &ContractRef, amount: U512) {
 }
 
 fn finalize_payment(pos: &ContractRef, amount_spent: U512, account: PublicKey) {
-    runtime::call_contract(pos.clone(), ("finalize_payment", amount_spent, account))
+    runtime::call_contract(
+        pos.clone(),
+        DEFAULT_ENTRY_POINT,
+        ("finalize_payment", amount_spent, account),
+    )
 }
 
 #[no_mangle]
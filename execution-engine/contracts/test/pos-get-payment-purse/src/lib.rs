@@ -4,7 +4,7 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PurseId, ApiError, U512};
+use types::{account::PurseId, ApiError, DEFAULT_ENTRY_POINT, U512};
 
 #[repr(u16)]
 enum Error {
@@ -23,7 +23,8 @@ pub extern "C" fn call() {
     let payment_fund: U512 = runtime::get_arg(0)
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
-    let payment_purse: PurseId = runtime::call_contract(pos_pointer, ("get_payment_purse",));
+    let payment_purse: PurseId =
+        runtime::call_contract(pos_pointer, DEFAULT_ENTRY_POINT, ("get_payment_purse",));
 
     // can deposit
     system::transfer_from_purse_to_purse(source_purse, payment_purse, payment_amount)
@@ -4,7 +4,7 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PurseId, ApiError, ContractRef, U512};
+use types::{account::PurseId, ApiError, ContractRef, DEFAULT_ENTRY_POINT, U512};
 
 #[repr(u16)]
 enum Error {
@@ -15,15 +15,15 @@ enum Error {
 }
 
 fn set_refund_purse(pos: &ContractRef, p: &PurseId) {
-    runtime::call_contract(pos.clone(), ("set_refund_purse", *p))
+    runtime::call_contract(pos.clone(), DEFAULT_ENTRY_POINT, ("set_refund_purse", *p))
 }
 
 fn get_refund_purse(pos: &ContractRef) -> Option<PurseId> {
-    runtime::call_contract(pos.clone(), ("get_refund_purse",))
+    runtime::call_contract(pos.clone(), DEFAULT_ENTRY_POINT, ("get_refund_purse",))
 }
 
 fn get_payment_purse(pos: &ContractRef) -> PurseId {
-    runtime::call_contract(pos.clone(), ("get_payment_purse",))
+    runtime::call_contract(pos.clone(), DEFAULT_ENTRY_POINT, ("get_payment_purse",))
 }
 
 fn submit_payment(pos: &ContractRef, amount: U512) {
@@ -8,7 +8,7 @@ use contract::{
     contract_api::{runtime, storage},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{AccessRights, ApiError, ContractRef, URef};
+use types::{AccessRights, ApiError, ContractRef, URef, DEFAULT_ENTRY_POINT};
 
 pub const METHOD_VERSION: &str = "version";
 
@@ -48,8 +48,11 @@ pub extern "C" fn call() {
 
     match method_name.as_str() {
         METHOD_VERSION => {
-            let version: String =
-                runtime::call_contract(purse_holder_contract_pointer, (method_name,));
+            let version: String = runtime::call_contract(
+                purse_holder_contract_pointer,
+                DEFAULT_ENTRY_POINT,
+                (method_name,),
+            );
             let version_key = storage::new_turef(version).into();
             runtime::put_key(METHOD_VERSION, version_key);
         }
@@ -60,6 +63,7 @@ pub extern "C" fn call() {
 
             runtime::call_contract::<_, ()>(
                 purse_holder_contract_pointer,
+                DEFAULT_ENTRY_POINT,
                 (method_name, purse_name),
             );
         }
@@ -0,0 +1,76 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{runtime, storage, TURef},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef};
+
+const VALUE_KEY: &str = "value";
+const ENTRY_POINT_WRITE: &str = "write";
+const ENTRY_POINT_WRITE_AND_REVERT: &str = "write_and_revert";
+const INITIAL_VALUE: u64 = 0;
+const WRITTEN_VALUE: u64 = 1;
+const CALLEE_ERROR: u16 = 100;
+const UNEXPECTED_RESULT: u16 = 1;
+
+fn write_value() {
+    let value_uref: URef = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let value_ref: TURef<u64> = TURef::from_uref(value_uref).unwrap_or_revert();
+    let reads: u32 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    // gives the callee some work to be charged for
+    for _ in 0..reads {
+        let _value: Option<u64> = storage::read(value_ref.clone()).unwrap_or_revert();
+    }
+    storage::write(value_ref, WRITTEN_VALUE);
+}
+
+#[no_mangle]
+pub extern "C" fn write() {
+    write_value();
+}
+
+#[no_mangle]
+pub extern "C" fn write_and_revert() {
+    write_value();
+    runtime::revert(ApiError::User(CALLEE_ERROR));
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let callee_reverts: bool = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let callee_reads: u32 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let contract_ref = storage::store_contract(
+        &[ENTRY_POINT_WRITE, ENTRY_POINT_WRITE_AND_REVERT],
+        BTreeMap::new(),
+    );
+    let value_ref = storage::new_turef(INITIAL_VALUE);
+    runtime::put_key(VALUE_KEY, value_ref.clone().into());
+
+    let (entry_point, expected_result) = if callee_reverts {
+        (
+            ENTRY_POINT_WRITE_AND_REVERT,
+            Err(ApiError::User(CALLEE_ERROR)),
+        )
+    } else {
+        (ENTRY_POINT_WRITE, Ok(()))
+    };
+    let result: Result<(), ApiError> =
+        runtime::try_call_contract(contract_ref, entry_point, (value_ref, callee_reads));
+    if result != expected_result {
+        runtime::revert(ApiError::User(UNEXPECTED_RESULT));
+    }
+}
@@ -0,0 +1,160 @@
+//! Detection of conflicts between the effects of deploys executed against the same state.
+
+use std::collections::{BTreeSet, HashMap};
+
+use types::Key;
+
+use super::{execution_effect::ExecutionEffect, execution_result::ExecutionResult, op::Op};
+
+/// The pairs of deploys whose effects do not commute, found from the [`Op`]s each deploy applied
+/// to global state.
+///
+/// Deploys are identified by their positions in the list of effects (or results) the conflicts
+/// were computed from.  Two deploys conflict if they touch a common key with operations which do
+/// not commute (see [`Op::commutes_with`]); all other pairs can be committed in either order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeployConflicts {
+    deploy_count: usize,
+    pairs: BTreeSet<(usize, usize)>,
+}
+
+impl DeployConflicts {
+    /// Finds the conflicts between the given effects.
+    pub fn from_effects<'a, I>(effects: I) -> Self
+    where
+        I: IntoIterator<Item = &'a ExecutionEffect>,
+    {
+        let mut deploy_count = 0;
+        let mut ops_by_key: HashMap<Key, Vec<(usize, Op)>> = HashMap::new();
+        for (index, effect) in effects.into_iter().enumerate() {
+            deploy_count += 1;
+            for (key, op) in effect.ops.iter() {
+                ops_by_key.entry(*key).or_default().push((index, *op));
+            }
+        }
+
+        let mut pairs = BTreeSet::new();
+        for ops in ops_by_key.values() {
+            for (position, (first, first_op)) in ops.iter().enumerate() {
+                for (second, second_op) in &ops[position + 1..] {
+                    if !first_op.commutes_with(*second_op) {
+                        pairs.insert((*first, *second));
+                    }
+                }
+            }
+        }
+
+        DeployConflicts {
+            deploy_count,
+            pairs,
+        }
+    }
+
+    /// Finds the conflicts between the effects of the given execution results.
+    pub fn from_results(results: &[ExecutionResult]) -> Self {
+        Self::from_effects(results.iter().map(ExecutionResult::effect))
+    }
+
+    /// Returns the number of deploys the conflicts were computed from.
+    pub fn deploy_count(&self) -> usize {
+        self.deploy_count
+    }
+
+    /// Returns `true` if all of the deploys commute.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Returns `true` if the deploys at `first` and `second` commute.
+    pub fn commute(&self, first: usize, second: usize) -> bool {
+        let pair = if first <= second {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        !self.pairs.contains(&pair)
+    }
+
+    /// Returns the conflicting pairs of deploys in ascending order, the lower index of each pair
+    /// first.
+    pub fn pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.pairs.iter().copied()
+    }
+
+    /// Returns the deploys conflicting with the deploy at `index`, in ascending order.
+    pub fn conflicting_with(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        // Pairs ending at `index` sort before those starting at it, so this is in order.
+        self.pairs.iter().filter_map(move |&(first, second)| {
+            if first == index {
+                Some(second)
+            } else if second == index {
+                Some(first)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::additive_map::AdditiveMap;
+    use types::Key;
+
+    use super::DeployConflicts;
+    use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
+
+    fn effect(ops: &[(Key, Op)]) -> ExecutionEffect {
+        let ops: AdditiveMap<Key, Op> = ops.iter().copied().collect();
+        ExecutionEffect::new(ops, AdditiveMap::new())
+    }
+
+    #[test]
+    fn should_commute_on_disjoint_keys() {
+        let effects = vec![
+            effect(&[(Key::Hash([1; 32]), Op::Write)]),
+            effect(&[(Key::Hash([2; 32]), Op::Write)]),
+        ];
+        let conflicts = DeployConflicts::from_effects(&effects);
+        assert_eq!(conflicts.deploy_count(), 2);
+        assert!(conflicts.is_empty());
+        assert!(conflicts.commute(0, 1));
+    }
+
+    #[test]
+    fn should_commute_on_reads_and_adds() {
+        let read_key = Key::Hash([1; 32]);
+        let add_key = Key::Hash([2; 32]);
+        let effects = vec![
+            effect(&[(read_key, Op::Read), (add_key, Op::Add)]),
+            effect(&[(read_key, Op::Read), (add_key, Op::Add)]),
+            effect(&[(read_key, Op::NoOp), (add_key, Op::NoOp)]),
+        ];
+        assert!(DeployConflicts::from_effects(&effects).is_empty());
+    }
+
+    #[test]
+    fn should_find_conflicts() {
+        let key = Key::Hash([1; 32]);
+        let other_key = Key::Hash([2; 32]);
+        let effects = vec![
+            effect(&[(key, Op::Read)]),
+            effect(&[(other_key, Op::Write)]),
+            effect(&[(key, Op::Add)]),
+            effect(&[(key, Op::Write), (other_key, Op::Read)]),
+        ];
+        let conflicts = DeployConflicts::from_effects(&effects);
+
+        assert_eq!(
+            conflicts.pairs().collect::<Vec<_>>(),
+            vec![(0, 2), (0, 3), (1, 3), (2, 3)]
+        );
+        assert!(conflicts.commute(0, 1));
+        assert!(!conflicts.commute(3, 1));
+        assert_eq!(
+            conflicts.conflicting_with(3).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(conflicts.conflicting_with(1).collect::<Vec<_>>(), vec![3]);
+    }
+}
@@ -1,7 +1,11 @@
+use engine_storage::trie_store::cache::DEFAULT_TRIE_CACHE_SIZE;
+
 /// The runtime configuration of the execution engine
 #[derive(Debug, Clone)]
 pub struct EngineConfig {
     // feature flags go here
+    trie_cache_size: usize,
+    execution_threads: usize,
 }
 
 impl EngineConfig {
@@ -9,10 +13,37 @@ impl EngineConfig {
     pub fn new() -> EngineConfig {
         Default::default()
     }
+
+    /// Returns the maximum size in bytes of the decoded trie nodes cached by global state.
+    pub fn trie_cache_size(&self) -> usize {
+        self.trie_cache_size
+    }
+
+    /// Sets the maximum size in bytes of the decoded trie nodes cached by global state.  A size
+    /// of zero disables the cache.
+    pub fn with_trie_cache_size(mut self, trie_cache_size: usize) -> EngineConfig {
+        self.trie_cache_size = trie_cache_size;
+        self
+    }
+
+    /// Returns the number of threads used to execute the deploys of a single request.
+    pub fn execution_threads(&self) -> usize {
+        self.execution_threads
+    }
+
+    /// Sets the number of threads used to execute the deploys of a single request.  With more
+    /// than one thread the deploys are executed in parallel; zero is treated as one.
+    pub fn with_execution_threads(mut self, execution_threads: usize) -> EngineConfig {
+        self.execution_threads = execution_threads;
+        self
+    }
 }
 
 impl Default for EngineConfig {
     fn default() -> Self {
-        EngineConfig {}
+        EngineConfig {
+            trie_cache_size: DEFAULT_TRIE_CACHE_SIZE,
+            execution_threads: 1,
+        }
     }
 }
@@ -1,3 +1,5 @@
+use std::fmt::{self, Display, Formatter};
+
 use failure::Fail;
 
 use engine_shared::newtypes::Blake2bHash;
@@ -28,6 +30,11 @@ pub enum Error {
     AuthorizationError,
     #[fail(display = "Insufficient payment")]
     InsufficientPaymentError,
+    #[fail(
+        display = "Gas price {} is below the minimum of {}",
+        gas_price, min_conv_rate
+    )]
+    InvalidGasPrice { gas_price: u64, min_conv_rate: u64 },
     #[fail(display = "Deploy error")]
     DeployError,
     #[fail(display = "Payment finalization error")]
@@ -38,6 +45,9 @@ pub enum Error {
     SerializationError(bytesrepr::Error),
     #[fail(display = "Mint error: {}", _0)]
     MintError(mint::Error),
+    /// An error rendered on the thread which raised it.
+    #[fail(display = "{}", _0)]
+    Rendered(RenderedError),
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
@@ -84,3 +94,114 @@ impl From<!> for Error {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct RootNotFound(pub Blake2bHash);
+
+/// How a deploy which failed with an [`Error`] is reported.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The deploy could not be executed, so it has no effects and costs nothing.
+    Precondition,
+    /// The deploy ran out of gas.
+    OutOfGas,
+    /// The deploy failed while executing.
+    Execution,
+}
+
+/// An [`Error`] rendered as the kind and message it is reported with.
+///
+/// Unlike an `Error`, which may hold a `wasmi` error, it owns all of its data, so it can be sent
+/// between threads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedError {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl RenderedError {
+    pub fn new(kind: ErrorKind, message: String) -> Self {
+        RenderedError { kind, message }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for RenderedError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl From<&Error> for RenderedError {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::InvalidHashLength { .. }
+            | Error::InvalidPublicKeyLength { .. }
+            | Error::InvalidProtocolVersion(_)
+            | Error::InvalidUpgradeConfig
+            | Error::InvalidGasPrice { .. }
+            | Error::WasmPreprocessingError(_)
+            | Error::WasmSerializationError(_)
+            | Error::ExecError(execution::Error::DeploymentAuthorizationFailure)
+            | Error::AuthorizationError => {
+                RenderedError::new(ErrorKind::Precondition, error.to_string())
+            }
+            // TODO(mateusz.gorski): Fix error model for the storage errors.
+            // We don't have separate IPC messages for storage errors so for the time being they
+            // are all reported as "wasm errors".
+            Error::StorageError(storage_error) => {
+                RenderedError::new(ErrorKind::Execution, storage_error.to_string())
+            }
+            Error::MissingSystemContractError(msg) => {
+                RenderedError::new(ErrorKind::Execution, msg.clone())
+            }
+            Error::InsufficientPaymentError
+            | Error::DeployError
+            | Error::FinalizationError
+            | Error::SerializationError(_)
+            | Error::MintError(_) => RenderedError::new(ErrorKind::Execution, error.to_string()),
+            Error::ExecError(exec_error) => exec_error.into(),
+            Error::Rendered(rendered) => rendered.clone(),
+        }
+    }
+}
+
+impl From<&execution::Error> for RenderedError {
+    fn from(error: &execution::Error) -> Self {
+        let message = match error {
+            execution::Error::GasLimit => {
+                return RenderedError::new(ErrorKind::OutOfGas, format!("{:?}", error))
+            }
+            execution::Error::KeyNotFound(key) => format!("Key {:?} not found.", key),
+            execution::Error::Revert(status) => format!("Exit code: {}", status),
+            execution::Error::Interpreter(error) => {
+                // If the error happens during contract execution it's mapped to HostError and
+                // wrapped in Interpreter error, so we may end up with
+                // InterpreterError(HostError(InterpreterError))).  In order to provide clear error
+                // messages we have to downcast and match on the inner error, otherwise we end up
+                // with `Host(Trap(Trap(TrapKind:InterpreterError)))`.
+                // TODO: This really should be happening in the `Executor::exec`.
+                match error
+                    .as_host_error()
+                    .and_then(|host_error| host_error.downcast_ref::<execution::Error>())
+                {
+                    Some(execution::Error::Revert(status)) => format!("Exit code: {}", status),
+                    Some(execution::Error::KeyNotFound(key)) => format!("Key {:?} not found.", key),
+                    Some(execution::Error::InvalidContext) => {
+                        // TODO: https://casperlabs.atlassian.net/browse/EE-771
+                        "Invalid execution context.".to_string()
+                    }
+                    Some(other) => format!("{:?}", other),
+                    None => format!("{:?}", error),
+                }
+            }
+            // TODO(mateusz.gorski): Be more specific about execution errors
+            other => format!("{:?}", other),
+        };
+        RenderedError::new(ErrorKind::Execution, message)
+    }
+}
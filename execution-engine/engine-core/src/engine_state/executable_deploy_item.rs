@@ -1,3 +1,5 @@
+use types::DEFAULT_ENTRY_POINT;
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum ExecutableDeployItem {
     ModuleBytes {
@@ -6,14 +8,17 @@ pub enum ExecutableDeployItem {
     },
     StoredContractByHash {
         hash: Vec<u8>,
+        entry_point: String,
         args: Vec<u8>,
     },
     StoredContractByName {
         name: String,
+        entry_point: String,
         args: Vec<u8>,
     },
     StoredContractByURef {
         uref: Vec<u8>,
+        entry_point: String,
         args: Vec<u8>,
     },
 }
@@ -27,4 +32,24 @@ impl ExecutableDeployItem {
             ExecutableDeployItem::StoredContractByURef { args, .. } => args,
         }
     }
+
+    pub fn args(&self) -> &[u8] {
+        match self {
+            ExecutableDeployItem::ModuleBytes { args, .. } => args,
+            ExecutableDeployItem::StoredContractByHash { args, .. } => args,
+            ExecutableDeployItem::StoredContractByName { args, .. } => args,
+            ExecutableDeployItem::StoredContractByURef { args, .. } => args,
+        }
+    }
+
+    /// Returns the name of the entry point to run; for module bytes, this is always
+    /// [`DEFAULT_ENTRY_POINT`].
+    pub fn entry_point(&self) -> &str {
+        match self {
+            ExecutableDeployItem::ModuleBytes { .. } => DEFAULT_ENTRY_POINT,
+            ExecutableDeployItem::StoredContractByHash { entry_point, .. }
+            | ExecutableDeployItem::StoredContractByName { entry_point, .. }
+            | ExecutableDeployItem::StoredContractByURef { entry_point, .. } => entry_point,
+        }
+    }
 }
@@ -0,0 +1,23 @@
+use std::collections::BTreeSet;
+
+use super::execute_request::ExecuteRequest;
+
+pub struct ExecuteAndCommitRequest {
+    /// The deploys to execute.  They are executed chained if its `chain_deploys` is `true`, and
+    /// each on its own against the parent state otherwise.
+    pub execute_request: ExecuteRequest,
+    /// The positions in `execute_request` of the deploys whose effects are committed.  Positions
+    /// without a deploy are ignored.
+    pub committed_deploys: BTreeSet<usize>,
+}
+
+impl ExecuteAndCommitRequest {
+    /// Creates a request committing the effects of every deploy in `execute_request`.
+    pub fn new(execute_request: ExecuteRequest) -> Self {
+        let committed_deploys = (0..execute_request.deploys.len()).collect();
+        ExecuteAndCommitRequest {
+            execute_request,
+            committed_deploys,
+        }
+    }
+}
@@ -10,6 +10,13 @@ pub struct ExecuteRequest {
     pub block_time: u64,
     pub deploys: Vec<Result<DeployItem, ExecutionResult>>,
     pub protocol_version: ProtocolVersion,
+    /// If `true`, each deploy is executed on top of the effects of the deploys before it rather
+    /// than on the parent state alone.  Such requests are executed by
+    /// `EngineState::run_execute_chained`.
+    pub chain_deploys: bool,
+    /// If `true`, the host function calls made while executing each deploy are recorded in its
+    /// result.  Meant for debugging failed deploys, as tracing slows execution down.
+    pub trace: bool,
 }
 
 impl ExecuteRequest {
@@ -24,6 +31,8 @@ impl ExecuteRequest {
             block_time,
             deploys,
             protocol_version,
+            chain_deploys: false,
+            trace: false,
         }
     }
 
@@ -39,6 +48,8 @@ impl Default for ExecuteRequest {
             block_time: 0,
             deploys: vec![],
             protocol_version: Default::default(),
+            chain_deploys: false,
+            trace: false,
         }
     }
 }
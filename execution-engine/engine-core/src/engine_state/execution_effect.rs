@@ -13,4 +13,18 @@ impl ExecutionEffect {
     pub fn new(ops: AdditiveMap<Key, Op>, transforms: AdditiveMap<Key, Transform>) -> Self {
         ExecutionEffect { ops, transforms }
     }
+
+    /// Returns `true` if every key touched by both `self` and `other` is touched by commuting
+    /// operations, i.e. the two effects can be applied in either order.
+    pub fn commutes_with(&self, other: &ExecutionEffect) -> bool {
+        let (smaller, larger) = if self.ops.len() <= other.ops.len() {
+            (&self.ops, &other.ops)
+        } else {
+            (&other.ops, &self.ops)
+        };
+        smaller.iter().all(|(key, op)| match larger.get(key) {
+            Some(other_op) => op.commutes_with(*other_op),
+            None => true,
+        })
+    }
 }
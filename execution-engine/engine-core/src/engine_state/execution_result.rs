@@ -1,10 +1,14 @@
-use super::{error, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
+use std::mem;
+
+use super::{error, execution_effect::ExecutionEffect, op::Op};
 use engine_shared::{
     additive_map::AdditiveMap, gas::Gas, motes::Motes, newtypes::CorrelationId,
     stored_value::StoredValue, transform::Transform,
 };
 use engine_storage::global_state::StateReader;
-use types::{CLValue, Key};
+use types::{CLValue, Key, Phase};
+
+use crate::execution::{CallFrame, Event};
 
 fn make_payment_error_effects(
     max_payment_cost: Motes,
@@ -38,6 +42,81 @@ fn make_payment_error_effects(
     ExecutionEffect::new(ops, transforms)
 }
 
+/// Where the gas and motes of a deploy went.
+///
+/// Only the results of whole deploys carry a breakdown; it is zero for the result of running a
+/// single phase.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct CostBreakdown {
+    payment_gas: Gas,
+    session_gas: Gas,
+    finalize_gas: Gas,
+    motes_charged: Motes,
+    motes_refunded: Motes,
+}
+
+impl CostBreakdown {
+    pub fn new(
+        payment_gas: Gas,
+        session_gas: Gas,
+        finalize_gas: Gas,
+        motes_charged: Motes,
+        motes_refunded: Motes,
+    ) -> Self {
+        CostBreakdown {
+            payment_gas,
+            session_gas,
+            finalize_gas,
+            motes_charged,
+            motes_refunded,
+        }
+    }
+
+    /// Returns the gas used by the given phase of the deploy.
+    pub fn gas(&self, phase: Phase) -> Gas {
+        match phase {
+            Phase::System => Gas::default(),
+            Phase::Payment => self.payment_gas,
+            Phase::Session => self.session_gas,
+            Phase::FinalizePayment => self.finalize_gas,
+        }
+    }
+
+    pub fn payment_gas(&self) -> Gas {
+        self.payment_gas
+    }
+
+    pub fn session_gas(&self) -> Gas {
+        self.session_gas
+    }
+
+    /// Returns the gas used by finalization, which the account is not charged for.
+    pub fn finalize_gas(&self) -> Gas {
+        self.finalize_gas
+    }
+
+    /// Returns the motes the account paid into the rewards purse.
+    pub fn motes_charged(&self) -> Motes {
+        self.motes_charged
+    }
+
+    /// Returns the motes of the payment which were returned to the account, or its refund purse.
+    pub fn motes_refunded(&self) -> Motes {
+        self.motes_refunded
+    }
+}
+
+/// What the results of successful and failed executions carry alike, besides their effect and
+/// cost.
+#[derive(Debug, Default)]
+pub struct ExecutionDetails {
+    pub cost_breakdown: CostBreakdown,
+    /// The host calls made by each phase run, if executed with tracing enabled
+    pub trace: Vec<CallFrame>,
+    /// The events emitted by the contracts run, in order
+    pub events: Vec<Event>,
+}
+
 #[derive(Debug)]
 pub enum ExecutionResult {
     /// An error condition that happened during execution
@@ -45,9 +124,14 @@ pub enum ExecutionResult {
         error: error::Error,
         effect: ExecutionEffect,
         cost: Gas,
+        details: ExecutionDetails,
     },
     /// Execution was finished successfully
-    Success { effect: ExecutionEffect, cost: Gas },
+    Success {
+        effect: ExecutionEffect,
+        cost: Gas,
+        details: ExecutionDetails,
+    },
 }
 
 pub enum ForcedTransferResult {
@@ -66,6 +150,7 @@ impl ExecutionResult {
             error,
             effect: Default::default(),
             cost: Gas::default(),
+            details: Default::default(),
         }
     }
 
@@ -106,26 +191,71 @@ impl ExecutionResult {
         }
     }
 
-    pub fn with_cost(self, cost: Gas) -> Self {
+    pub fn details(&self) -> &ExecutionDetails {
         match self {
-            ExecutionResult::Failure { error, effect, .. } => ExecutionResult::Failure {
-                error,
-                effect,
-                cost,
-            },
-            ExecutionResult::Success { effect, .. } => ExecutionResult::Success { effect, cost },
+            ExecutionResult::Failure { details, .. } => details,
+            ExecutionResult::Success { details, .. } => details,
         }
     }
 
-    pub fn with_effect(self, effect: ExecutionEffect) -> Self {
+    fn details_mut(&mut self) -> &mut ExecutionDetails {
         match self {
-            ExecutionResult::Failure { error, cost, .. } => ExecutionResult::Failure {
-                error,
-                effect,
-                cost,
-            },
-            ExecutionResult::Success { cost, .. } => ExecutionResult::Success { effect, cost },
+            ExecutionResult::Failure { details, .. } => details,
+            ExecutionResult::Success { details, .. } => details,
+        }
+    }
+
+    pub fn cost_breakdown(&self) -> CostBreakdown {
+        self.details().cost_breakdown
+    }
+
+    pub fn trace(&self) -> &[CallFrame] {
+        &self.details().trace
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.details().events
+    }
+
+    pub fn with_cost(mut self, new_cost: Gas) -> Self {
+        match &mut self {
+            ExecutionResult::Failure { cost, .. } => *cost = new_cost,
+            ExecutionResult::Success { cost, .. } => *cost = new_cost,
+        }
+        self
+    }
+
+    pub fn with_effect(mut self, new_effect: ExecutionEffect) -> Self {
+        match &mut self {
+            ExecutionResult::Failure { effect, .. } => *effect = new_effect,
+            ExecutionResult::Success { effect, .. } => *effect = new_effect,
         }
+        self
+    }
+
+    pub fn with_cost_breakdown(mut self, cost_breakdown: CostBreakdown) -> Self {
+        self.details_mut().cost_breakdown = cost_breakdown;
+        self
+    }
+
+    pub fn with_trace(mut self, trace: Vec<CallFrame>) -> Self {
+        self.details_mut().trace = trace;
+        self
+    }
+
+    pub fn with_events(mut self, events: Vec<Event>) -> Self {
+        self.details_mut().events = events;
+        self
+    }
+
+    /// Removes and returns the trace, leaving this result with none.
+    pub fn take_trace(&mut self) -> Vec<CallFrame> {
+        mem::replace(&mut self.details_mut().trace, Vec::new())
+    }
+
+    /// Removes and returns the events, leaving this result with none.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        mem::replace(&mut self.details_mut().events, Vec::new())
     }
 
     pub fn error(&self) -> Option<&error::Error> {
@@ -147,10 +277,11 @@ impl ExecutionResult {
     pub fn check_forced_transfer(
         &self,
         payment_purse_balance: Motes,
+        conv_rate: u64,
     ) -> Option<ForcedTransferResult> {
-        let payment_result_cost = match Motes::from_gas(self.cost(), CONV_RATE) {
+        let payment_result_cost = match Motes::from_gas(self.cost(), conv_rate) {
             Some(cost) => cost,
-            // Multiplying cost by CONV_RATE overflowed the U512 range
+            // Multiplying cost by conv_rate overflowed the U512 range
             None => return Some(ForcedTransferResult::InsufficientPayment),
         };
         // payment_code_spec_3_b_ii: if (balance of PoS pay purse) < (gas spent during
@@ -179,6 +310,7 @@ impl ExecutionResult {
         account_main_purse_balance: Motes,
         account_main_purse: Key,
         rewards_purse: Key,
+        conv_rate: u64,
     ) -> ExecutionResult {
         let effect = make_payment_error_effects(
             max_payment_cost,
@@ -186,11 +318,23 @@ impl ExecutionResult {
             account_main_purse,
             rewards_purse,
         );
-        let cost = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+        let cost = Gas::from_motes(max_payment_cost, conv_rate).unwrap_or_default();
+        // The whole payment is forfeit, so it is all reported as spent by the payment code
+        let cost_breakdown = CostBreakdown::new(
+            cost,
+            Gas::default(),
+            Gas::default(),
+            max_payment_cost,
+            Motes::default(),
+        );
         ExecutionResult::Failure {
             error,
             effect,
             cost,
+            details: ExecutionDetails {
+                cost_breakdown,
+                ..Default::default()
+            },
         }
     }
 }
@@ -267,17 +411,21 @@ impl ExecutionResultBuilder {
         let mut ops = AdditiveMap::new();
         let mut transforms = AdditiveMap::new();
 
+        let mut events = Vec::new();
+
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
             cost,
+            details: Default::default(),
         };
 
         match self.payment_execution_result {
-            Some(result) => {
+            Some(mut result) => {
                 if result.is_failure() {
                     return Ok(result);
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    events.append(&mut result.take_events());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -286,18 +434,19 @@ impl ExecutionResultBuilder {
         // session_code_spec_3: only include session exec effects if there is no session
         // exec error
         match self.session_execution_result {
-            Some(result) => {
+            Some(mut result) => {
                 if result.is_failure() {
                     ret = result.with_cost(cost);
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    events.append(&mut result.take_events());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
         };
 
         match self.finalize_execution_result {
-            Some(result) => {
+            Some(mut result) => {
                 if result.is_failure() {
                     // payment_code_spec_5_a: FinalizationError should only ever be raised here
                     return Ok(ExecutionResult::precondition_failure(
@@ -305,15 +454,21 @@ impl ExecutionResultBuilder {
                     ));
                 } else {
                     Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    events.append(&mut result.take_events());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
         }
 
+        // A deploy whose session code fails emits no events, not even those of its payment code
+        if ret.is_failure() {
+            events.clear();
+        }
+
         // Remove redundant writes to allow more opportunity to commute
         let reduced_effect = Self::reduce_identity_writes(ops, transforms, reader, correlation_id);
 
-        Ok(ret.with_effect(reduced_effect))
+        Ok(ret.with_effect(reduced_effect).with_events(events))
     }
 
     fn add_effects(
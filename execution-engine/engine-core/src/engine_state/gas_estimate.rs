@@ -0,0 +1,70 @@
+use engine_shared::{gas::Gas, motes::Motes};
+
+use super::error::Error;
+
+/// The gas a deploy's payment and session code used when dry-run by
+/// [`EngineState::estimate_gas`](super::EngineState::estimate_gas).
+#[derive(Debug)]
+pub struct GasEstimate {
+    payment_cost: Gas,
+    session_cost: Gas,
+    conv_rate: u64,
+    error: Option<Error>,
+}
+
+impl GasEstimate {
+    pub fn new(payment_cost: Gas, session_cost: Gas, conv_rate: u64, error: Option<Error>) -> Self {
+        GasEstimate {
+            payment_cost,
+            session_cost,
+            conv_rate,
+            error,
+        }
+    }
+
+    /// Constructs a [`GasEstimate`] for a deploy which could not be run at all, e.g. because of
+    /// invalid Wasm or missing authorization.
+    pub fn precondition_failure(error: Error) -> Self {
+        GasEstimate::new(Gas::default(), Gas::default(), 0, Some(error))
+    }
+
+    /// Returns the gas used by the payment code.
+    pub fn payment_cost(&self) -> Gas {
+        self.payment_cost
+    }
+
+    /// Returns the gas used by the session code, zero if it was not run.
+    pub fn session_cost(&self) -> Gas {
+        self.session_cost
+    }
+
+    /// Returns the gas used by the payment and session code together.
+    pub fn cost(&self) -> Gas {
+        self.payment_cost + self.session_cost
+    }
+
+    /// Returns the rate, in motes per unit of gas, the deploy would pay at given its gas price.
+    pub fn conv_rate(&self) -> u64 {
+        self.conv_rate
+    }
+
+    /// Returns the payment which covers [`cost`](GasEstimate::cost) at the deploy's
+    /// [`conv_rate`](GasEstimate::conv_rate), or `None` on overflow.
+    pub fn suggested_payment(&self) -> Option<Motes> {
+        Motes::from_gas(self.cost(), self.conv_rate)
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the error which stopped the dry run, in which case the costs are those measured up
+    /// to the failure.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    pub fn take_error(self) -> Option<Error> {
+        self.error
+    }
+}
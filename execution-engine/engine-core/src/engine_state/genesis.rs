@@ -6,9 +6,13 @@ use rand::{
     Rng,
 };
 
-use engine_shared::{motes::Motes, newtypes::Blake2bHash, transform::TypeMismatch};
+use engine_shared::{
+    conv_rates::ConvRates, motes::Motes, newtypes::Blake2bHash, transform::TypeMismatch,
+};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{
+    host_function_costs::HostFunctionCosts, storage_costs::StorageCosts, wasm_costs::WasmCosts,
+};
 use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
@@ -117,6 +121,9 @@ pub struct GenesisConfig {
     proof_of_stake_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    host_function_costs: HostFunctionCosts,
+    storage_costs: StorageCosts,
+    conv_rates: ConvRates,
 }
 
 impl GenesisConfig {
@@ -128,6 +135,9 @@ impl GenesisConfig {
         proof_of_stake_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        host_function_costs: HostFunctionCosts,
+        storage_costs: StorageCosts,
+        conv_rates: ConvRates,
     ) -> Self {
         GenesisConfig {
             name,
@@ -137,6 +147,9 @@ impl GenesisConfig {
             proof_of_stake_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            conv_rates,
         }
     }
 
@@ -164,6 +177,18 @@ impl GenesisConfig {
         self.wasm_costs
     }
 
+    pub fn host_function_costs(&self) -> HostFunctionCosts {
+        self.host_function_costs
+    }
+
+    pub fn storage_costs(&self) -> StorageCosts {
+        self.storage_costs
+    }
+
+    pub fn conv_rates(&self) -> ConvRates {
+        self.conv_rates
+    }
+
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (PublicKey, Motes)> + '_ {
         let zero = Motes::zero();
         self.accounts.iter().filter_map(move |genesis_account| {
@@ -218,6 +243,55 @@ impl Distribution<GenesisConfig> for Standard {
             opcodes_div: rng.gen(),
         };
 
+        let host_function_costs = HostFunctionCosts {
+            read_value: rng.gen(),
+            read_value_local: rng.gen(),
+            write: rng.gen(),
+            write_local: rng.gen(),
+            add: rng.gen(),
+            add_local: rng.gen(),
+            new_uref: rng.gen(),
+            load_named_keys: rng.gen(),
+            ret: rng.gen(),
+            get_key: rng.gen(),
+            has_key: rng.gen(),
+            put_key: rng.gen(),
+            remove_key: rng.gen(),
+            call_contract: rng.gen(),
+            get_arg_size: rng.gen(),
+            get_arg: rng.gen(),
+            get_caller: rng.gen(),
+            get_blocktime: rng.gen(),
+            store_function: rng.gen(),
+            store_function_at_hash: rng.gen(),
+            is_valid_uref: rng.gen(),
+            revert: rng.gen(),
+            add_associated_key: rng.gen(),
+            remove_associated_key: rng.gen(),
+            update_associated_key: rng.gen(),
+            set_action_threshold: rng.gen(),
+            create_purse: rng.gen(),
+            transfer_to_account: rng.gen(),
+            transfer_from_purse_to_account: rng.gen(),
+            transfer_from_purse_to_purse: rng.gen(),
+            get_balance: rng.gen(),
+            get_phase: rng.gen(),
+            upgrade_contract_at_uref: rng.gen(),
+            get_system_contract: rng.gen(),
+            get_main_purse: rng.gen(),
+            read_host_buffer: rng.gen(),
+            emit_event: rng.gen(),
+            store_contract: rng.gen(),
+            store_contract_at_hash: rng.gen(),
+            try_call_contract: rng.gen(),
+            read_value_byte: rng.gen(),
+            event_byte: rng.gen(),
+        };
+
+        let storage_costs = StorageCosts::new(rng.gen());
+
+        let conv_rates = ConvRates::new(rng.gen(), rng.gen());
+
         GenesisConfig {
             name,
             timestamp,
@@ -226,6 +300,9 @@ impl Distribution<GenesisConfig> for Standard {
             proof_of_stake_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            conv_rates,
         }
     }
 }
@@ -1,20 +1,25 @@
+pub mod conflicts;
 pub mod deploy_item;
 pub mod engine_config;
 mod error;
 pub mod executable_deploy_item;
+pub mod execute_and_commit_request;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
+pub mod gas_estimate;
 pub mod genesis;
 pub mod op;
 pub mod query;
 pub mod system_contract_cache;
 pub mod upgrade;
 pub mod utils;
+pub mod view;
 
 use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet, HashMap},
+    panic,
     rc::Rc,
 };
 
@@ -25,6 +30,7 @@ use contract::args_parser::ArgsParser;
 use engine_shared::{
     account::Account,
     additive_map::AdditiveMap,
+    conv_rates::{ConvRates, DEFAULT_CONV_RATE},
     gas::Gas,
     motes::Motes,
     newtypes::{Blake2bHash, CorrelationId},
@@ -32,10 +38,13 @@ use engine_shared::{
     transform::Transform,
 };
 use engine_storage::{
-    global_state::{CommitResult, StateProvider, StateReader},
+    global_state::{CommitResult, PutTrieResult, StateProvider, StateReader},
     protocol_data::ProtocolData,
 };
-use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
+use engine_wasm_prep::{
+    host_function_costs::HostFunctionCosts, storage_costs::StorageCosts, wasm_costs::WasmCosts,
+    Preprocessor,
+};
 use types::{
     account::{PublicKey, PurseId},
     bytesrepr::ToBytes,
@@ -47,36 +56,119 @@ use types::{
 use self::{
     deploy_item::DeployItem,
     executable_deploy_item::ExecutableDeployItem,
-    execution_result::{ExecutionResult, ForcedTransferResult},
+    execution_effect::ExecutionEffect,
+    execution_result::{CostBreakdown, ExecutionDetails, ExecutionResult, ForcedTransferResult},
+    gas_estimate::GasEstimate,
     genesis::{GenesisAccount, GenesisConfig, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE},
     system_contract_cache::SystemContractCache,
 };
 pub use self::{
     engine_config::EngineConfig,
-    error::{Error, RootNotFound},
+    error::{Error, ErrorKind, RenderedError, RootNotFound},
 };
 use crate::{
     engine_state::{
         error::Error::MissingSystemContractError,
+        execute_and_commit_request::ExecuteAndCommitRequest,
         execute_request::ExecuteRequest,
         query::{QueryRequest, QueryResult},
         upgrade::{UpgradeConfig, UpgradeResult},
+        view::{ViewRequest, ViewResult},
     },
     execution::{self, AddressGenerator, Executor, MINT_NAME, POS_NAME},
-    tracking_copy::{TrackingCopy, TrackingCopyExt},
+    tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
     KnownKeys,
 };
 
-// TODO?: MAX_PAYMENT && CONV_RATE values are currently arbitrary w/ real values
-// TBD gas * CONV_RATE = motes
+// TODO?: MAX_PAYMENT value is currently arbitrary w/ real value TBD
 pub const MAX_PAYMENT: u64 = 10_000_000;
-pub const CONV_RATE: u64 = 10;
+/// The rate, in motes per unit of gas, paid by deploys which don't set a gas price, unless changed
+/// at genesis or by an upgrade.
+pub const CONV_RATE: u64 = DEFAULT_CONV_RATE;
 
 pub const SYSTEM_ACCOUNT_ADDR: [u8; 32] = [0u8; 32];
 
 const GENESIS_INITIAL_BLOCKTIME: u64 = 0;
 const MINT_METHOD_NAME: &str = "mint";
 
+const EXECUTION_THREADS_JOINED_EXPECT: &str = "all execution threads should have been joined";
+const EXECUTION_RESULT_EXPECT: &str = "every deploy should have a result";
+
+/// The result of a deploy executed on a worker thread of
+/// [`EngineState::run_execute_parallel`].
+///
+/// An `ExecutionResult` can't be sent between threads, as `wasmi` boxes host errors without a
+/// `Send` bound, so the worker sends the data of the result with its error rendered.
+struct WorkerResult {
+    error: Option<RenderedError>,
+    effect: ExecutionEffect,
+    cost: Gas,
+    details: ExecutionDetails,
+}
+
+impl From<ExecutionResult> for WorkerResult {
+    fn from(execution_result: ExecutionResult) -> Self {
+        match execution_result {
+            ExecutionResult::Failure {
+                error,
+                effect,
+                cost,
+                details,
+            } => WorkerResult {
+                error: Some(RenderedError::from(&error)),
+                effect,
+                cost,
+                details,
+            },
+            ExecutionResult::Success {
+                effect,
+                cost,
+                details,
+            } => WorkerResult {
+                error: None,
+                effect,
+                cost,
+                details,
+            },
+        }
+    }
+}
+
+impl From<WorkerResult> for ExecutionResult {
+    fn from(worker_result: WorkerResult) -> Self {
+        let WorkerResult {
+            error,
+            effect,
+            cost,
+            details,
+        } = worker_result;
+        match error {
+            Some(rendered_error) => ExecutionResult::Failure {
+                error: Error::Rendered(rendered_error),
+                effect,
+                cost,
+                details,
+            },
+            None => ExecutionResult::Success {
+                effect,
+                cost,
+                details,
+            },
+        }
+    }
+}
+
+/// Returns the rate at which a deploy with the given gas price pays for gas under `protocol_data`.
+fn deploy_conv_rate(protocol_data: &ProtocolData, gas_price: u64) -> Result<u64, Error> {
+    let conv_rates = protocol_data.conv_rates();
+    conv_rates
+        .conv_rate(gas_price)
+        .ok_or_else(|| Error::InvalidGasPrice {
+            gas_price,
+            min_conv_rate: conv_rates.min_conv_rate(),
+        })
+}
+
 #[derive(Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
@@ -112,6 +204,44 @@ where
         }
     }
 
+    /// Returns a preprocessor applying the wasm costs of `protocol_version`.
+    fn preprocessor(&self, protocol_version: ProtocolVersion) -> Result<Preprocessor, Error> {
+        match self.wasm_costs(protocol_version)? {
+            Some(wasm_costs) => Ok(Preprocessor::new(wasm_costs)),
+            None => Err(Error::InvalidProtocolVersion(protocol_version)),
+        }
+    }
+
+    pub fn host_function_costs(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<HostFunctionCosts>, Error> {
+        match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => Ok(Some(*protocol_data.host_function_costs())),
+            None => Ok(None),
+        }
+    }
+
+    pub fn storage_costs(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<StorageCosts>, Error> {
+        match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => Ok(Some(*protocol_data.storage_costs())),
+            None => Ok(None),
+        }
+    }
+
+    pub fn conv_rates(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ConvRates>, Error> {
+        match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => Ok(Some(*protocol_data.conv_rates())),
+            None => Ok(None),
+        }
+    }
+
     pub fn get_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -253,6 +383,9 @@ where
             // Constructs a partial protocol data with already known urefs to pass the validation
             // step
             let partial_protocol_data = ProtocolData::new(
+                Default::default(),
+                Default::default(),
+                Default::default(),
                 Default::default(),
                 mint_reference,
                 // This is used as unknown key
@@ -280,7 +413,14 @@ where
         };
 
         // Spec #2: Associate given CostTable with given ProtocolVersion.
-        let protocol_data = ProtocolData::new(wasm_costs, mint_reference, proof_of_stake_reference);
+        let protocol_data = ProtocolData::new(
+            wasm_costs,
+            genesis_config.host_function_costs(),
+            genesis_config.storage_costs(),
+            genesis_config.conv_rates(),
+            mint_reference,
+            proof_of_stake_reference,
+        );
 
         self.state
             .put_protocol_data(protocol_version, &protocol_data)
@@ -342,8 +482,10 @@ where
                 let contract = tracking_copy
                     .borrow_mut()
                     .get_contract(correlation_id, Key::URef(mint_reference))?;
-                let (bytes, _, _) = contract.destructure();
-                engine_wasm_prep::deserialize(&bytes)?
+                let contract_wasm = tracking_copy
+                    .borrow_mut()
+                    .get_contract_wasm(correlation_id, contract.wasm_hash())?;
+                engine_wasm_prep::deserialize(contract_wasm.bytes())?
             };
 
             // For each account...
@@ -469,9 +611,27 @@ where
             None => *current_protocol_data.wasm_costs(),
         };
 
+        let new_host_function_costs = match upgrade_config.host_function_costs() {
+            Some(new_host_function_costs) => new_host_function_costs,
+            None => *current_protocol_data.host_function_costs(),
+        };
+
+        let new_storage_costs = match upgrade_config.storage_costs() {
+            Some(new_storage_costs) => new_storage_costs,
+            None => *current_protocol_data.storage_costs(),
+        };
+
+        let new_conv_rates = match upgrade_config.conv_rates() {
+            Some(new_conv_rates) => new_conv_rates,
+            None => *current_protocol_data.conv_rates(),
+        };
+
         // 3.1.2.2 persist wasm CostTable
         let new_protocol_data = ProtocolData::new(
             new_wasm_costs,
+            new_host_function_costs,
+            new_storage_costs,
+            new_conv_rates,
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
         );
@@ -605,10 +765,149 @@ where
 
         let mut mut_tracking_copy = tracking_copy.borrow_mut();
 
-        Ok(mut_tracking_copy
-            .query(correlation_id, query_request.key(), query_request.path())
-            .map_err(|err| Error::ExecError(err.into()))?
-            .into())
+        let mut query_result: QueryResult = if query_request.include_proofs() {
+            mut_tracking_copy
+                .query_with_proof(correlation_id, query_request.key(), query_request.path())
+                .map_err(|err| Error::ExecError(err.into()))?
+                .into()
+        } else {
+            mut_tracking_copy
+                .query(correlation_id, query_request.key(), query_request.path())
+                .map_err(|err| Error::ExecError(err.into()))?
+                .into()
+        };
+
+        // Contracts only refer to their code by hash, so resolve it for the caller.
+        if let QueryResult::Success {
+            value: StoredValue::Contract(contract),
+            contract_wasm,
+            ..
+        } = &mut query_result
+        {
+            let wasm = mut_tracking_copy.get_contract_wasm(correlation_id, contract.wasm_hash())?;
+            *contract_wasm = Some(wasm);
+        }
+
+        Ok(query_result)
+    }
+
+    /// Calls the contract stored under the request's key without a deploy, returning the value
+    /// the contract passes to `runtime::ret`.
+    ///
+    /// The contract runs in a read-only context, so it fails as soon as it tries to write to, add
+    /// to or put keys into global state, and it may use no more than the request's gas limit.
+    pub fn run_view(
+        &self,
+        correlation_id: CorrelationId,
+        mut view_request: ViewRequest,
+    ) -> Result<ViewResult, Error> {
+        let tracking_copy = match self.tracking_copy(view_request.state_hash())? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(ViewResult::RootNotFound),
+        };
+
+        let failure = |error: Error| ViewResult::Failure {
+            error,
+            cost: Gas::default(),
+        };
+
+        let protocol_version = view_request.protocol_version();
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => return Ok(failure(Error::InvalidProtocolVersion(protocol_version))),
+        };
+
+        let account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, view_request.caller().value())
+        {
+            Ok(account) => account,
+            Err(error) => return Ok(failure(error.into())),
+        };
+
+        let contract_key = view_request.contract_key();
+        let contract = match tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, contract_key)
+        {
+            Ok(contract) => contract,
+            Err(error) => return Ok(failure(error.into())),
+        };
+
+        let contract_version = contract.protocol_version();
+        if !contract_version.is_compatible_with(&protocol_version) {
+            let error = execution::Error::IncompatibleProtocolMajorVersion {
+                actual: protocol_version.value().major,
+                expected: contract_version.value().major,
+            };
+            return Ok(failure(error.into()));
+        }
+
+        let entry_point = view_request.entry_point();
+        if !contract.has_entry_point(entry_point) {
+            let error = execution::Error::FunctionNotFound(entry_point.to_string());
+            return Ok(failure(error.into()));
+        }
+        if let Err(error) = execution::check_args(&contract, entry_point, view_request.args()) {
+            return Ok(failure(error.into()));
+        }
+
+        let maybe_module = match contract_key {
+            Key::URef(uref) => self.system_contract_cache.get(&uref),
+            _ => None,
+        };
+        let mut module = match maybe_module {
+            Some(module) => module,
+            None => {
+                let contract_wasm = match tracking_copy
+                    .borrow_mut()
+                    .get_contract_wasm(correlation_id, contract.wasm_hash())
+                {
+                    Ok(contract_wasm) => contract_wasm,
+                    Err(error) => return Ok(failure(error.into())),
+                };
+                match engine_wasm_prep::deserialize(contract_wasm.bytes()) {
+                    Ok(module) => module,
+                    Err(error) => return Ok(failure(error.into())),
+                }
+            }
+        };
+        if let Err(error) = execution::select_entry_point(&mut module, entry_point) {
+            return Ok(failure(error.into()));
+        }
+
+        let entry_point_abi = contract
+            .abi()
+            .and_then(|abi| abi.entry_point(entry_point))
+            .cloned();
+        let executor = Executor;
+        let mut named_keys = contract.take_named_keys();
+        let (result, cost) = executor.exec_view(
+            module,
+            view_request.take_args(),
+            &mut named_keys,
+            contract_key,
+            &account,
+            view_request.block_time(),
+            view_request.gas_limit(),
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            protocol_data,
+            SystemContractCache::clone(&self.system_contract_cache),
+        );
+
+        let result = result.and_then(|value| {
+            execution::check_ret(entry_point_abi.as_ref(), &value)?;
+            Ok(value)
+        });
+        match result {
+            Ok(value) => Ok(ViewResult::Success { value, cost }),
+            Err(error) => Ok(ViewResult::Failure {
+                error: error.into(),
+                cost,
+            }),
+        }
     }
 
     pub fn run_execute(
@@ -616,27 +915,25 @@ where
         correlation_id: CorrelationId,
         mut exec_request: ExecuteRequest,
     ) -> Result<Vec<ExecutionResult>, RootNotFound> {
-        // TODO: do not unwrap
-        let wasm_costs = self
-            .wasm_costs(exec_request.protocol_version)
-            .unwrap()
-            .unwrap();
         let executor = Executor;
-        let preprocessor = Preprocessor::new(wasm_costs);
 
         let mut results = Vec::new();
 
         for deploy_item in exec_request.take_deploys() {
             let result = match deploy_item {
-                Ok(deploy_item) => self.deploy(
-                    correlation_id,
-                    &executor,
-                    &preprocessor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                ),
+                Ok(deploy_item) => match self.preprocessor(exec_request.protocol_version) {
+                    Ok(preprocessor) => self.deploy(
+                        correlation_id,
+                        &executor,
+                        &preprocessor,
+                        exec_request.protocol_version,
+                        exec_request.parent_state_hash,
+                        BlockTime::new(exec_request.block_time),
+                        deploy_item,
+                        exec_request.trace,
+                    ),
+                    Err(error) => Ok(ExecutionResult::precondition_failure(error)),
+                },
                 Err(exec_result) => Ok(exec_result), /* this will get pushed into the results vec
                                                       * below */
             };
@@ -651,15 +948,322 @@ where
         Ok(results)
     }
 
-    pub fn get_module(
+    /// Executes the deploys of `exec_request` like [`run_execute`](EngineState::run_execute), but
+    /// spread across up to [`EngineConfig::execution_threads`] threads.
+    ///
+    /// Every deploy is executed against the request's parent state, so the results are identical
+    /// to, and in the same order as, those of the sequential path.  Use
+    /// [`DeployConflicts::from_results`](conflicts::DeployConflicts::from_results) to find which of
+    /// them commute.
+    pub fn run_execute_parallel(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<Vec<ExecutionResult>, RootNotFound>
+    where
+        S: Sync,
+    {
+        let executor = Executor;
+        let protocol_version = exec_request.protocol_version;
+        let prestate_hash = exec_request.parent_state_hash;
+        let blocktime = BlockTime::new(exec_request.block_time);
+        let trace = exec_request.trace;
+
+        let deploys = exec_request.take_deploys();
+        let thread_count = self.config.execution_threads().max(1).min(deploys.len());
+
+        // Deploys which failed to parse already have their results; the rest are dealt out to the
+        // worker threads, remembering their positions in the request.
+        let mut results: Vec<Option<Result<ExecutionResult, RootNotFound>>> =
+            Vec::with_capacity(deploys.len());
+        let mut batches: Vec<Vec<(usize, DeployItem)>> =
+            (0..thread_count).map(|_| Vec::new()).collect();
+        for (position, deploy_item) in deploys.into_iter().enumerate() {
+            match deploy_item {
+                Ok(deploy_item) => {
+                    batches[position % thread_count].push((position, deploy_item));
+                    results.push(None);
+                }
+                Err(exec_result) => results.push(Some(Ok(exec_result))),
+            }
+        }
+
+        let executor = &executor;
+        let batch_results = crossbeam_utils::thread::scope(|scope| {
+            let handles: Vec<_> = batches
+                .into_iter()
+                .map(|batch| {
+                    scope.spawn(move |_| {
+                        batch
+                            .into_iter()
+                            .map(|(position, deploy_item)| {
+                                let result = match self.preprocessor(protocol_version) {
+                                    Ok(preprocessor) => self.deploy(
+                                        correlation_id,
+                                        executor,
+                                        &preprocessor,
+                                        protocol_version,
+                                        prestate_hash,
+                                        blocktime,
+                                        deploy_item,
+                                        trace,
+                                    ),
+                                    Err(error) => Ok(ExecutionResult::precondition_failure(error)),
+                                };
+                                (position, result.map(WorkerResult::from))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|error| panic::resume_unwind(error))
+                })
+                .collect::<Vec<_>>()
+        })
+        .expect(EXECUTION_THREADS_JOINED_EXPECT);
+
+        for (position, result) in batch_results.into_iter().flatten() {
+            results[position] = Some(result.map(ExecutionResult::from));
+        }
+
+        results
+            .into_iter()
+            .map(|result| result.expect(EXECUTION_RESULT_EXPECT))
+            .collect()
+    }
+
+    /// Executes the deploys of `exec_request` one after another, each on top of the effects of the
+    /// deploys before it, without committing anything to global state.
+    ///
+    /// Returns the result of each deploy along with the combined effect of all of them, which can
+    /// be committed to the request's parent state in one go.
+    pub fn run_execute_chained(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<(Vec<ExecutionResult>, ExecutionEffect), RootNotFound> {
+        self.execute_chained(correlation_id, exec_request, |_| true)
+    }
+
+    /// Executes the deploys of `exec_request` one after another, applying the effect of each deploy
+    /// at a position for which `is_applied` returns `true` before executing the next one.
+    ///
+    /// Returns the result of every deploy along with the combined effect of the applied ones.
+    fn execute_chained<F>(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+        is_applied: F,
+    ) -> Result<(Vec<ExecutionResult>, ExecutionEffect), RootNotFound>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let prestate_hash = exec_request.parent_state_hash;
+        let mut block_tracking_copy = match self.tracking_copy(prestate_hash) {
+            Ok(Some(tracking_copy)) => tracking_copy,
+            Ok(None) => return Err(RootNotFound(prestate_hash)),
+            // Every deploy fails the same way without any effects, just as it would on its own.
+            Err(_) => {
+                return self
+                    .run_execute(correlation_id, exec_request)
+                    .map(|results| (results, ExecutionEffect::default()))
+            }
+        };
+
+        let executor = Executor;
+        let protocol_version = exec_request.protocol_version;
+        let blocktime = BlockTime::new(exec_request.block_time);
+
+        let mut results = Vec::new();
+
+        for (position, deploy_item) in exec_request.take_deploys().into_iter().enumerate() {
+            let deploy_item = match deploy_item {
+                Ok(deploy_item) => deploy_item,
+                Err(exec_result) => {
+                    results.push(exec_result);
+                    continue;
+                }
+            };
+            let preprocessor = match self.preprocessor(protocol_version) {
+                Ok(preprocessor) => preprocessor,
+                Err(error) => {
+                    results.push(ExecutionResult::precondition_failure(error));
+                    continue;
+                }
+            };
+
+            let result = {
+                let tracking_copy = Rc::new(RefCell::new(block_tracking_copy.fork()));
+                self.deploy_with_tracking_copy(
+                    correlation_id,
+                    &executor,
+                    &preprocessor,
+                    protocol_version,
+                    blocktime,
+                    deploy_item,
+                    tracking_copy,
+                    exec_request.trace,
+                )
+            };
+
+            if !is_applied(position) {
+                results.push(result);
+                continue;
+            }
+
+            // The effect was produced against the state it is now applied to, so this can only
+            // fail if global state itself is broken.
+            let exec_error =
+                match block_tracking_copy.apply_effect(correlation_id, result.effect().clone()) {
+                    Ok(AddResult::Success) => {
+                        results.push(result);
+                        continue;
+                    }
+                    Ok(AddResult::KeyNotFound(key)) => execution::Error::KeyNotFound(key),
+                    Ok(AddResult::TypeMismatch(type_mismatch)) => {
+                        execution::Error::TypeMismatch(type_mismatch)
+                    }
+                    Ok(AddResult::Serialization(error)) => execution::Error::BytesRepr(error),
+                    Err(error) => error.into(),
+                };
+            results.push(ExecutionResult::precondition_failure(exec_error.into()));
+        }
+
+        Ok((results, block_tracking_copy.effect()))
+    }
+
+    /// Executes the deploys of `request` and commits the effects of the selected ones to the
+    /// request's parent state.
+    ///
+    /// If the deploys are chained, only the effects of the selected deploys are seen by the deploys
+    /// after them, as by [`run_execute_chained`](EngineState::run_execute_chained).  Otherwise each
+    /// deploy is executed against the parent state alone, as by
+    /// [`run_execute`](EngineState::run_execute), and the effects of the selected deploys are
+    /// combined.
+    ///
+    /// Returns the result of each deploy along with the outcome of the commit.  If the parent state
+    /// does not exist, no deploys are executed and the outcome is `CommitResult::RootNotFound`.
+    pub fn run_execute_and_commit(
         &self,
-        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        correlation_id: CorrelationId,
+        request: ExecuteAndCommitRequest,
+    ) -> Result<(Vec<ExecutionResult>, CommitResult), Error>
+    where
+        Error: From<S::Error>,
+    {
+        let ExecuteAndCommitRequest {
+            execute_request,
+            committed_deploys,
+        } = request;
+        let protocol_version = execute_request.protocol_version;
+        let prestate_hash = execute_request.parent_state_hash;
+        let is_committed = |position| committed_deploys.contains(&position);
+
+        let maybe_results = if execute_request.chain_deploys {
+            self.execute_chained(correlation_id, execute_request, is_committed)
+                .map(|(results, effect)| (results, effect.transforms))
+        } else {
+            self.run_execute(correlation_id, execute_request)
+                .map(|results| {
+                    let mut transforms = AdditiveMap::new();
+                    for (position, result) in results.iter().enumerate() {
+                        if is_committed(position) {
+                            for (key, transform) in result.effect().transforms.iter() {
+                                transforms.insert_add(*key, transform.clone());
+                            }
+                        }
+                    }
+                    (results, transforms)
+                })
+        };
+
+        let (results, transforms) = match maybe_results {
+            Ok(ret) => ret,
+            Err(RootNotFound(_)) => return Ok((Vec::new(), CommitResult::RootNotFound)),
+        };
+
+        let commit_result =
+            self.apply_effect(correlation_id, protocol_version, prestate_hash, transforms)?;
+
+        Ok((results, commit_result))
+    }
+
+    /// Dry-runs the payment and session code of each deploy in `exec_request` against the
+    /// request's parent state, measuring the gas each phase uses.
+    ///
+    /// Unlike [`run_execute`](EngineState::run_execute), neither phase is limited by the balance of
+    /// the payment purse, payment is not finalized and no effects are returned, so nothing can be
+    /// committed.
+    pub fn estimate_gas(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+    ) -> Result<Vec<GasEstimate>, RootNotFound> {
+        let executor = Executor;
+        let protocol_version = exec_request.protocol_version;
+        let prestate_hash = exec_request.parent_state_hash;
+        let blocktime = BlockTime::new(exec_request.block_time);
+
+        let mut estimates = Vec::new();
+
+        for deploy_item in exec_request.take_deploys() {
+            let deploy_item = match deploy_item {
+                Ok(deploy_item) => deploy_item,
+                Err(exec_result) => {
+                    let error = exec_result.take_error();
+                    estimates.push(GasEstimate::new(Gas::default(), Gas::default(), 0, error));
+                    continue;
+                }
+            };
+            let preprocessor = match self.preprocessor(protocol_version) {
+                Ok(preprocessor) => preprocessor,
+                Err(error) => {
+                    estimates.push(GasEstimate::precondition_failure(error));
+                    continue;
+                }
+            };
+
+            let tracking_copy = match self.tracking_copy(prestate_hash) {
+                Err(error) => {
+                    estimates.push(GasEstimate::precondition_failure(error));
+                    continue;
+                }
+                Ok(None) => return Err(RootNotFound(prestate_hash)),
+                Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+            };
+
+            estimates.push(self.estimate_deploy_gas(
+                correlation_id,
+                &executor,
+                &preprocessor,
+                protocol_version,
+                blocktime,
+                deploy_item,
+                tracking_copy,
+            ));
+        }
+
+        Ok(estimates)
+    }
+
+    pub fn get_module<R>(
+        &self,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
         deploy_item: &ExecutableDeployItem,
         account: &Account,
         correlation_id: CorrelationId,
         preprocessor: &Preprocessor,
         protocol_version: &ProtocolVersion,
-    ) -> Result<Module, error::Error> {
+    ) -> Result<Module, error::Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
         let stored_contract_key = match deploy_item {
             ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
                 let module = preprocessor.preprocess(&module_bytes)?;
@@ -746,8 +1350,19 @@ where
             return Err(error::Error::ExecError(exec_error));
         }
 
-        let (ret, _, _) = contract.destructure();
-        let module = engine_wasm_prep::deserialize(&ret)?;
+        let entry_point = deploy_item.entry_point();
+        if !contract.has_entry_point(entry_point) {
+            return Err(error::Error::ExecError(execution::Error::FunctionNotFound(
+                entry_point.to_string(),
+            )));
+        }
+        execution::check_args(&contract, entry_point, deploy_item.args())?;
+
+        let contract_wasm = tracking_copy
+            .borrow_mut()
+            .get_contract_wasm(correlation_id, contract.wasm_hash())?;
+        let mut module = engine_wasm_prep::deserialize(contract_wasm.bytes())?;
+        execution::select_entry_point(&mut module, entry_point)?;
         Ok(module)
     }
 
@@ -761,15 +1376,8 @@ where
         prestate_hash: Blake2bHash,
         blocktime: BlockTime,
         deploy_item: DeployItem,
+        trace: bool,
     ) -> Result<ExecutionResult, RootNotFound> {
-        // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
-
-        let session = deploy_item.session;
-        let payment = deploy_item.payment;
-        let address = Key::Account(deploy_item.address.value());
-        let authorization_keys = deploy_item.authorization_keys;
-        let deploy_hash = deploy_item.deploy_hash;
-
         // Create tracking copy (which functions as a deploy context)
         // validation_spec_2: prestate_hash check
         let tracking_copy = match self.tracking_copy(prestate_hash) {
@@ -778,15 +1386,50 @@ where
             Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
         };
 
+        Ok(self.deploy_with_tracking_copy(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            blocktime,
+            deploy_item,
+            tracking_copy,
+            trace,
+        ))
+    }
+
+    /// Executes `deploy_item` against the state seen through `tracking_copy`, recording the host
+    /// calls of each phase in the result if `trace` is set.
+    #[allow(clippy::too_many_arguments)]
+    fn deploy_with_tracking_copy<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+        trace: bool,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
+
+        let session = deploy_item.session;
+        let payment = deploy_item.payment;
+        let address = Key::Account(deploy_item.address.value());
+        let gas_price = deploy_item.gas_price;
+        let authorization_keys = deploy_item.authorization_keys;
+        let deploy_hash = deploy_item.deploy_hash;
+
         // Get addr bytes from `address` (which is actually a Key)
         // validation_spec_3: account validity
         let account_addr = match address.as_account() {
             Some(account_addr) => account_addr,
-            None => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::AuthorizationError,
-                ))
-            }
+            None => return ExecutionResult::precondition_failure(error::Error::AuthorizationError),
         };
 
         // Get account from tracking copy
@@ -797,27 +1440,25 @@ where
         {
             Ok(account) => account,
             Err(_) => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::AuthorizationError,
-                ));
+                return ExecutionResult::precondition_failure(error::Error::AuthorizationError);
             }
         };
 
         // Authorize using provided authorization keys
         // validation_spec_3: account validity
         if !account.can_authorize(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
+            return ExecutionResult::precondition_failure(
                 crate::engine_state::error::Error::AuthorizationError,
-            ));
+            );
         }
 
         // Check total key weight against deploy threshold
         // validation_spec_4: deploy validity
         if !account.can_deploy_with(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
+            return ExecutionResult::precondition_failure(
                 // TODO?:this doesn't happen in execution any longer, should error variant be moved
                 execution::Error::DeploymentAuthorizationFailure.into(),
-            ));
+            );
         }
 
         // Create session code `A` from provided session bytes
@@ -832,7 +1473,7 @@ where
         ) {
             Ok(module) => module,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error));
+                return ExecutionResult::precondition_failure(error);
             }
         };
 
@@ -841,15 +1482,19 @@ where
             Ok(Some(protocol_data)) => protocol_data,
             Ok(None) => {
                 let error = Error::InvalidProtocolVersion(protocol_version);
-                return Ok(ExecutionResult::precondition_failure(error));
+                return ExecutionResult::precondition_failure(error);
             }
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(Error::ExecError(
-                    error.into(),
-                )));
+                return ExecutionResult::precondition_failure(Error::ExecError(error.into()));
             }
         };
 
+        // The rate at which the deploy pays for the gas it uses
+        let conv_rate = match deploy_conv_rate(&protocol_data, gas_price) {
+            Ok(conv_rate) => conv_rate,
+            Err(error) => return ExecutionResult::precondition_failure(error),
+        };
+
         let max_payment_cost: Motes = Motes::new(U512::from(MAX_PAYMENT));
 
         // Get mint system contract details
@@ -865,13 +1510,20 @@ where
                 .get_contract(correlation_id, Key::URef(mint_reference))
             {
                 Ok(contract) => contract,
-                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                Err(error) => return ExecutionResult::precondition_failure(error.into()),
             };
 
             if !self.system_contract_cache.has(&mint_reference) {
-                let module = match engine_wasm_prep::deserialize(mint_contract.bytes()) {
+                let mint_contract_wasm = match tracking_copy
+                    .borrow_mut()
+                    .get_contract_wasm(correlation_id, mint_contract.wasm_hash())
+                {
+                    Ok(contract_wasm) => contract_wasm,
+                    Err(error) => return ExecutionResult::precondition_failure(error.into()),
+                };
+                let module = match engine_wasm_prep::deserialize(mint_contract_wasm.bytes()) {
                     Ok(module) => module,
-                    Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                    Err(error) => return ExecutionResult::precondition_failure(error.into()),
                 };
                 self.system_contract_cache.insert(mint_reference, module);
             }
@@ -892,7 +1544,7 @@ where
         {
             Ok(contract) => contract,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error.into()));
+                return ExecutionResult::precondition_failure(error.into());
             }
         };
 
@@ -905,7 +1557,7 @@ where
                 match proof_of_stake_contract.named_keys().get(POS_REWARDS_PURSE) {
                     Some(key) => *key,
                     None => {
-                        return Ok(ExecutionResult::precondition_failure(Error::DeployError));
+                        return ExecutionResult::precondition_failure(Error::DeployError);
                     }
                 };
 
@@ -916,7 +1568,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -932,7 +1584,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
@@ -944,15 +1596,13 @@ where
             .get_purse_balance(correlation_id, account_main_purse_balance_key)
         {
             Ok(balance) => balance,
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            Err(error) => return ExecutionResult::precondition_failure(error.into()),
         };
 
         // Enforce minimum main purse balance validation
         // validation_spec_5: account main purse minimum balance
         if account_main_purse_balance < max_payment_cost {
-            return Ok(ExecutionResult::precondition_failure(
-                Error::InsufficientPaymentError,
-            ));
+            return ExecutionResult::precondition_failure(Error::InsufficientPaymentError);
         }
 
         // Finalization is executed by system account (currently genesis account)
@@ -969,10 +1619,10 @@ where
         let mut execution_result_builder = execution_result::ExecutionResultBuilder::new();
 
         // Execute provided payment code
-        let payment_result = {
+        let mut payment_result = {
             // payment_code_spec_1: init pay environment w/ gas limit == (max_payment_cost /
             // conv_rate)
-            let pay_gas_limit = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+            let pay_gas_limit = Gas::from_motes(max_payment_cost, conv_rate).unwrap_or_default();
 
             // Create payment code module from bytes
             // validation_spec_1: valid wasm bytes
@@ -986,7 +1636,7 @@ where
             ) {
                 Ok(module) => module,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error));
+                    return ExecutionResult::precondition_failure(error);
                 }
             };
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
@@ -1007,10 +1657,12 @@ where
                 Phase::Payment,
                 protocol_data,
                 system_contract_cache,
+                trace,
             )
         };
 
         let payment_result_cost = payment_result.cost();
+        let mut call_frames = payment_result.take_trace();
 
         // payment_code_spec_3: fork based upon payment purse balance and cost of
         // payment code execution
@@ -1020,7 +1672,7 @@ where
             let payment_purse: Key =
                 match proof_of_stake_contract.named_keys().get(POS_PAYMENT_PURSE) {
                     Some(key) => *key,
-                    None => return Ok(ExecutionResult::precondition_failure(Error::DeployError)),
+                    None => return ExecutionResult::precondition_failure(Error::DeployError),
                 };
 
             let purse_balance_key = match tracking_copy.borrow_mut().get_purse_balance_key(
@@ -1030,7 +1682,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             };
 
@@ -1040,23 +1692,27 @@ where
             {
                 Ok(balance) => balance,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return ExecutionResult::precondition_failure(error.into());
                 }
             }
         };
 
-        if let Some(forced_transfer) = payment_result.check_forced_transfer(payment_purse_balance) {
+        if let Some(forced_transfer) =
+            payment_result.check_forced_transfer(payment_purse_balance, conv_rate)
+        {
             let error = match forced_transfer {
                 ForcedTransferResult::InsufficientPayment => Error::InsufficientPaymentError,
                 ForcedTransferResult::PaymentFailure => payment_result.take_error().unwrap(),
             };
-            return Ok(ExecutionResult::new_payment_code_error(
+            return ExecutionResult::new_payment_code_error(
                 error,
                 max_payment_cost,
                 account_main_purse_balance,
                 account_main_purse_balance_key,
                 rewards_purse_balance_key,
-            ));
+                conv_rate,
+            )
+            .with_trace(call_frames);
         }
 
         execution_result_builder.set_payment_execution_result(payment_result);
@@ -1065,12 +1721,12 @@ where
         let session_tc = Rc::new(RefCell::new(post_payment_tc.fork()));
 
         // session_code_spec_2: execute session code
-        let session_result = {
+        let mut session_result = {
             // payment_code_spec_3_b_i: if (balance of PoS pay purse) >= (gas spent during
             // payment code execution) * conv_rate, yes session
             // session_code_spec_1: gas limit = ((balance of PoS payment purse) / conv_rate)
             // - (gas spent during payment execution)
-            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, CONV_RATE)
+            let session_gas_limit: Gas = Gas::from_motes(payment_purse_balance, conv_rate)
                 .unwrap_or_default()
                 - payment_result_cost;
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
@@ -1090,6 +1746,7 @@ where
                 Phase::Session,
                 protocol_data,
                 system_contract_cache,
+                trace,
             )
         };
 
@@ -1103,35 +1760,54 @@ where
 
         // NOTE: session_code_spec_3: (do not include session execution effects in
         // results) is enforced in execution_result_builder.build()
+        let session_result_cost = session_result.cost();
+        call_frames.extend(session_result.take_trace());
         execution_result_builder.set_session_execution_result(session_result);
 
+        // ((gas spent during payment code execution) + (gas spent during session code execution))
+        // * conv_rate
+        let finalize_cost_motes: Motes =
+            Motes::from_gas(execution_result_builder.total_cost(), conv_rate)
+                .expect("motes overflow");
+
         // payment_code_spec_5: run finalize process
-        let finalize_result = {
+        let mut finalize_result = {
             let post_session_tc = post_session_rc.borrow();
             let finalization_tc = Rc::new(RefCell::new(post_session_tc.fork()));
 
             // validation_spec_1: valid wasm bytes
-            let proof_of_stake_module =
-                match self.system_contract_cache.get(&proof_of_stake_reference) {
-                    Some(module) => module,
-                    None => {
-                        let module =
-                            match engine_wasm_prep::deserialize(proof_of_stake_contract.bytes()) {
-                                Ok(module) => module,
-                                Err(error) => {
-                                    return Ok(ExecutionResult::precondition_failure(error.into()))
-                                }
-                            };
-                        self.system_contract_cache
-                            .insert(proof_of_stake_reference, module.clone());
-                        module
-                    }
-                };
+            let proof_of_stake_module = match self
+                .system_contract_cache
+                .get(&proof_of_stake_reference)
+            {
+                Some(module) => module,
+                None => {
+                    let proof_of_stake_contract_wasm = match finalization_tc
+                        .borrow_mut()
+                        .get_contract_wasm(correlation_id, proof_of_stake_contract.wasm_hash())
+                    {
+                        Ok(contract_wasm) => contract_wasm,
+                        Err(error) => return ExecutionResult::precondition_failure(error.into()),
+                    };
+                    let module =
+                        match engine_wasm_prep::deserialize(proof_of_stake_contract_wasm.bytes()) {
+                            Ok(module) => module,
+                            Err(error) => {
+                                return ExecutionResult::precondition_failure(error.into())
+                            }
+                        };
+                    self.system_contract_cache
+                        .insert(proof_of_stake_reference, module.clone());
+                    module
+                }
+            };
 
             let proof_of_stake_args = {
-                //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
-                let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
-                let args = ("finalize_payment", finalize_cost_motes.value(), account_addr);
+                let args = (
+                    "finalize_payment",
+                    finalize_cost_motes.value(),
+                    account_addr,
+                );
                 ArgsParser::parse(args)
                     .expect("args should convert to `Vec<CLValue>`")
                     .into_bytes()
@@ -1145,7 +1821,7 @@ where
                 .get_contract(correlation_id, Key::URef(proof_of_stake_reference))
             {
                 Ok(info) => info,
-                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                Err(error) => return ExecutionResult::precondition_failure(error.into()),
             };
 
             let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
@@ -1170,20 +1846,182 @@ where
                 Phase::FinalizePayment,
                 protocol_data,
                 system_contract_cache,
+                trace,
             )
         };
 
+        let finalize_result_cost = finalize_result.cost();
+        call_frames.extend(finalize_result.take_trace());
         execution_result_builder.set_finalize_execution_result(finalize_result);
 
         // We panic here to indicate that the builder was not used properly.
         let ret = execution_result_builder
             .build(tracking_copy.borrow().reader(), correlation_id)
-            .expect("ExecutionResultBuilder not initialized properly");
+            .expect("ExecutionResultBuilder not initialized properly")
+            .with_trace(call_frames);
+
+        // NOTE: payment_code_spec_5_a is enforced in execution_result_builder.build(), and such a
+        // failure charges nothing
+        if ret.has_precondition_failure() {
+            return ret;
+        }
+
+        // finalize_payment refunds whatever is left in the payment purse
+        let motes_refunded = if payment_purse_balance > finalize_cost_motes {
+            payment_purse_balance - finalize_cost_motes
+        } else {
+            Motes::default()
+        };
+        let cost_breakdown = CostBreakdown::new(
+            payment_result_cost,
+            session_result_cost,
+            finalize_result_cost,
+            finalize_cost_motes,
+            motes_refunded,
+        );
 
-        // NOTE: payment_code_spec_5_a is enforced in execution_result_builder.build()
         // payment_code_spec_6: return properly combined set of transforms and
         // appropriate error
-        Ok(ret)
+        ret.with_cost_breakdown(cost_breakdown)
+    }
+
+    /// Runs the payment and then the session code of `deploy_item` with an unlimited amount of
+    /// gas, stopping at the first phase which fails.
+    #[allow(clippy::too_many_arguments)]
+    fn estimate_deploy_gas<R>(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+        tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    ) -> GasEstimate
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<execution::Error>,
+    {
+        let session = deploy_item.session;
+        let payment = deploy_item.payment;
+        let account_addr = deploy_item.address.value();
+        let address = Key::Account(account_addr);
+        let gas_price = deploy_item.gas_price;
+        let authorization_keys = deploy_item.authorization_keys;
+        let deploy_hash = deploy_item.deploy_hash;
+
+        let account: Account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, account_addr)
+        {
+            Ok(account) => account,
+            Err(_) => return GasEstimate::precondition_failure(Error::AuthorizationError),
+        };
+
+        if !account.can_authorize(&authorization_keys) {
+            return GasEstimate::precondition_failure(Error::AuthorizationError);
+        }
+
+        if !account.can_deploy_with(&authorization_keys) {
+            return GasEstimate::precondition_failure(
+                execution::Error::DeploymentAuthorizationFailure.into(),
+            );
+        }
+
+        let session_module = match self.get_module(
+            Rc::clone(&tracking_copy),
+            &session,
+            &account,
+            correlation_id,
+            preprocessor,
+            &protocol_version,
+        ) {
+            Ok(module) => module,
+            Err(error) => return GasEstimate::precondition_failure(error),
+        };
+
+        let payment_module = match self.get_module(
+            Rc::clone(&tracking_copy),
+            &payment,
+            &account,
+            correlation_id,
+            preprocessor,
+            &protocol_version,
+        ) {
+            Ok(module) => module,
+            Err(error) => return GasEstimate::precondition_failure(error),
+        };
+
+        let protocol_data = match self.state.get_protocol_data(protocol_version) {
+            Ok(Some(protocol_data)) => protocol_data,
+            Ok(None) => {
+                let error = Error::InvalidProtocolVersion(protocol_version);
+                return GasEstimate::precondition_failure(error);
+            }
+            Err(error) => {
+                return GasEstimate::precondition_failure(Error::ExecError(error.into()));
+            }
+        };
+
+        let conv_rate = match deploy_conv_rate(&protocol_data, gas_price) {
+            Ok(conv_rate) => conv_rate,
+            Err(error) => return GasEstimate::precondition_failure(error),
+        };
+
+        let gas_limit = Gas::new(U512::from(std::u64::MAX));
+
+        let payment_result = executor.exec(
+            payment_module,
+            payment.take_args(),
+            address,
+            &account,
+            authorization_keys.clone(),
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+            Phase::Payment,
+            protocol_data,
+            SystemContractCache::clone(&self.system_contract_cache),
+            false,
+        );
+        let payment_cost = payment_result.cost();
+        if payment_result.is_failure() {
+            return GasEstimate::new(
+                payment_cost,
+                Gas::default(),
+                conv_rate,
+                payment_result.take_error(),
+            );
+        }
+
+        // Session code sees the effects of payment code, just as it would when executed.
+        let session_result = executor.exec(
+            session_module,
+            session.take_args(),
+            address,
+            &account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            Phase::Session,
+            protocol_data,
+            SystemContractCache::clone(&self.system_contract_cache),
+            false,
+        );
+
+        GasEstimate::new(
+            payment_cost,
+            session_result.cost(),
+            conv_rate,
+            session_result.take_error(),
+        )
     }
 
     pub fn apply_effect(
@@ -1209,6 +2047,47 @@ where
         }
     }
 
+    /// Returns the serialized trie stored under `trie_key`, if any, to be sent to a peer syncing
+    /// global state.
+    pub fn get_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Vec<u8>>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let ret = self.state.get_trie(correlation_id, trie_key)?;
+        Ok(ret)
+    }
+
+    /// Returns the keys of the tries which are missing among the tries at `trie_keys` and their
+    /// descendants, i.e. the tries to fetch next when syncing global state.
+    pub fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let ret = self.state.missing_trie_keys(correlation_id, trie_keys)?;
+        Ok(ret)
+    }
+
+    /// Stores serialized tries received from a peer syncing global state.
+    pub fn put_tries(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[(Blake2bHash, Vec<u8>)],
+    ) -> Result<Vec<PutTrieResult>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let ret = self.state.put_tries(correlation_id, tries)?;
+        Ok(ret)
+    }
+
     /// Calculates bonded validators at `root_hash` state.
     ///
     /// Should only be called with a valid root hash after a successful call to
@@ -12,6 +12,21 @@ pub enum Op {
     NoOp,
 }
 
+impl Op {
+    /// Returns `true` if applying `self` and `other` to the same key gives the same result in
+    /// either order.
+    ///
+    /// Reads commute with reads and additions commute with additions, but a read does not commute
+    /// with an addition, and a write commutes with nothing but a no-op.
+    pub fn commutes_with(self, other: Op) -> bool {
+        match (self, other) {
+            (Op::NoOp, _) | (_, Op::NoOp) => true,
+            (Op::Read, Op::Read) | (Op::Add, Op::Add) => true,
+            _ => false,
+        }
+    }
+}
+
 impl Add for Op {
     type Output = Op;
 
@@ -1,12 +1,25 @@
-use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
-use types::Key;
+use engine_shared::{
+    contract_wasm::ContractWasm, newtypes::Blake2bHash, stored_value::StoredValue,
+};
+use types::{Key, TrieMerkleProof};
 
 use crate::tracking_copy::TrackingCopyQueryResult;
 
 pub enum QueryResult {
     RootNotFound,
-    ValueNotFound(String),
-    Success(StoredValue),
+    ValueNotFound {
+        full_path: String,
+        /// Merkle proofs of every key read while resolving the query, if they were requested.  The
+        /// last one shows the absence of the missing key.
+        proofs: Vec<TrieMerkleProof>,
+    },
+    Success {
+        value: StoredValue,
+        /// The code of the contract found, if the value is a contract.
+        contract_wasm: Option<ContractWasm>,
+        /// Merkle proofs of every key read while resolving the query, if they were requested.
+        proofs: Vec<TrieMerkleProof>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,14 +27,16 @@ pub struct QueryRequest {
     state_hash: Blake2bHash,
     key: Key,
     path: Vec<String>,
+    include_proofs: bool,
 }
 
 impl QueryRequest {
-    pub fn new(state_hash: Blake2bHash, key: Key, path: Vec<String>) -> Self {
+    pub fn new(state_hash: Blake2bHash, key: Key, path: Vec<String>, include_proofs: bool) -> Self {
         QueryRequest {
             state_hash,
             key,
             path,
+            include_proofs,
         }
     }
 
@@ -36,15 +51,31 @@ impl QueryRequest {
     pub fn path(&self) -> &[String] {
         &self.path
     }
+
+    pub fn include_proofs(&self) -> bool {
+        self.include_proofs
+    }
 }
 
-impl From<TrackingCopyQueryResult> for QueryResult {
-    fn from(tracking_copy_query_result: TrackingCopyQueryResult) -> Self {
+impl From<(TrackingCopyQueryResult, Vec<TrieMerkleProof>)> for QueryResult {
+    fn from(
+        (tracking_copy_query_result, proofs): (TrackingCopyQueryResult, Vec<TrieMerkleProof>),
+    ) -> Self {
         match tracking_copy_query_result {
             TrackingCopyQueryResult::ValueNotFound(full_path) => {
-                QueryResult::ValueNotFound(full_path)
+                QueryResult::ValueNotFound { full_path, proofs }
             }
-            TrackingCopyQueryResult::Success(value) => QueryResult::Success(value),
+            TrackingCopyQueryResult::Success(value) => QueryResult::Success {
+                value,
+                contract_wasm: None,
+                proofs,
+            },
         }
     }
 }
+
+impl From<TrackingCopyQueryResult> for QueryResult {
+    fn from(tracking_copy_query_result: TrackingCopyQueryResult) -> Self {
+        (tracking_copy_query_result, Vec::new()).into()
+    }
+}
@@ -1,8 +1,10 @@
 use std::fmt;
 
-use engine_shared::{newtypes::Blake2bHash, transform::TypeMismatch};
+use engine_shared::{conv_rates::ConvRates, newtypes::Blake2bHash, transform::TypeMismatch};
 use engine_storage::global_state::CommitResult;
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{
+    host_function_costs::HostFunctionCosts, storage_costs::StorageCosts, wasm_costs::WasmCosts,
+};
 use types::{bytesrepr, Key, ProtocolVersion};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
@@ -60,6 +62,9 @@ pub struct UpgradeConfig {
     upgrade_installer_args: Option<Vec<u8>>,
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_costs: Option<WasmCosts>,
+    host_function_costs: Option<HostFunctionCosts>,
+    storage_costs: Option<StorageCosts>,
+    conv_rates: Option<ConvRates>,
     activation_point: Option<ActivationPoint>,
 }
 
@@ -71,6 +76,9 @@ impl UpgradeConfig {
         upgrade_installer_args: Option<Vec<u8>>,
         upgrade_installer_bytes: Option<Vec<u8>>,
         wasm_costs: Option<WasmCosts>,
+        host_function_costs: Option<HostFunctionCosts>,
+        storage_costs: Option<StorageCosts>,
+        conv_rates: Option<ConvRates>,
         activation_point: Option<ActivationPoint>,
     ) -> Self {
         UpgradeConfig {
@@ -80,6 +88,9 @@ impl UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            conv_rates,
             activation_point,
         }
     }
@@ -110,6 +121,18 @@ impl UpgradeConfig {
         self.wasm_costs
     }
 
+    pub fn host_function_costs(&self) -> Option<HostFunctionCosts> {
+        self.host_function_costs
+    }
+
+    pub fn storage_costs(&self) -> Option<StorageCosts> {
+        self.storage_costs
+    }
+
+    pub fn conv_rates(&self) -> Option<ConvRates> {
+        self.conv_rates
+    }
+
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
@@ -0,0 +1,101 @@
+use engine_shared::{gas::Gas, newtypes::Blake2bHash};
+use types::{account::PublicKey, BlockTime, CLValue, Key, ProtocolVersion, DEFAULT_ENTRY_POINT};
+
+use super::error::Error;
+
+/// A read-only call of a stored contract against a given state root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewRequest {
+    state_hash: Blake2bHash,
+    contract_key: Key,
+    entry_point: String,
+    args: Vec<u8>,
+    caller: PublicKey,
+    block_time: BlockTime,
+    gas_limit: Gas,
+    protocol_version: ProtocolVersion,
+}
+
+impl ViewRequest {
+    /// Creates a [`ViewRequest`] of the default entry point; `args` are the serialized arguments,
+    /// just as in a deploy.
+    pub fn new(
+        state_hash: Blake2bHash,
+        contract_key: Key,
+        args: Vec<u8>,
+        caller: PublicKey,
+        block_time: BlockTime,
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        ViewRequest {
+            state_hash,
+            contract_key,
+            entry_point: DEFAULT_ENTRY_POINT.to_string(),
+            args,
+            caller,
+            block_time,
+            gas_limit,
+            protocol_version,
+        }
+    }
+
+    /// Sets the name of the entry point to call.
+    pub fn with_entry_point(mut self, entry_point: String) -> Self {
+        self.entry_point = entry_point;
+        self
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn contract_key(&self) -> Key {
+        self.contract_key
+    }
+
+    pub fn entry_point(&self) -> &str {
+        &self.entry_point
+    }
+
+    pub fn args(&self) -> &[u8] {
+        &self.args
+    }
+
+    pub fn take_args(&mut self) -> Vec<u8> {
+        std::mem::replace(&mut self.args, Vec::new())
+    }
+
+    /// Returns the account the contract sees as its caller.
+    pub fn caller(&self) -> PublicKey {
+        self.caller
+    }
+
+    pub fn block_time(&self) -> BlockTime {
+        self.block_time
+    }
+
+    /// Returns the most gas the call may use.
+    pub fn gas_limit(&self) -> Gas {
+        self.gas_limit
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+}
+
+#[derive(Debug)]
+pub enum ViewResult {
+    RootNotFound,
+    /// The contract returned `value`.
+    Success {
+        value: CLValue,
+        cost: Gas,
+    },
+    /// The call failed, e.g. because the contract tried to change global state or ran out of gas.
+    Failure {
+        error: Error,
+        cost: Gas,
+    },
+}
@@ -3,10 +3,10 @@ use core::fmt;
 use parity_wasm::elements;
 use wasmi;
 
-use engine_shared::transform::TypeMismatch;
+use engine_shared::{gas::Gas, transform::TypeMismatch};
 use types::{
     account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
-    bytesrepr, system_contract_errors, AccessRights, CLValueError, Key, URef,
+    bytesrepr, system_contract_errors, AccessRights, CLType, CLValueError, Key, URef,
 };
 
 use crate::resolvers::error::ResolverError;
@@ -25,8 +25,24 @@ pub enum Error {
     ForgedReference(URef),
     URefNotFound(String),
     FunctionNotFound(String),
+    /// The arguments passed to an entry point don't match the types given in the contract's ABI.
+    InvalidArgs {
+        entry_point: String,
+        expected: Vec<CLType>,
+        actual: Vec<CLType>,
+    },
+    /// The value returned by an entry point doesn't match the type given in the contract's ABI.
+    InvalidReturnValue {
+        entry_point: String,
+        expected: CLType,
+        actual: CLType,
+    },
+    /// The ABI of a contract being stored describes the named entry point more than once.
+    DuplicateEntryPoint(String),
     ParityWasm(elements::Error),
     GasLimit,
+    /// A called contract used more gas than the limit it was called with.
+    SubCallGasLimit(Gas),
     Ret(Vec<URef>),
     Rng(rand::Error),
     ResolverError(ResolverError),
@@ -41,6 +57,8 @@ pub enum Error {
     ExpectedReturnValue,
     UnexpectedReturnValue,
     InvalidContext,
+    /// A write, add or key put was attempted during a read-only call.
+    WriteInReadOnlyContext,
     IncompatibleProtocolMajorVersion {
         expected: u32,
         actual: u32,
@@ -0,0 +1,22 @@
+use types::CLValue;
+
+/// A named value emitted by a contract through `emit_event`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    name: String,
+    value: CLValue,
+}
+
+impl Event {
+    pub fn new(name: String, value: CLValue) -> Self {
+        Event { name, value }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &CLValue {
+        &self.value
+    }
+}
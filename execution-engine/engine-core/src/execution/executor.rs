@@ -16,9 +16,12 @@ use types::{
     BlockTime, CLType, CLTyped, CLValue, Key, Phase, ProtocolVersion,
 };
 
-use super::{extract_access_rights_from_keys, instance_and_memory, Error, Runtime};
+use super::{extract_access_rights_from_keys, instance_and_memory, CallFrame, Error, Runtime};
 use crate::{
-    engine_state::{execution_result::ExecutionResult, system_contract_cache::SystemContractCache},
+    engine_state::{
+        execution_result::{ExecutionDetails, ExecutionResult},
+        system_contract_cache::SystemContractCache,
+    },
     execution::{address_generator::AddressGenerator, FN_STORE_ID_INITIAL},
     runtime_context::{self, RuntimeContext},
     tracking_copy::TrackingCopy,
@@ -43,11 +46,15 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: Default::default(),
                     cost: $cost,
+                    details: Default::default(),
                 };
             }
         }
     };
     ($fn:expr, $cost:expr, $effect:expr) => {
+        on_fail_charge!($fn, $cost, $effect, Default::default())
+    };
+    ($fn:expr, $cost:expr, $effect:expr, $trace:expr) => {
         match $fn {
             Ok(res) => res,
             Err(e) => {
@@ -56,6 +63,10 @@ macro_rules! on_fail_charge {
                     error: exec_err.into(),
                     effect: $effect,
                     cost: $cost,
+                    details: crate::engine_state::execution_result::ExecutionDetails {
+                        trace: $trace,
+                        ..Default::default()
+                    },
                 };
             }
         }
@@ -82,6 +93,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        trace: bool,
     ) -> ExecutionResult
     where
         R: StateReader<Key, StoredValue>,
@@ -143,15 +155,26 @@ impl Executor {
         );
 
         let mut runtime = Runtime::new(system_contract_cache, memory, parity_module, context);
+        if trace {
+            runtime.enable_trace();
+        }
+        let result = instance.invoke_export("call", &[], &mut runtime);
+        let trace: Vec<CallFrame> = runtime.take_trace().into_iter().collect();
         on_fail_charge!(
-            instance.invoke_export("call", &[], &mut runtime),
+            result,
             runtime.context().gas_counter(),
-            effects_snapshot
+            effects_snapshot,
+            trace
         );
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
             cost: runtime.context().gas_counter(),
+            details: ExecutionDetails {
+                trace,
+                events: runtime.take_events(),
+                ..Default::default()
+            },
         }
     }
 
@@ -172,6 +195,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        trace: bool,
     ) -> ExecutionResult
     where
         R: StateReader<Key, StoredValue>,
@@ -228,11 +252,21 @@ impl Executor {
             on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
 
         let mut runtime = Runtime::new(system_contract_cache, memory, parity_module, context);
+        if trace {
+            runtime.enable_trace();
+        }
 
-        match instance.invoke_export("call", &[], &mut runtime) {
+        let result = instance.invoke_export("call", &[], &mut runtime);
+        let trace: Vec<CallFrame> = runtime.take_trace().into_iter().collect();
+        match result {
             Ok(_) => ExecutionResult::Success {
                 effect: runtime.context().effect(),
                 cost: runtime.context().gas_counter(),
+                details: ExecutionDetails {
+                    trace,
+                    events: runtime.take_events(),
+                    ..Default::default()
+                },
             },
             Err(e) => {
                 if let Some(host_error) = e.as_host_error() {
@@ -249,6 +283,11 @@ impl Executor {
                             return ExecutionResult::Success {
                                 effect: runtime.context().effect(),
                                 cost: runtime.context().gas_counter(),
+                                details: ExecutionDetails {
+                                    trace,
+                                    events: runtime.take_events(),
+                                    ..Default::default()
+                                },
                             };
                         }
                         Error::Revert(status) => {
@@ -258,6 +297,10 @@ impl Executor {
                                 error: Error::Revert(*status).into(),
                                 effect: effects_snapshot,
                                 cost: runtime.context().gas_counter(),
+                                details: ExecutionDetails {
+                                    trace,
+                                    ..Default::default()
+                                },
                             };
                         }
                         _ => {}
@@ -268,6 +311,10 @@ impl Executor {
                     error: Error::Interpreter(e).into(),
                     effect: effects_snapshot,
                     cost: runtime.context().gas_counter(),
+                    details: ExecutionDetails {
+                        trace,
+                        ..Default::default()
+                    },
                 }
             }
         }
@@ -365,4 +412,103 @@ impl Executor {
         let ret = return_value.into_t()?;
         Ok(ret)
     }
+
+    /// Runs `module` as the contract stored under `base_key` in a read-only context, in which any
+    /// attempt to change global state fails.
+    ///
+    /// Returns the value the contract passed to `runtime::ret` (or `()` if it returned without
+    /// calling it) along with the gas used.
+    pub fn exec_view<R>(
+        &self,
+        module: Module,
+        args: Vec<u8>,
+        named_keys: &mut BTreeMap<String, Key>,
+        base_key: Key,
+        account: &Account,
+        blocktime: BlockTime,
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        state: Rc<RefCell<TrackingCopy<R>>>,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+    ) -> (Result<CLValue, Error>, Gas)
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        let access_rights =
+            {
+                let mut keys: Vec<Key> = named_keys.values().cloned().collect();
+                keys.extend(protocol_data.system_contracts().into_iter().map(|uref| {
+                    Key::from(runtime_context::attenuate_uref_for_account(account, uref))
+                }));
+                extract_access_rights_from_keys(keys)
+            };
+
+        let args: Vec<CLValue> = if args.is_empty() {
+            Vec::new()
+        } else {
+            match bytesrepr::deserialize(args) {
+                Ok(args) => args,
+                Err(error) => return (Err(error.into()), Gas::default()),
+            }
+        };
+
+        // A view is not part of any deploy, and as it cannot store anything it has no use for
+        // unique addresses either.
+        let deploy_hash = [0u8; 32];
+        let phase = Phase::Session;
+        let address_generator = {
+            let address_generator = AddressGenerator::new(deploy_hash, phase);
+            Rc::new(RefCell::new(address_generator))
+        };
+
+        let mut runtime_context = RuntimeContext::new(
+            state,
+            named_keys,
+            access_rights,
+            args,
+            BTreeSet::new(),
+            account,
+            base_key,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            Gas::default(),
+            FN_STORE_ID_INITIAL,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            phase,
+            protocol_data,
+        );
+        runtime_context.set_read_only(true);
+
+        let (instance, memory) = match instance_and_memory(module.clone(), protocol_version) {
+            Ok(instance_and_memory) => instance_and_memory,
+            Err(error) => return (Err(error), Gas::default()),
+        };
+
+        let mut runtime = Runtime::new(system_contract_cache, memory, module, runtime_context);
+
+        let result = match instance.invoke_export("call", &[], &mut runtime) {
+            Ok(_) => match runtime.take_host_buf() {
+                Some(value) => Ok(value),
+                None => CLValue::from_t(()).map_err(Error::CLValue),
+            },
+            Err(error) => match error
+                .as_host_error()
+                .and_then(|host_error| host_error.downcast_ref::<Error>())
+            {
+                Some(Error::Ret(_)) => runtime.take_host_buf().ok_or(Error::ExpectedReturnValue),
+                Some(Error::Revert(code)) => Err(Error::Revert(*code)),
+                Some(Error::GasLimit) => Err(Error::GasLimit),
+                Some(Error::WriteInReadOnlyContext) => Err(Error::WriteInReadOnlyContext),
+                _ => Err(Error::Interpreter(error)),
+            },
+        };
+
+        (result, runtime.context().gas_counter())
+    }
 }
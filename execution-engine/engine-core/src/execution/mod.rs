@@ -1,19 +1,23 @@
 mod address_generator;
 mod error;
+mod event;
 #[macro_use]
 mod executor;
 mod runtime;
 #[cfg(test)]
 mod tests;
+mod trace;
 
 pub use self::{
     address_generator::{AddressGenerator, AddressGeneratorBuilder},
     error::Error,
+    event::Event,
     executor::Executor,
     runtime::{
-        extract_access_rights_from_keys, extract_access_rights_from_urefs, instance_and_memory,
-        Runtime,
+        check_args, check_ret, extract_access_rights_from_keys, extract_access_rights_from_urefs,
+        instance_and_memory, select_entry_point, Runtime,
     },
+    trace::{CallFrame, HostCall, HostCallArg},
 };
 
 pub const MINT_NAME: &str = "mint";
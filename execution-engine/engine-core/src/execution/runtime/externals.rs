@@ -2,15 +2,11 @@ use std::convert::TryFrom;
 
 use wasmi::{Externals, RuntimeArgs, RuntimeValue, Trap};
 
-use types::{
-    account::PublicKey,
-    api_error,
-    bytesrepr::{self, ToBytes},
-    Key, TransferredTo, U512,
-};
+use types::{api_error, bytesrepr::ToBytes, Key, TransferredTo, DEFAULT_ENTRY_POINT};
 
 use engine_shared::{gas::Gas, stored_value::StoredValue};
 use engine_storage::global_state::StateReader;
+use engine_wasm_prep::host_function_costs::HostFunctionCosts;
 
 use super::{args::Args, Error, Runtime};
 use crate::resolvers::v1_function_index::FunctionIndex;
@@ -26,6 +22,31 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+        // Calls to `gas` are injected by the metering rather than made by the contract, so they are
+        // left out of the trace.
+        if !self.is_tracing() || func == FunctionIndex::GasFuncIndex {
+            return self.call_host_function(func, args);
+        }
+        let result = self.call_host_function(func, args);
+        self.record_host_call(func);
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn call_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        let host_function_costs = *self.context.protocol_data().host_function_costs();
+        self.gas(Gas::new(
+            host_function_cost(&func, &host_function_costs).into(),
+        ))?;
         match func {
             FunctionIndex::ReadFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
@@ -33,6 +54,9 @@ where
                 // args(2) = pointer to output size (output param)
                 let (key_ptr, key_size, output_size_ptr) = Args::parse(args)?;
                 let ret = self.read(key_ptr, key_size, output_size_ptr)?;
+                if ret.is_ok() {
+                    self.charge_host_buf_bytes(host_function_costs.read_value_byte)?;
+                }
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
@@ -42,6 +66,9 @@ where
                 // args(2) = pointer to output size (output param)
                 let (key_ptr, key_size, output_size_ptr) = Args::parse(args)?;
                 let ret = self.read_local(key_ptr, key_size, output_size_ptr)?;
+                if ret.is_ok() {
+                    self.charge_host_buf_bytes(host_function_costs.read_value_byte)?;
+                }
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
@@ -138,8 +165,93 @@ where
 
                 let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
                 let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+                self.trace_call_args(&args_bytes);
+
+                let ret = self.call_contract_host_buf(
+                    key_contract,
+                    DEFAULT_ENTRY_POINT,
+                    args_bytes,
+                    result_size_ptr,
+                    false,
+                    None,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::CallContractEntryPointIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to entry point name in Wasm memory
+                // args(3) = size of entry point name
+                // args(4) = pointer to function arguments in Wasm memory
+                // args(5) = size of arguments
+                // args(6) = pointer to serialized optional gas limit in Wasm memory
+                // args(7) = size of gas limit
+                // args(8) = pointer to result size (output)
+                let (
+                    key_ptr,
+                    key_size,
+                    entry_point_ptr,
+                    entry_point_size,
+                    args_ptr,
+                    args_size,
+                    gas_limit_ptr,
+                    gas_limit_size,
+                    result_size_ptr,
+                ): (_, _, _, _, _, u32, _, u32, _) = Args::parse(args)?;
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+                self.trace_call_args(&args_bytes);
+                let gas_limit = self.gas_limit_from_mem(gas_limit_ptr, gas_limit_size)?;
+
+                let ret = self.call_contract_host_buf(
+                    key_contract,
+                    &entry_point,
+                    args_bytes,
+                    result_size_ptr,
+                    false,
+                    gas_limit,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
 
-                let ret = self.call_contract_host_buf(key_contract, args_bytes, result_size_ptr)?;
+            FunctionIndex::TryCallContractIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to entry point name in Wasm memory
+                // args(3) = size of entry point name
+                // args(4) = pointer to function arguments in Wasm memory
+                // args(5) = size of arguments
+                // args(6) = pointer to serialized optional gas limit in Wasm memory
+                // args(7) = size of gas limit
+                // args(8) = pointer to result size (output)
+                let (
+                    key_ptr,
+                    key_size,
+                    entry_point_ptr,
+                    entry_point_size,
+                    args_ptr,
+                    args_size,
+                    gas_limit_ptr,
+                    gas_limit_size,
+                    result_size_ptr,
+                ): (_, _, _, _, _, u32, _, u32, _) = Args::parse(args)?;
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let entry_point = self.string_from_mem(entry_point_ptr, entry_point_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+                self.trace_call_args(&args_bytes);
+                let gas_limit = self.gas_limit_from_mem(gas_limit_ptr, gas_limit_size)?;
+
+                let ret = self.try_call_contract_host_buf(
+                    key_contract,
+                    &entry_point,
+                    args_bytes,
+                    result_size_ptr,
+                    gas_limit,
+                )?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
@@ -223,12 +335,9 @@ where
                 let (name_ptr, name_size, urefs_ptr, urefs_size, hash_ptr) = Args::parse(args)?;
                 let _uref_type: u32 = urefs_size;
                 let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
-                let uref_bytes = self
-                    .memory
-                    .get(urefs_ptr, urefs_size as usize)
-                    .map_err(Error::Interpreter)?;
-                let urefs = bytesrepr::deserialize(uref_bytes).map_err(Error::BytesRepr)?;
-                let contract_hash = self.store_function(fn_bytes, urefs)?;
+                let urefs = self.named_keys_from_mem(urefs_ptr, urefs_size)?;
+                let entry_points = vec![DEFAULT_ENTRY_POINT.to_string()];
+                let contract_hash = self.store_function(fn_bytes, urefs, entry_points, None)?;
                 self.function_address(contract_hash, hash_ptr)?;
                 Ok(None)
             }
@@ -244,12 +353,55 @@ where
                 let (name_ptr, name_size, urefs_ptr, urefs_size, hash_ptr) = Args::parse(args)?;
                 let _uref_type: u32 = urefs_size;
                 let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
-                let uref_bytes = self
-                    .memory
-                    .get(urefs_ptr, urefs_size as usize)
-                    .map_err(Error::Interpreter)?;
-                let urefs = bytesrepr::deserialize(uref_bytes).map_err(Error::BytesRepr)?;
-                let contract_hash = self.store_function_at_hash(fn_bytes, urefs)?;
+                let urefs = self.named_keys_from_mem(urefs_ptr, urefs_size)?;
+                let entry_points = vec![DEFAULT_ENTRY_POINT.to_string()];
+                let contract_hash =
+                    self.store_function_at_hash(fn_bytes, urefs, entry_points, None)?;
+                self.function_address(contract_hash, hash_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::StoreContractIndex => {
+                // args(0) = pointer to serialized names of entry points in Wasm memory
+                // args(1) = size of the names
+                // args(2) = pointer to additional unforgable names
+                //           to be saved with the contract
+                // args(3) = size of the additional unforgable names
+                // args(4) = pointer to the serialized optional ABI of the contract
+                // args(5) = size of the ABI
+                // args(6) = pointer to a Wasm memory where we will save
+                //           uref address of the new contract
+                let (names_ptr, names_size, urefs_ptr, urefs_size, abi_ptr, abi_size, hash_ptr) =
+                    Args::parse(args)?;
+                let _uref_type: u32 = urefs_size;
+                let (fn_bytes, entry_points) =
+                    self.get_functions_by_names(names_ptr, names_size)?;
+                let urefs = self.named_keys_from_mem(urefs_ptr, urefs_size)?;
+                let abi = self.abi_from_mem(abi_ptr, abi_size, &entry_points)?;
+                let contract_hash = self.store_function(fn_bytes, urefs, entry_points, abi)?;
+                self.function_address(contract_hash, hash_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::StoreContractAtHashIndex => {
+                // args(0) = pointer to serialized names of entry points in Wasm memory
+                // args(1) = size of the names
+                // args(2) = pointer to additional unforgable names
+                //           to be saved with the contract
+                // args(3) = size of the additional unforgable names
+                // args(4) = pointer to the serialized optional ABI of the contract
+                // args(5) = size of the ABI
+                // args(6) = pointer to a Wasm memory where we will save
+                //           hash of the new contract
+                let (names_ptr, names_size, urefs_ptr, urefs_size, abi_ptr, abi_size, hash_ptr) =
+                    Args::parse(args)?;
+                let _uref_type: u32 = urefs_size;
+                let (fn_bytes, entry_points) =
+                    self.get_functions_by_names(names_ptr, names_size)?;
+                let urefs = self.named_keys_from_mem(urefs_ptr, urefs_size)?;
+                let abi = self.abi_from_mem(abi_ptr, abi_size, &entry_points)?;
+                let contract_hash =
+                    self.store_function_at_hash(fn_bytes, urefs, entry_points, abi)?;
                 self.function_address(contract_hash, hash_ptr)?;
                 Ok(None)
             }
@@ -322,14 +474,8 @@ where
                 // args(3) = length of array of bytes of an amount
                 let (key_ptr, key_size, amount_ptr, amount_size): (u32, u32, u32, u32) =
                     Args::parse(args)?;
-                let public_key: PublicKey = {
-                    let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
-                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
-                };
-                let amount: U512 = {
-                    let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
-                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
-                };
+                let public_key = self.public_key_from_mem(key_ptr, key_size)?;
+                let amount = self.amount_from_mem(amount_ptr, amount_size)?;
                 let ret = self.transfer_to_account(public_key, amount)?;
                 Ok(Some(RuntimeValue::I32(TransferredTo::i32_from(ret))))
             }
@@ -350,18 +496,9 @@ where
                     u32,
                 ) = Args::parse(args)?;
 
-                let source_purse = {
-                    let bytes = self.bytes_from_mem(source_ptr, source_size as usize)?;
-                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
-                };
-                let public_key: PublicKey = {
-                    let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
-                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
-                };
-                let amount: U512 = {
-                    let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
-                    bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
-                };
+                let source_purse = self.purse_id_from_mem(source_ptr, source_size)?;
+                let public_key = self.public_key_from_mem(key_ptr, key_size)?;
+                let amount = self.amount_from_mem(amount_ptr, amount_size)?;
                 let ret = self.transfer_from_purse_to_account(source_purse, public_key, amount)?;
                 Ok(Some(RuntimeValue::I32(TransferredTo::i32_from(ret))))
             }
@@ -434,6 +571,73 @@ where
                 let ret = self.read_host_buffer(dest_ptr, dest_size as usize, bytes_written_ptr)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
+
+            FunctionIndex::EmitEventIndex => {
+                // args(0) = pointer to name in Wasm memory
+                // args(1) = size of name in Wasm memory
+                // args(2) = pointer to value in Wasm memory
+                // args(3) = size of value
+                let (name_ptr, name_size, value_ptr, value_size) = Args::parse(args)?;
+                self.charge_bytes(
+                    host_function_costs.event_byte,
+                    name_size.saturating_add(value_size),
+                )?;
+                self.emit_event(name_ptr, name_size, value_ptr, value_size)?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Returns the fixed cost of calling the host function `func`.
+fn host_function_cost(func: &FunctionIndex, host_function_costs: &HostFunctionCosts) -> u32 {
+    match func {
+        FunctionIndex::ReadFuncIndex => host_function_costs.read_value,
+        FunctionIndex::ReadLocalFuncIndex => host_function_costs.read_value_local,
+        FunctionIndex::LoadNamedKeysFuncIndex => host_function_costs.load_named_keys,
+        FunctionIndex::WriteFuncIndex => host_function_costs.write,
+        FunctionIndex::WriteLocalFuncIndex => host_function_costs.write_local,
+        FunctionIndex::AddFuncIndex => host_function_costs.add,
+        FunctionIndex::AddLocalFuncIndex => host_function_costs.add_local,
+        FunctionIndex::NewFuncIndex => host_function_costs.new_uref,
+        FunctionIndex::GetArgSizeFuncIndex => host_function_costs.get_arg_size,
+        FunctionIndex::GetArgFuncIndex => host_function_costs.get_arg,
+        FunctionIndex::RetFuncIndex => host_function_costs.ret,
+        FunctionIndex::CallContractFuncIndex => host_function_costs.call_contract,
+        FunctionIndex::GetKeyFuncIndex => host_function_costs.get_key,
+        FunctionIndex::HasKeyFuncIndex => host_function_costs.has_key,
+        FunctionIndex::PutKeyFuncIndex => host_function_costs.put_key,
+        // The gas function does the metering of Wasm instructions itself
+        FunctionIndex::GasFuncIndex => 0,
+        FunctionIndex::StoreFnIndex => host_function_costs.store_function,
+        FunctionIndex::StoreFnAtHashIndex => host_function_costs.store_function_at_hash,
+        FunctionIndex::IsValidURefFnIndex => host_function_costs.is_valid_uref,
+        FunctionIndex::RevertFuncIndex => host_function_costs.revert,
+        FunctionIndex::AddAssociatedKeyFuncIndex => host_function_costs.add_associated_key,
+        FunctionIndex::RemoveAssociatedKeyFuncIndex => host_function_costs.remove_associated_key,
+        FunctionIndex::UpdateAssociatedKeyFuncIndex => host_function_costs.update_associated_key,
+        FunctionIndex::SetActionThresholdFuncIndex => host_function_costs.set_action_threshold,
+        FunctionIndex::RemoveKeyFuncIndex => host_function_costs.remove_key,
+        FunctionIndex::GetCallerIndex => host_function_costs.get_caller,
+        FunctionIndex::GetBlocktimeIndex => host_function_costs.get_blocktime,
+        FunctionIndex::CreatePurseIndex => host_function_costs.create_purse,
+        FunctionIndex::TransferToAccountIndex => host_function_costs.transfer_to_account,
+        FunctionIndex::TransferFromPurseToAccountIndex => {
+            host_function_costs.transfer_from_purse_to_account
+        }
+        FunctionIndex::TransferFromPurseToPurseIndex => {
+            host_function_costs.transfer_from_purse_to_purse
         }
+        FunctionIndex::GetBalanceIndex => host_function_costs.get_balance,
+        FunctionIndex::GetPhaseIndex => host_function_costs.get_phase,
+        FunctionIndex::UpgradeContractAtURefIndex => host_function_costs.upgrade_contract_at_uref,
+        FunctionIndex::GetSystemContractIndex => host_function_costs.get_system_contract,
+        FunctionIndex::GetMainPurseIndex => host_function_costs.get_main_purse,
+        FunctionIndex::ReadHostBufferIndex => host_function_costs.read_host_buffer,
+        FunctionIndex::EmitEventIndex => host_function_costs.emit_event,
+        FunctionIndex::StoreContractIndex => host_function_costs.store_contract,
+        FunctionIndex::StoreContractAtHashIndex => host_function_costs.store_contract_at_hash,
+        FunctionIndex::TryCallContractIndex => host_function_costs.try_call_contract,
+        FunctionIndex::CallContractEntryPointIndex => host_function_costs.call_contract,
     }
 }
@@ -6,6 +6,7 @@ use std::{
     collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
     iter::IntoIterator,
+    mem,
 };
 
 use itertools::Itertools;
@@ -17,17 +18,24 @@ use engine_shared::{account::Account, contract::Contract, gas::Gas, stored_value
 use engine_storage::global_state::StateReader;
 use types::{
     account::{ActionType, PublicKey, PurseId, Weight, PUBLIC_KEY_SERIALIZED_LENGTH},
+    api_error,
     bytesrepr::{self, ToBytes},
     system_contract_errors,
     system_contract_errors::mint,
-    AccessRights, ApiError, CLType, CLValue, Key, ProtocolVersion, SystemContractType,
-    TransferResult, TransferredTo, URef, U128, U256, U512,
+    AccessRights, ApiError, CLType, CLValue, ContractAbi, EntryPointAbi, Key, ProtocolVersion,
+    SystemContractType, TransferResult, TransferredTo, URef, DEFAULT_ENTRY_POINT, U128, U256, U512,
 };
 
-use super::{Error, MINT_NAME, POS_NAME};
+use super::{
+    event::Event,
+    trace::{CallFrame, HostCall, HostCallArg},
+    Error, MINT_NAME, POS_NAME,
+};
 use crate::{
     engine_state::system_contract_cache::SystemContractCache,
-    resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
+    resolvers::{
+        create_module_resolver, memory_resolver::MemoryResolver, v1_function_index::FunctionIndex,
+    },
     runtime_context::RuntimeContext,
     Address,
 };
@@ -38,6 +46,12 @@ pub struct Runtime<'a, R> {
     module: Module,
     host_buf: Option<CLValue>,
     context: RuntimeContext<'a, R>,
+    trace: Option<CallFrame>,
+    // The traces of contracts run by the host function currently being handled
+    callee_traces: Vec<CallFrame>,
+    // The arguments read from Wasm memory by the host function currently being handled
+    host_call_args: Vec<HostCallArg>,
+    events: Vec<Event>,
 }
 
 /// Rename function called `name` in the `module` to `call`.
@@ -56,6 +70,61 @@ pub fn rename_export_to_call(module: &mut Module, name: String) {
     main_export.push_str("call");
 }
 
+/// Makes the function exported from `module` as `entry_point` the one run when calling it, by
+/// exporting it as `call` in place of the function exported under that name, if any.
+pub fn select_entry_point(module: &mut Module, entry_point: &str) -> Result<(), Error> {
+    if entry_point == DEFAULT_ENTRY_POINT {
+        return Ok(());
+    }
+    let exports = match module.export_section_mut() {
+        Some(export_section) => export_section.entries_mut(),
+        None => return Err(Error::FunctionNotFound(entry_point.to_string())),
+    };
+    if !exports.iter().any(|export| export.field() == entry_point) {
+        return Err(Error::FunctionNotFound(entry_point.to_string()));
+    }
+    exports.retain(|export| export.field() != DEFAULT_ENTRY_POINT);
+    rename_export_to_call(module, entry_point.to_string());
+    Ok(())
+}
+
+/// Checks that `args_bytes` are arguments of the types expected by `entry_point`, if the ABI of
+/// `contract` describes it.
+pub fn check_args(contract: &Contract, entry_point: &str, args_bytes: &[u8]) -> Result<(), Error> {
+    let entry_point_abi = match contract.abi().and_then(|abi| abi.entry_point(entry_point)) {
+        Some(entry_point_abi) => entry_point_abi,
+        None => return Ok(()),
+    };
+    let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes.to_vec())?;
+    if entry_point_abi.accepts(&args) {
+        return Ok(());
+    }
+    Err(Error::InvalidArgs {
+        entry_point: entry_point.to_string(),
+        expected: entry_point_abi
+            .parameters()
+            .iter()
+            .map(|parameter| parameter.cl_type().clone())
+            .collect(),
+        actual: args.iter().map(|arg| arg.cl_type().clone()).collect(),
+    })
+}
+
+/// Checks that `value`, returned by an entry point, is of the type given in `entry_point_abi`, if
+/// the contract's ABI describes the entry point.
+pub fn check_ret(entry_point_abi: Option<&EntryPointAbi>, value: &CLValue) -> Result<(), Error> {
+    match entry_point_abi {
+        Some(entry_point_abi) if !entry_point_abi.returns(value) => {
+            Err(Error::InvalidReturnValue {
+                entry_point: entry_point_abi.name().to_string(),
+                expected: entry_point_abi.ret().clone(),
+                actual: value.cl_type().clone(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 pub fn instance_and_memory(
     parity_module: Module,
     protocol_version: ProtocolVersion,
@@ -1343,6 +1412,11 @@ fn sub_call<R>(
     // the contract takes a uref argument).
     extra_urefs: Vec<Key>,
     protocol_version: ProtocolVersion,
+    // Whether the gas used by the callee is added to the caller's gas counter, whatever the
+    // outcome of the call.
+    charge_callee_gas: bool,
+    // The most gas the callee may use.  Implies `charge_callee_gas`.
+    gas_limit: Option<Gas>,
 ) -> Result<CLValue, Error>
 where
     R: StateReader<Key, StoredValue>,
@@ -1360,6 +1434,13 @@ where
 
     let system_contract_cache = SystemContractCache::clone(&current_runtime.system_contract_cache);
 
+    // The callee's gas counter starts from the caller's, so its limit is the caller's counter plus
+    // the given budget, capped at the caller's own limit.
+    let caller_gas_limit = current_runtime.context.gas_limit();
+    let callee_gas_limit = gas_limit
+        .and_then(|limit| current_runtime.context.gas_counter().checked_add(limit))
+        .map_or(caller_gas_limit, |limit| cmp::min(limit, caller_gas_limit));
+
     let mut runtime = Runtime {
         system_contract_cache,
         memory,
@@ -1375,7 +1456,7 @@ where
             key,
             current_runtime.context.get_blocktime(),
             current_runtime.context.get_deployhash(),
-            current_runtime.context.gas_limit(),
+            callee_gas_limit,
             current_runtime.context.gas_counter(),
             current_runtime.context.fn_store_id(),
             current_runtime.context.address_generator(),
@@ -1384,20 +1465,45 @@ where
             current_runtime.context.phase(),
             current_runtime.context.protocol_data(),
         ),
+        trace: None,
+        callee_traces: Vec::new(),
+        host_call_args: Vec::new(),
+        events: Vec::new(),
     };
+    // A read-only caller must not be able to change global state through a callee.
+    runtime
+        .context
+        .set_read_only(current_runtime.context.is_read_only());
+    if current_runtime.is_tracing() {
+        runtime.enable_trace();
+    }
 
     let result = instance.invoke_export("call", &[], &mut runtime);
 
+    if let Some(trace) = runtime.take_trace() {
+        current_runtime.callee_traces.push(trace);
+    }
+
     // TODO: To account for the gas used in a subcall, we should uncomment the following lines
     // if !current_runtime.charge_gas(runtime.context.gas_counter()) {
     //     return Err(Error::GasLimit);
     // }
+    if charge_callee_gas || gas_limit.is_some() {
+        // The callee's gas counter started from the caller's, so it already holds the gas used by
+        // both.  Any of the callee's budget left unused is not charged.
+        current_runtime
+            .context
+            .set_gas_counter(runtime.context.gas_counter());
+    }
 
     match result {
         // If `Ok` and the `host_buf` is `None`, the contract's execution succeeded but did not
         // explicitly call `runtime::ret()`.  Treat as though the execution returned the unit type
         // `()` as per Rust functions which don't specify a return value.
-        Ok(_) => Ok(runtime.take_host_buf().unwrap_or(CLValue::from_t(())?)),
+        Ok(_) => {
+            current_runtime.events.append(&mut runtime.events);
+            Ok(runtime.take_host_buf().unwrap_or(CLValue::from_t(())?))
+        }
         Err(e) => {
             if let Some(host_error) = e.as_host_error() {
                 // If the "error" was in fact a trap caused by calling `ret` then
@@ -1410,6 +1516,7 @@ where
                         let ret_urefs_map: HashMap<Address, HashSet<AccessRights>> =
                             extract_access_rights_from_urefs(ret_urefs.clone());
                         current_runtime.context.access_rights_extend(ret_urefs_map);
+                        current_runtime.events.append(&mut runtime.events);
                         // if ret has not set host_buf consider it programmer error
                         return runtime.take_host_buf().ok_or(Error::ExpectedReturnValue);
                     }
@@ -1422,6 +1529,19 @@ where
                         // TODO: https://casperlabs.atlassian.net/browse/EE-771
                         return Err(Error::InvalidContext);
                     }
+                    Error::WriteInReadOnlyContext => {
+                        return Err(Error::WriteInReadOnlyContext);
+                    }
+                    Error::GasLimit => {
+                        if let Some(limit) = gas_limit {
+                            // The callee's budget is used up, so charge all of it.
+                            current_runtime.context.set_gas_counter(callee_gas_limit);
+                            if callee_gas_limit < caller_gas_limit {
+                                return Err(Error::SubCallGasLimit(limit));
+                            }
+                            return Err(Error::GasLimit);
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -1447,6 +1567,10 @@ where
             module,
             host_buf: None,
             context,
+            trace: None,
+            callee_traces: Vec::new(),
+            host_call_args: Vec::new(),
+            events: Vec::new(),
         }
     }
 
@@ -1454,22 +1578,54 @@ where
         &self.context
     }
 
+    /// Starts recording every host function called from here on, along with the keys it touched
+    /// and the host functions called by any contracts it ran.
+    pub fn enable_trace(&mut self) {
+        self.trace = Some(CallFrame::new(self.context.base_key()));
+        self.context.record_touched_keys();
+    }
+
+    /// Returns the host functions called since tracing was enabled, or `None` if it wasn't.
+    pub fn take_trace(&mut self) -> Option<CallFrame> {
+        self.trace.take()
+    }
+
+    /// Returns the events emitted so far, including those of contracts which were called and
+    /// returned successfully.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        mem::replace(&mut self.events, Vec::new())
+    }
+
+    fn is_tracing(&self) -> bool {
+        self.trace.is_some()
+    }
+
+    /// Records `arg` as read from Wasm memory by the host function being handled, if tracing.
+    fn trace_arg<F: FnOnce() -> HostCallArg>(&mut self, arg: F) {
+        if self.is_tracing() {
+            self.host_call_args.push(arg());
+        }
+    }
+
+    /// Appends a call to `function` to the trace, along with everything recorded while handling
+    /// it.
+    fn record_host_call(&mut self, function: FunctionIndex) {
+        let args = mem::replace(&mut self.host_call_args, Vec::new());
+        let keys = self.context.take_touched_keys();
+        let gas_used = self.context.gas_counter();
+        let calls = mem::replace(&mut self.callee_traces, Vec::new());
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(HostCall::new(function, args, keys, gas_used, calls));
+        }
+    }
+
     /// Charge specified amount of gas
     ///
     /// Returns false if gas limit exceeded and true if not.
     /// Intuition about the return value sense is to answer the question 'are we
     /// allowed to continue?'
     fn charge_gas(&mut self, amount: Gas) -> bool {
-        let prev = self.context.gas_counter();
-        match prev.checked_add(amount) {
-            // gas charge overflow protection
-            None => false,
-            Some(val) if val > self.context.gas_limit() => false,
-            Some(val) => {
-                self.context.set_gas_counter(val);
-                true
-            }
-        }
+        self.context.charge_gas(amount).is_ok()
     }
 
     fn gas(&mut self, amount: Gas) -> Result<(), Trap> {
@@ -1480,6 +1636,20 @@ where
         }
     }
 
+    /// Charges `cost_per_byte` for each of `size` bytes.
+    fn charge_bytes(&mut self, cost_per_byte: u32, size: u32) -> Result<(), Trap> {
+        self.gas(Gas::new(U512::from(cost_per_byte) * U512::from(size)))
+    }
+
+    /// Charges `cost_per_byte` for each byte of the value held in the host buffer.
+    fn charge_host_buf_bytes(&mut self, cost_per_byte: u32) -> Result<(), Trap> {
+        let size = self
+            .host_buf
+            .as_ref()
+            .map_or(0, |value| value.inner_bytes().len() as u32);
+        self.charge_bytes(cost_per_byte, size)
+    }
+
     fn bytes_from_mem(&self, ptr: u32, size: usize) -> Result<Vec<u8>, Error> {
         self.memory.get(ptr, size).map_err(Into::into)
     }
@@ -1487,7 +1657,9 @@ where
     /// Reads key (defined as `key_ptr` and `key_size` tuple) from Wasm memory.
     fn key_from_mem(&mut self, key_ptr: u32, key_size: u32) -> Result<Key, Error> {
         let bytes = self.bytes_from_mem(key_ptr, key_size as usize)?;
-        bytesrepr::deserialize(bytes).map_err(Into::into)
+        let key: Key = bytesrepr::deserialize(bytes)?;
+        self.trace_arg(|| HostCallArg::Key(key));
+        Ok(key)
     }
 
     /// Reads `CLValue` (defined as `cl_value_ptr` and `cl_value_size` tuple) from Wasm memory.
@@ -1497,12 +1669,89 @@ where
         cl_value_size: u32,
     ) -> Result<CLValue, Error> {
         let bytes = self.bytes_from_mem(cl_value_ptr, cl_value_size as usize)?;
-        bytesrepr::deserialize(bytes).map_err(Into::into)
+        let cl_value: CLValue = bytesrepr::deserialize(bytes)?;
+        self.trace_arg(|| HostCallArg::Value(cl_value.clone()));
+        Ok(cl_value)
+    }
+
+    fn string_from_mem(&mut self, ptr: u32, size: u32) -> Result<String, Trap> {
+        let bytes = self.bytes_from_mem(ptr, size as usize)?;
+        let string: String = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
+        self.trace_arg(|| HostCallArg::Name(string.clone()));
+        Ok(string)
+    }
+
+    /// Reads a public key from Wasm memory.
+    fn public_key_from_mem(&mut self, ptr: u32, size: u32) -> Result<PublicKey, Error> {
+        let bytes = self.bytes_from_mem(ptr, size as usize)?;
+        let public_key: PublicKey = bytesrepr::deserialize(bytes)?;
+        self.trace_arg(|| HostCallArg::Key(Key::Account(public_key.value())));
+        Ok(public_key)
+    }
+
+    /// Reads a purse id from Wasm memory.
+    fn purse_id_from_mem(&mut self, ptr: u32, size: u32) -> Result<PurseId, Error> {
+        let bytes = self.bytes_from_mem(ptr, size as usize)?;
+        let purse_id: PurseId = bytesrepr::deserialize(bytes)?;
+        self.trace_arg(|| HostCallArg::Key(Key::URef(purse_id.value())));
+        Ok(purse_id)
+    }
+
+    /// Reads an amount of motes from Wasm memory.
+    fn amount_from_mem(&mut self, ptr: u32, size: u32) -> Result<U512, Error> {
+        let bytes = self.bytes_from_mem(ptr, size as usize)?;
+        let amount: U512 = bytesrepr::deserialize(bytes.clone())?;
+        self.trace_arg(|| HostCallArg::Value(CLValue::from_components(CLType::U512, bytes)));
+        Ok(amount)
+    }
+
+    /// Reads the named keys to store along with a contract from Wasm memory.
+    fn named_keys_from_mem(&mut self, ptr: u32, size: u32) -> Result<BTreeMap<String, Key>, Error> {
+        let bytes = self.bytes_from_mem(ptr, size as usize)?;
+        let named_keys: BTreeMap<String, Key> = bytesrepr::deserialize(bytes)?;
+        for (name, key) in &named_keys {
+            self.trace_arg(|| HostCallArg::Name(name.clone()));
+            self.trace_arg(|| HostCallArg::Key(*key));
+        }
+        Ok(named_keys)
+    }
+
+    /// Records the arguments passed to a called contract, if tracing.
+    fn trace_call_args(&mut self, args_bytes: &[u8]) {
+        if !self.is_tracing() {
+            return;
+        }
+        // Arguments which don't deserialize are rejected when calling the contract.
+        if let Ok(args) = bytesrepr::deserialize::<Vec<CLValue>>(args_bytes.to_vec()) {
+            self.host_call_args
+                .extend(args.into_iter().map(HostCallArg::Value));
+        }
     }
 
-    fn string_from_mem(&self, ptr: u32, size: u32) -> Result<String, Trap> {
+    /// Reads the optional gas limit of a contract call from Wasm memory.
+    fn gas_limit_from_mem(&self, ptr: u32, size: u32) -> Result<Option<Gas>, Error> {
         let bytes = self.bytes_from_mem(ptr, size as usize)?;
-        bytesrepr::deserialize(bytes).map_err(|e| Error::BytesRepr(e).into())
+        let maybe_limit: Option<U512> = bytesrepr::deserialize(bytes)?;
+        Ok(maybe_limit.map(Gas::new))
+    }
+
+    /// Records an event with the name and value found in Wasm memory.
+    ///
+    /// Events are effects of the deploy, so they can't be emitted in a read-only context.
+    fn emit_event(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Trap> {
+        if self.context.is_read_only() {
+            return Err(Error::WriteInReadOnlyContext.into());
+        }
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let value = self.cl_value_from_mem(value_ptr, value_size)?;
+        self.events.push(Event::new(name, value));
+        Ok(())
     }
 
     fn get_function_by_name(&mut self, name_ptr: u32, name_size: u32) -> Result<Vec<u8>, Trap> {
@@ -1532,10 +1781,74 @@ where
         }
     }
 
+    /// Reads the serialized names of entry points from Wasm memory and returns them along with the
+    /// current module stripped of everything but the functions exported under those names.
+    fn get_functions_by_names(
+        &mut self,
+        names_ptr: u32,
+        names_size: u32,
+    ) -> Result<(Vec<u8>, Vec<String>), Trap> {
+        let bytes = self.bytes_from_mem(names_ptr, names_size as usize)?;
+        let names: Vec<String> = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
+        for name in &names {
+            self.trace_arg(|| HostCallArg::Name(name.clone()));
+        }
+
+        let exports: Vec<&str> = self
+            .module
+            .export_section()
+            .map(|export_section| {
+                export_section
+                    .entries()
+                    .iter()
+                    .map(|export_entry| export_entry.field())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some(name) = names.iter().find(|name| !exports.contains(&name.as_str())) {
+            return Err(Error::FunctionNotFound(name.clone()).into());
+        }
+
+        let mut module = self.module.clone();
+        // Unlike with `get_function_by_name`, the functions keep their names so that each of them
+        // can be called.
+        pwasm_utils::optimize(&mut module, names.iter().map(String::as_str).collect()).unwrap();
+
+        let fn_bytes = parity_wasm::serialize(module).map_err(Error::ParityWasm)?;
+        Ok((fn_bytes, names))
+    }
+
+    /// Reads the optional ABI of a contract being stored, checking that it only describes some of
+    /// the contract's `entry_points`.
+    fn abi_from_mem(
+        &mut self,
+        abi_ptr: u32,
+        abi_size: u32,
+        entry_points: &[String],
+    ) -> Result<Option<ContractAbi>, Trap> {
+        let bytes = self.bytes_from_mem(abi_ptr, abi_size as usize)?;
+        let maybe_abi: Option<ContractAbi> =
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
+        if let Some(abi) = maybe_abi.as_ref() {
+            if let Some(entry_point) = abi
+                .entry_points()
+                .iter()
+                .find(|entry_point| !entry_points.iter().any(|name| name == entry_point.name()))
+            {
+                return Err(Error::FunctionNotFound(entry_point.name().to_string()).into());
+            }
+            if let Some(name) = abi.duplicate_entry_point() {
+                return Err(Error::DuplicateEntryPoint(name.to_string()).into());
+            }
+        }
+        Ok(maybe_abi)
+    }
+
     fn is_valid_uref(&mut self, uref_ptr: u32, uref_size: u32) -> Result<bool, Trap> {
         let bytes = self.bytes_from_mem(uref_ptr, uref_size as usize)?;
         let uref: URef = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
         let key = Key::URef(uref);
+        self.trace_arg(|| HostCallArg::Key(key));
         Ok(self.context.validate_key(&key).is_ok())
     }
 
@@ -1718,8 +2031,15 @@ where
         }
     }
 
-    /// Calls contract living under a `key`, with supplied `args`.
-    fn call_contract(&mut self, key: Key, args_bytes: Vec<u8>) -> Result<CLValue, Error> {
+    /// Calls the `entry_point` of the contract living under a `key`, with supplied `args`.
+    fn call_contract(
+        &mut self,
+        key: Key,
+        entry_point: &str,
+        args_bytes: Vec<u8>,
+        charge_callee_gas: bool,
+        gas_limit: Option<Gas>,
+    ) -> Result<CLValue, Error> {
         let contract = match self.context.read_gs(&key)? {
             Some(StoredValue::Contract(contract)) => contract,
             Some(_) => {
@@ -1731,6 +2051,10 @@ where
             None => return Err(Error::KeyNotFound(key)),
         };
 
+        if !contract.has_entry_point(entry_point) {
+            return Err(Error::FunctionNotFound(entry_point.to_string()));
+        }
+
         // Check for major version compatibility before calling
         let contract_version = contract.protocol_version();
         let current_version = self.context.protocol_version();
@@ -1741,17 +2065,26 @@ where
             });
         }
 
+        check_args(&contract, entry_point, &args_bytes)?;
         let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes)?;
+        let entry_point_abi = contract
+            .abi()
+            .and_then(|abi| abi.entry_point(entry_point))
+            .cloned();
 
         let maybe_module = match key {
             Key::URef(uref) => self.system_contract_cache.get(&uref),
             _ => None,
         };
 
-        let module = match maybe_module {
+        let mut module = match maybe_module {
             Some(module) => module,
-            None => parity_wasm::deserialize_buffer(contract.bytes())?,
+            None => {
+                let contract_wasm = self.context.read_contract_wasm(contract.wasm_hash())?;
+                parity_wasm::deserialize_buffer(contract_wasm.bytes())?
+            }
         };
+        select_entry_point(&mut module, entry_point)?;
 
         let mut extra_urefs = vec![];
         // A loop is needed to be able to use the '?' operator
@@ -1776,22 +2109,42 @@ where
             self,
             extra_urefs,
             contract_version,
+            charge_callee_gas,
+            gas_limit,
         )?;
+        check_ret(entry_point_abi.as_ref(), &result)?;
         Ok(result)
     }
 
     fn call_contract_host_buf(
         &mut self,
         key: Key,
+        entry_point: &str,
         args_bytes: Vec<u8>,
         result_size_ptr: u32,
+        charge_callee_gas: bool,
+        gas_limit: Option<Gas>,
     ) -> Result<Result<(), ApiError>, Error> {
         if !self.can_write_to_host_buf() {
             // Exit early if the host buffer is already occupied
             return Ok(Err(ApiError::HostBufferFull));
         }
 
-        let result = self.call_contract(key, args_bytes)?;
+        // If a capped callee goes over its limit, its changes to global state are discarded and the
+        // error is returned to the caller, which may carry on with the rest of its own gas.
+        let checkpoint = match gas_limit {
+            Some(_) => Some(self.context.state().borrow_mut().checkpoint()),
+            None => None,
+        };
+        let result = self.call_contract(key, entry_point, args_bytes, charge_callee_gas, gas_limit);
+        if let Some(checkpoint) = checkpoint {
+            if let Err(Error::SubCallGasLimit(_)) = result {
+                self.context.state().borrow_mut().rollback(checkpoint);
+                return Ok(Err(ApiError::GasLimit));
+            }
+            self.context.state().borrow_mut().release(checkpoint);
+        }
+        let result = result?;
         let result_size = result.inner_bytes().len() as u32; // considered to be safe
 
         if let Err(error) = self.write_host_buf(result) {
@@ -1806,6 +2159,37 @@ where
         Ok(Ok(()))
     }
 
+    /// Calls the contract like `call_contract_host_buf`, except that if the callee reverts, its
+    /// changes to global state are discarded and the error is returned to the caller instead of
+    /// aborting the caller too.  The gas used by the callee is charged either way.
+    fn try_call_contract_host_buf(
+        &mut self,
+        key: Key,
+        entry_point: &str,
+        args_bytes: Vec<u8>,
+        result_size_ptr: u32,
+        gas_limit: Option<Gas>,
+    ) -> Result<Result<(), ApiError>, Error> {
+        let checkpoint = self.context.state().borrow_mut().checkpoint();
+        let result = self.call_contract_host_buf(
+            key,
+            entry_point,
+            args_bytes,
+            result_size_ptr,
+            true,
+            gas_limit,
+        );
+        let error = match result {
+            Err(Error::Revert(status)) => api_error::result_from(status as i32),
+            result => {
+                self.context.state().borrow_mut().release(checkpoint);
+                return result;
+            }
+        };
+        self.context.state().borrow_mut().rollback(checkpoint);
+        Ok(error)
+    }
+
     fn load_named_keys(
         &mut self,
         total_keys_ptr: u32,
@@ -1847,8 +2231,15 @@ where
         &mut self,
         fn_bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
+        entry_points: Vec<String>,
+        maybe_abi: Option<ContractAbi>,
     ) -> Result<[u8; 32], Error> {
-        let contract = Contract::new(fn_bytes, named_keys, self.context.protocol_version());
+        let wasm_hash = self.context.store_contract_wasm(fn_bytes)?;
+        let mut contract = Contract::new(wasm_hash, named_keys, self.context.protocol_version())
+            .with_entry_points(entry_points);
+        if let Some(abi) = maybe_abi {
+            contract = contract.with_abi(abi);
+        }
         let contract_addr = self
             .context
             .store_function(StoredValue::Contract(contract))?;
@@ -1862,8 +2253,15 @@ where
         &mut self,
         fn_bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
+        entry_points: Vec<String>,
+        maybe_abi: Option<ContractAbi>,
     ) -> Result<[u8; 32], Error> {
-        let contract = Contract::new(fn_bytes, named_keys, self.context.protocol_version());
+        let wasm_hash = self.context.store_contract_wasm(fn_bytes)?;
+        let mut contract = Contract::new(wasm_hash, named_keys, self.context.protocol_version())
+            .with_entry_points(entry_points);
+        if let Some(abi) = maybe_abi {
+            contract = contract.with_abi(abi);
+        }
         let new_hash = self
             .context
             .store_function_at_hash(StoredValue::Contract(contract))?;
@@ -2024,15 +2422,8 @@ where
     }
 
     fn add_associated_key(&mut self, public_key_ptr: u32, weight_value: u8) -> Result<i32, Trap> {
-        let public_key = {
-            // Public key as serialized bytes
-            let source_serialized =
-                self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SERIALIZED_LENGTH)?;
-            // Public key deserialized
-            let source: PublicKey =
-                bytesrepr::deserialize(source_serialized).map_err(Error::BytesRepr)?;
-            source
-        };
+        let public_key =
+            self.public_key_from_mem(public_key_ptr, PUBLIC_KEY_SERIALIZED_LENGTH as u32)?;
         let weight = Weight::new(weight_value);
 
         match self.context.add_associated_key(public_key, weight) {
@@ -2048,15 +2439,8 @@ where
     }
 
     fn remove_associated_key(&mut self, public_key_ptr: u32) -> Result<i32, Trap> {
-        let public_key = {
-            // Public key as serialized bytes
-            let source_serialized =
-                self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SERIALIZED_LENGTH)?;
-            // Public key deserialized
-            let source: PublicKey =
-                bytesrepr::deserialize(source_serialized).map_err(Error::BytesRepr)?;
-            source
-        };
+        let public_key =
+            self.public_key_from_mem(public_key_ptr, PUBLIC_KEY_SERIALIZED_LENGTH as u32)?;
         match self.context.remove_associated_key(public_key) {
             Ok(_) => Ok(0),
             Err(Error::RemoveKeyFailure(e)) => Ok(e as i32),
@@ -2069,15 +2453,8 @@ where
         public_key_ptr: u32,
         weight_value: u8,
     ) -> Result<i32, Trap> {
-        let public_key = {
-            // Public key as serialized bytes
-            let source_serialized =
-                self.bytes_from_mem(public_key_ptr, PUBLIC_KEY_SERIALIZED_LENGTH)?;
-            // Public key deserialized
-            let source: PublicKey =
-                bytesrepr::deserialize(source_serialized).map_err(Error::BytesRepr)?;
-            source
-        };
+        let public_key =
+            self.public_key_from_mem(public_key_ptr, PUBLIC_KEY_SERIALIZED_LENGTH as u32)?;
         let weight = Weight::new(weight_value);
 
         match self.context.update_associated_key(public_key, weight) {
@@ -2134,7 +2511,13 @@ where
             ArgsParser::parse(args)?.into_bytes()?
         };
 
-        let result = self.call_contract(mint_contract_key, args_bytes)?;
+        let result = self.call_contract(
+            mint_contract_key,
+            DEFAULT_ENTRY_POINT,
+            args_bytes,
+            false,
+            None,
+        )?;
         let purse_uref = result.into_t()?;
 
         Ok(PurseId::new(purse_uref))
@@ -2162,7 +2545,13 @@ where
             ArgsParser::parse(args)?.into_bytes()?
         };
 
-        let result = self.call_contract(mint_contract_key, args_bytes)?;
+        let result = self.call_contract(
+            mint_contract_key,
+            DEFAULT_ENTRY_POINT,
+            args_bytes,
+            false,
+            None,
+        )?;
         let result: Result<(), mint::Error> = result.into_t()?;
         Ok(result.map_err(system_contract_errors::Error::from)?)
     }
@@ -2295,20 +2684,9 @@ where
         amount_ptr: u32,
         amount_size: u32,
     ) -> Result<Result<(), ApiError>, Error> {
-        let source: PurseId = {
-            let bytes = self.bytes_from_mem(source_ptr, source_size as usize)?;
-            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
-        };
-
-        let target: PurseId = {
-            let bytes = self.bytes_from_mem(target_ptr, target_size as usize)?;
-            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
-        };
-
-        let amount: U512 = {
-            let bytes = self.bytes_from_mem(amount_ptr, amount_size as usize)?;
-            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
-        };
+        let source = self.purse_id_from_mem(source_ptr, source_size)?;
+        let target = self.purse_id_from_mem(target_ptr, target_size)?;
+        let amount = self.amount_from_mem(amount_ptr, amount_size)?;
 
         let mint_contract_key = self.get_mint_contract_uref().into();
 
@@ -2373,6 +2751,7 @@ where
                 Err(error) => return Ok(Err(error.into())),
             }
         };
+        self.trace_arg(|| HostCallArg::Key(Key::URef(purse_id.value())));
 
         let balance = match self.get_balance(purse_id)? {
             Some(balance) => balance,
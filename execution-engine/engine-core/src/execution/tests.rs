@@ -15,6 +15,7 @@ fn on_fail_charge_test_helper<T>(
     ExecutionResult::Success {
         effect: Default::default(),
         cost: success_cost,
+        details: Default::default(),
     }
 }
 
@@ -55,6 +56,7 @@ fn on_fail_charge_with_action() {
         ExecutionResult::Success {
             effect: Default::default(),
             cost: Gas::default(),
+            details: Default::default(),
         }
     };
     match f() {
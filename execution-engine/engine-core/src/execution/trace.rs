@@ -0,0 +1,100 @@
+use engine_shared::gas::Gas;
+use types::{CLValue, Key};
+
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+/// The run of a single contract, or of the session or payment code of a deploy, as recorded when
+/// executing with tracing enabled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallFrame {
+    base_key: Key,
+    host_calls: Vec<HostCall>,
+}
+
+impl CallFrame {
+    pub fn new(base_key: Key) -> Self {
+        CallFrame {
+            base_key,
+            host_calls: Vec::new(),
+        }
+    }
+
+    /// Returns the key of the account or contract which was run.
+    pub fn base_key(&self) -> Key {
+        self.base_key
+    }
+
+    /// Returns the host functions the code called, in order.
+    pub fn host_calls(&self) -> &[HostCall] {
+        &self.host_calls
+    }
+
+    pub fn push(&mut self, host_call: HostCall) {
+        self.host_calls.push(host_call);
+    }
+}
+
+/// An argument of a host function call, as decoded by the host from Wasm memory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HostCallArg {
+    /// A key, e.g. one to read from or the public key of the target account of a transfer.
+    Key(Key),
+    /// A name, e.g. of a named key, an entry point or an event.
+    Name(String),
+    /// A value, e.g. one to write, an amount to transfer or an argument to a called contract.
+    Value(CLValue),
+}
+
+/// A call from Wasm to a host function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostCall {
+    function: FunctionIndex,
+    args: Vec<HostCallArg>,
+    keys: Vec<Key>,
+    gas_used: Gas,
+    calls: Vec<CallFrame>,
+}
+
+impl HostCall {
+    pub fn new(
+        function: FunctionIndex,
+        args: Vec<HostCallArg>,
+        keys: Vec<Key>,
+        gas_used: Gas,
+        calls: Vec<CallFrame>,
+    ) -> Self {
+        HostCall {
+            function,
+            args,
+            keys,
+            gas_used,
+            calls,
+        }
+    }
+
+    pub fn function(&self) -> FunctionIndex {
+        self.function
+    }
+
+    /// Returns the keys, names and values the function read from Wasm memory, in order.
+    pub fn args(&self) -> &[HostCallArg] {
+        &self.args
+    }
+
+    /// Returns the keys read from or written to global state while handling the call, in order.
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+
+    /// Returns the gas used by the calling code once the call had been handled, including the
+    /// cost of the call itself.
+    pub fn gas_used(&self) -> Gas {
+        self.gas_used
+    }
+
+    /// Returns the contracts the call ran, such as the callee of `call_contract` or the mint when
+    /// transferring.
+    pub fn calls(&self) -> &[CallFrame] {
+        &self.calls
+    }
+}
@@ -3,7 +3,7 @@ use std::convert::TryFrom;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
 
-#[derive(Debug, PartialEq, FromPrimitive, ToPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromPrimitive, ToPrimitive)]
 #[repr(usize)]
 pub enum FunctionIndex {
     WriteFuncIndex,
@@ -43,6 +43,11 @@ pub enum FunctionIndex {
     GetMainPurseIndex,
     GetArgSizeFuncIndex,
     ReadHostBufferIndex,
+    EmitEventIndex,
+    StoreContractIndex,
+    StoreContractAtHashIndex,
+    TryCallContractIndex,
+    CallContractEntryPointIndex,
 }
 
 impl Into<usize> for FunctionIndex {
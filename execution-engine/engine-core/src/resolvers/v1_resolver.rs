@@ -188,6 +188,26 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
                 FunctionIndex::ReadHostBufferIndex.into(),
             ),
+            "emit_event" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], None),
+                FunctionIndex::EmitEventIndex.into(),
+            ),
+            "store_contract" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], None),
+                FunctionIndex::StoreContractIndex.into(),
+            ),
+            "store_contract_at_hash" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], None),
+                FunctionIndex::StoreContractAtHashIndex.into(),
+            ),
+            "try_call_contract" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 9][..], Some(ValueType::I32)),
+                FunctionIndex::TryCallContractIndex.into(),
+            ),
+            "call_contract_entry_point" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 9][..], Some(ValueType::I32)),
+                FunctionIndex::CallContractEntryPointIndex.into(),
+            ),
             _ => {
                 return Err(InterpreterError::Function(format!(
                     "host module doesn't export function with name {}",
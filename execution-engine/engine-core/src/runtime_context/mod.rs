@@ -3,6 +3,7 @@ use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     convert::{TryFrom, TryInto},
     fmt::Debug,
+    mem,
     rc::Rc,
 };
 
@@ -12,8 +13,13 @@ use blake2::{
 };
 
 use engine_shared::{
-    account::Account, contract::Contract, gas::Gas, newtypes::CorrelationId,
+    account::Account,
+    contract::Contract,
+    contract_wasm::{ContractWasm, ContractWasmHash},
+    gas::Gas,
+    newtypes::CorrelationId,
     stored_value::StoredValue,
+    transform::TypeMismatch,
 };
 use engine_storage::{global_state::StateReader, protocol_data::ProtocolData};
 use types::{
@@ -28,7 +34,7 @@ use types::{
 use crate::{
     engine_state::{execution_effect::ExecutionEffect, SYSTEM_ACCOUNT_ADDR},
     execution::{AddressGenerator, Error},
-    tracking_copy::{AddResult, TrackingCopy},
+    tracking_copy::{byte_size::ByteSize, AddResult, TrackingCopy},
     Address,
 };
 
@@ -73,6 +79,10 @@ pub struct RuntimeContext<'a, R> {
     correlation_id: CorrelationId,
     phase: Phase,
     protocol_data: ProtocolData,
+    // If set, any attempt to write to, add to or put keys into global state fails
+    read_only: bool,
+    // If set, the keys read from or written to global state since they were last taken
+    touched_keys: Option<Vec<Key>>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -118,6 +128,8 @@ where
             correlation_id,
             phase,
             protocol_data,
+            read_only: false,
+            touched_keys: None,
         }
     }
 
@@ -152,7 +164,7 @@ where
 
         let contract_value = StoredValue::Contract(contract);
 
-        self.state.borrow_mut().write(key, contract_value);
+        self.write_unsafe(key, contract_value)?;
 
         Ok(())
     }
@@ -171,7 +183,7 @@ where
                 };
                 self.named_keys.remove(name);
                 let account_value = self.account_to_validated_value(account)?;
-                self.state.borrow_mut().write(public_key, account_value);
+                self.write_unsafe(public_key, account_value)?;
                 Ok(())
             }
             contract_uref @ Key::URef(_) => {
@@ -246,6 +258,18 @@ where
         self.gas_counter = new_gas_counter;
     }
 
+    /// Adds `amount` to the gas counter, failing with [`Error::GasLimit`] and leaving the counter
+    /// unchanged if that would exceed the gas limit.
+    pub fn charge_gas(&mut self, amount: Gas) -> Result<(), Error> {
+        match self.gas_counter.checked_add(amount) {
+            Some(gas_counter) if gas_counter <= self.gas_limit => {
+                self.gas_counter = gas_counter;
+                Ok(())
+            }
+            _ => Err(Error::GasLimit),
+        }
+    }
+
     pub fn inc_fn_store_id(&mut self) {
         self.fn_store_id += 1;
     }
@@ -275,6 +299,36 @@ where
         self.phase
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Makes every subsequent write, add or key put in this context fail with
+    /// [`Error::WriteInReadOnlyContext`].
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Starts recording every key this context reads from or writes to global state.
+    pub fn record_touched_keys(&mut self) {
+        self.touched_keys = Some(Vec::new());
+    }
+
+    /// Returns the keys touched since this was last called, or nothing if they are not being
+    /// recorded.
+    pub fn take_touched_keys(&mut self) -> Vec<Key> {
+        match self.touched_keys.as_mut() {
+            Some(keys) => mem::replace(keys, Vec::new()),
+            None => Vec::new(),
+        }
+    }
+
+    fn touch(&mut self, key: Key) {
+        if let Some(keys) = self.touched_keys.as_mut() {
+            keys.push(key);
+        }
+    }
+
     /// Generates new function address.
     /// Function address is deterministic. It is a hash of public key, nonce and
     /// `fn_store_id`, which is a counter that is being incremented after
@@ -313,6 +367,9 @@ where
         let named_key_value = StoredValue::CLValue(CLValue::from_t((name.clone(), key))?);
         self.validate_value(&named_key_value)?;
 
+        if !self.named_keys_contains_key(&name) {
+            self.charge_storage(name.byte_size() + key.byte_size())?;
+        }
         self.add_unsafe(self.base_key(), named_key_value)?;
         self.insert_key(name, key);
         Ok(())
@@ -330,6 +387,7 @@ where
         key_bytes: &[u8],
     ) -> Result<Option<CLValue>, Error> {
         let key = Key::local(seed, key_bytes);
+        self.touch(key);
         let maybe_stored_value = self
             .state
             .borrow_mut()
@@ -346,15 +404,15 @@ where
     pub fn write_ls(&mut self, key_bytes: &[u8], cl_value: CLValue) -> Result<(), Error> {
         let seed = self.seed();
         let key = Key::local(seed, key_bytes);
-        self.state
-            .borrow_mut()
-            .write(key, StoredValue::CLValue(cl_value));
-        Ok(())
+        let value = StoredValue::CLValue(cl_value);
+        self.charge_storage(value.byte_size())?;
+        self.write_unsafe(key, value)
     }
 
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         self.validate_readable(key)?;
         self.validate_key(key)?;
+        self.touch(*key);
 
         self.state
             .borrow_mut()
@@ -364,6 +422,7 @@ where
 
     /// DO NOT EXPOSE THIS VIA THE FFI
     pub fn read_gs_direct(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
+        self.touch(*key);
         self.state
             .borrow_mut()
             .read(self.correlation_id, key)
@@ -396,13 +455,15 @@ where
         self.validate_writeable(&key)?;
         self.validate_key(&key)?;
         self.validate_value(&value)?;
-        self.state.borrow_mut().write(key, value);
+        self.charge_storage(value.byte_size())?;
+        self.write_unsafe(key, value)?;
         Ok(())
     }
 
     pub fn read_account(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         if let Key::Account(_) = key {
             self.validate_key(key)?;
+            self.touch(*key);
             self.state
                 .borrow_mut()
                 .read(self.correlation_id, key)
@@ -416,7 +477,7 @@ where
         if let Key::Account(_) = key {
             self.validate_key(&key)?;
             let account_value = self.account_to_validated_value(account)?;
-            self.state.borrow_mut().write(key, account_value);
+            self.write_unsafe(key, account_value)?;
             Ok(())
         } else {
             panic!("Do not use this function for writing non-account keys")
@@ -437,10 +498,42 @@ where
         let new_hash = self.new_function_address()?;
         self.validate_value(&contract)?;
         let hash_key = Key::Hash(new_hash);
-        self.state.borrow_mut().write(hash_key, contract);
+        self.charge_storage(contract.byte_size())?;
+        self.write_unsafe(hash_key, contract)?;
         Ok(new_hash)
     }
 
+    /// Stores `bytes` as a [`ContractWasm`] under its hash, unless the same code is already
+    /// stored there, and returns the hash.
+    pub fn store_contract_wasm(&mut self, bytes: Vec<u8>) -> Result<ContractWasmHash, Error> {
+        let contract_wasm = ContractWasm::new(bytes);
+        let wasm_hash = contract_wasm.hash();
+        let key = Key::Hash(wasm_hash);
+        match self.read_gs_direct(&key)? {
+            Some(StoredValue::ContractWasm(_)) => (),
+            Some(other) => {
+                return Err(Error::TypeMismatch(TypeMismatch::new(
+                    "ContractWasm".to_string(),
+                    other.type_name(),
+                )))
+            }
+            None => {
+                let value = StoredValue::ContractWasm(contract_wasm);
+                self.charge_storage(value.byte_size())?;
+                self.write_unsafe(key, value)?
+            }
+        }
+        Ok(wasm_hash)
+    }
+
+    /// Reads the [`ContractWasm`] stored under `wasm_hash`.
+    pub fn read_contract_wasm(
+        &mut self,
+        wasm_hash: ContractWasmHash,
+    ) -> Result<ContractWasm, Error> {
+        self.read_gs_typed(&Key::Hash(wasm_hash))
+    }
+
     pub fn insert_key(&mut self, name: String, key: Key) {
         if let Key::URef(uref) = key {
             self.insert_uref(uref);
@@ -511,6 +604,7 @@ where
                 .named_keys()
                 .values()
                 .try_for_each(|key| self.validate_key(key)),
+            StoredValue::ContractWasm(_) => Ok(()),
         }
     }
 
@@ -650,6 +744,10 @@ where
     }
 
     fn add_unsafe(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        if self.read_only {
+            return Err(Error::WriteInReadOnlyContext);
+        }
+        self.touch(key);
         match self.state.borrow_mut().add(self.correlation_id, key, value) {
             Err(storage_error) => Err(storage_error.into()),
             Ok(AddResult::Success) => Ok(()),
@@ -659,6 +757,22 @@ where
         }
     }
 
+    /// Charges gas for storing `byte_size` bytes in global state, at the price set in the protocol
+    /// data.
+    fn charge_storage(&mut self, byte_size: usize) -> Result<(), Error> {
+        let cost = Gas::new(self.protocol_data.storage_costs().cost(byte_size).into());
+        self.charge_gas(cost)
+    }
+
+    fn write_unsafe(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        if self.read_only {
+            return Err(Error::WriteInReadOnlyContext);
+        }
+        self.touch(key);
+        self.state.borrow_mut().write(key, value);
+        Ok(())
+    }
+
     pub fn add_associated_key(
         &mut self,
         public_key: PublicKey,
@@ -694,7 +808,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -728,7 +842,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -766,7 +880,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -804,7 +918,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -815,14 +929,15 @@ where
         bytes: Vec<u8>,
         named_keys: BTreeMap<String, Key>,
     ) -> Result<(), Error> {
-        let protocol_version = self.protocol_version();
-        let contract = Contract::new(bytes, named_keys, protocol_version);
-        let contract = StoredValue::Contract(contract);
-
         self.validate_writeable(&key)?;
         self.validate_key(&key)?;
 
-        self.state.borrow_mut().write(key, contract);
+        let protocol_version = self.protocol_version();
+        let wasm_hash = self.store_contract_wasm(bytes)?;
+        let contract = Contract::new(wasm_hash, named_keys, protocol_version);
+        let contract = StoredValue::Contract(contract);
+
+        self.write_unsafe(key, contract)?;
         Ok(())
     }
 
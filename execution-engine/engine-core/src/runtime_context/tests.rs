@@ -5,6 +5,7 @@ use std::{
     rc::Rc,
 };
 
+use matches::assert_matches;
 use rand::RngCore;
 
 use engine_shared::{
@@ -16,10 +17,14 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::Transform,
 };
-use engine_storage::global_state::{
-    in_memory::{InMemoryGlobalState, InMemoryGlobalStateView},
-    CommitResult, StateProvider,
+use engine_storage::{
+    global_state::{
+        in_memory::{InMemoryGlobalState, InMemoryGlobalStateView},
+        CommitResult, StateProvider,
+    },
+    protocol_data::ProtocolData,
 };
+use engine_wasm_prep::storage_costs::StorageCosts;
 use types::{
     account::{
         ActionType, AddKeyFailure, PublicKey, PurseId, RemoveKeyFailure, SetThresholdFailure,
@@ -32,7 +37,7 @@ use super::{attenuate_uref_for_account, Address, Error, RuntimeContext};
 use crate::{
     engine_state::SYSTEM_ACCOUNT_ADDR,
     execution::{extract_access_rights_from_keys, AddressGenerator},
-    tracking_copy::TrackingCopy,
+    tracking_copy::{byte_size::ByteSize, TrackingCopy},
 };
 
 const DEPLOY_HASH: [u8; 32] = [1u8; 32];
@@ -206,7 +211,7 @@ fn store_contract_with_uref_valid() {
     let access_rights = extract_access_rights_from_keys(vec![uref]);
 
     let contract = StoredValue::Contract(Contract::new(
-        Vec::new(),
+        [0; 32],
         iter::once(("ValidURef".to_owned(), uref)).collect(),
         ProtocolVersion::V1_0_0,
     ));
@@ -231,7 +236,7 @@ fn store_contract_with_uref_forged() {
     let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
     let uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let contract = StoredValue::Contract(Contract::new(
-        Vec::new(),
+        [0; 32],
         iter::once(("ForgedURef".to_owned(), uref)).collect(),
         ProtocolVersion::V1_0_0,
     ));
@@ -243,6 +248,159 @@ fn store_contract_with_uref_forged() {
     assert_forged_reference(query_result);
 }
 
+#[test]
+fn store_contract_wasm_deduplicated() {
+    let wasm_bytes = vec![0u8, 97, 115, 109, 1, 0, 0, 0];
+
+    let query_result = test(HashMap::new(), |mut rc| {
+        let first_hash = rc.store_contract_wasm(wasm_bytes.clone())?;
+        let effect_after_first = rc.effect();
+        let second_hash = rc.store_contract_wasm(wasm_bytes.clone())?;
+        let effect_after_second = rc.effect();
+        let contract_wasm = rc.read_contract_wasm(first_hash)?;
+        Ok((
+            first_hash,
+            second_hash,
+            effect_after_first,
+            effect_after_second,
+            contract_wasm,
+        ))
+    });
+
+    let (first_hash, second_hash, effect_after_first, effect_after_second, contract_wasm) =
+        query_result.expect("storing contract wasm should succeed");
+
+    assert_eq!(first_hash, second_hash);
+    assert_eq!(contract_wasm.bytes(), wasm_bytes.as_slice());
+    // The second store should not add another write of the same code.
+    assert_eq!(
+        effect_after_first.transforms.get(&Key::Hash(first_hash)),
+        effect_after_second.transforms.get(&Key::Hash(first_hash))
+    );
+    assert_matches!(
+        effect_after_second.transforms.get(&Key::Hash(first_hash)),
+        Some(Transform::Write(StoredValue::ContractWasm(_)))
+    );
+}
+
+#[test]
+fn read_only_context_rejects_changes() {
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref]);
+    let value = StoredValue::CLValue(CLValue::from_t(43_i32).unwrap());
+
+    let query_result = test(access_rights, |mut rc| {
+        rc.write_gs(uref, value.clone())?;
+        rc.set_read_only(true);
+        let results = vec![
+            rc.read_gs(&uref).map(|_| ()),
+            rc.write_gs(uref, value.clone()),
+            rc.add_gs(uref, value),
+            rc.put_key("key".to_string(), uref),
+            rc.write_ls(&[1u8; 32], CLValue::from_t(1_i32).unwrap()),
+        ];
+        Ok((results, rc.named_keys_contains_key("key"), rc.effect()))
+    });
+
+    let (results, has_put_key, effect) = query_result.expect("writing before should succeed");
+    assert!(results[0].is_ok());
+    for result in &results[1..] {
+        assert_matches!(result, Err(Error::WriteInReadOnlyContext));
+    }
+    assert!(!has_put_key);
+    assert_eq!(effect.transforms.len(), 1);
+}
+
+#[test]
+fn should_record_touched_keys() {
+    let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref]);
+    let value = StoredValue::CLValue(CLValue::from_t(43_i32).unwrap());
+
+    let query_result = test(access_rights, |mut rc| {
+        rc.write_gs(uref, value.clone())?;
+        let untracked = rc.take_touched_keys();
+        rc.record_touched_keys();
+        rc.write_gs(uref, value)?;
+        rc.read_gs(&uref)?;
+        let first_taken = rc.take_touched_keys();
+        let second_taken = rc.take_touched_keys();
+        Ok((untracked, first_taken, second_taken))
+    });
+
+    let (untracked, first_taken, second_taken) =
+        query_result.expect("writing and reading should succeed");
+    assert!(untracked.is_empty());
+    assert_eq!(first_taken, vec![uref, uref]);
+    assert!(second_taken.is_empty());
+}
+
+#[test]
+fn should_charge_for_storage() {
+    let (base_key, account) = mock_account([0u8; 32]);
+    let mut named_keys = BTreeMap::new();
+    let mut address_generator = AddressGenerator::new(DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut address_generator, AccessRights::READ_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref]);
+
+    let storage_costs = StorageCosts::new(10);
+    let protocol_data = ProtocolData::new(
+        Default::default(),
+        Default::default(),
+        storage_costs,
+        Default::default(),
+        URef::new([0; 32], AccessRights::READ),
+        URef::new([0; 32], AccessRights::READ),
+    );
+    let value = StoredValue::CLValue(CLValue::from_t(vec![0u8; 100]).unwrap());
+    let write_cost = Gas::new(storage_costs.cost(value.byte_size()).into());
+    let named_key_cost = Gas::new(
+        storage_costs
+            .cost("key".to_string().byte_size() + uref.byte_size())
+            .into(),
+    );
+
+    let mut runtime_context = RuntimeContext::new(
+        Rc::new(RefCell::new(mock_tc(base_key, account.clone()))),
+        &mut named_keys,
+        access_rights,
+        Vec::new(),
+        BTreeSet::from_iter(vec![PublicKey::new([0; 32])]),
+        &account,
+        base_key,
+        BlockTime::new(0),
+        DEPLOY_HASH,
+        write_cost + named_key_cost,
+        Gas::default(),
+        0,
+        Rc::new(RefCell::new(address_generator)),
+        ProtocolVersion::V1_0_0,
+        CorrelationId::new(),
+        PHASE,
+        protocol_data,
+    );
+
+    runtime_context
+        .write_gs(uref, value.clone())
+        .expect("should write");
+    assert_eq!(runtime_context.gas_counter(), write_cost);
+
+    runtime_context
+        .put_key("key".to_string(), uref)
+        .expect("should put key");
+    assert_eq!(runtime_context.gas_counter(), write_cost + named_key_cost);
+
+    // Replacing a named key does not grow the named keys
+    runtime_context
+        .put_key("key".to_string(), uref)
+        .expect("should put key again");
+    assert_eq!(runtime_context.gas_counter(), write_cost + named_key_cost);
+
+    assert_matches!(runtime_context.write_gs(uref, value), Err(Error::GasLimit));
+}
+
 #[test]
 fn store_contract_under_uref_valid() {
     // Test that storing contract under URef that is known and has WRITE access
@@ -251,7 +409,7 @@ fn store_contract_under_uref_valid() {
     let contract_uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let access_rights = extract_access_rights_from_keys(vec![contract_uref]);
     let contract = StoredValue::Contract(Contract::new(
-        Vec::new(),
+        [0; 32],
         iter::once(("ValidURef".to_owned(), contract_uref)).collect(),
         ProtocolVersion::V1_0_0,
     ));
@@ -276,7 +434,7 @@ fn store_contract_under_uref_forged() {
     let mut rng = AddressGenerator::new(DEPLOY_HASH, PHASE);
     let contract_uref = create_uref(&mut rng, AccessRights::READ_WRITE);
     let contract = StoredValue::Contract(Contract::new(
-        Vec::new(),
+        [0; 32],
         BTreeMap::new(),
         ProtocolVersion::V1_0_0,
     ));
@@ -296,7 +454,7 @@ fn store_contract_uref_invalid_access() {
     let contract_uref = create_uref(&mut rng, AccessRights::READ);
     let access_rights = extract_access_rights_from_keys(vec![contract_uref]);
     let contract = StoredValue::Contract(Contract::new(
-        Vec::new(),
+        [0; 32],
         BTreeMap::new(),
         ProtocolVersion::V1_0_0,
     ));
@@ -432,7 +590,7 @@ fn contract_key_addable_valid() {
     let mut rng = rand::thread_rng();
     let contract_key = random_contract_key(&mut rng);
     let contract = StoredValue::Contract(Contract::new(
-        Vec::new(),
+        [0; 32],
         BTreeMap::new(),
         ProtocolVersion::V1_0_0,
     ));
@@ -472,7 +630,7 @@ fn contract_key_addable_valid() {
         .expect("Adding should work.");
 
     let updated_contract = StoredValue::Contract(Contract::new(
-        Vec::new(),
+        [0; 32],
         iter::once((uref_name, uref)).collect(),
         ProtocolVersion::V1_0_0,
     ));
@@ -494,7 +652,7 @@ fn contract_key_addable_invalid() {
     let contract_key = random_contract_key(&mut rng);
     let other_contract_key = random_contract_key(&mut rng);
     let contract = StoredValue::Contract(Contract::new(
-        Vec::new(),
+        [0; 32],
         BTreeMap::new(),
         ProtocolVersion::V1_0_0,
     ));
@@ -1,6 +1,8 @@
 use std::{collections::BTreeMap, mem};
 
-use engine_shared::{account::Account, contract::Contract, stored_value::StoredValue};
+use engine_shared::{
+    account::Account, contract::Contract, contract_wasm::ContractWasm, stored_value::StoredValue,
+};
 use types::Key;
 
 /// Returns byte size of the element - both heap size and stack size.
@@ -22,10 +24,13 @@ impl ByteSize for Account {
 
 impl ByteSize for Contract {
     fn byte_size(&self) -> usize {
-        mem::size_of::<Self>()
-            + self.heap_size()
-            + self.named_keys().byte_size()
-            + self.bytes().len()
+        mem::size_of::<Self>() + self.heap_size() + self.named_keys().byte_size()
+    }
+}
+
+impl ByteSize for ContractWasm {
+    fn byte_size(&self) -> usize {
+        mem::size_of::<Self>() + self.heap_size()
     }
 }
 
@@ -50,6 +55,7 @@ impl ByteSize for StoredValue {
                 StoredValue::CLValue(cl_value) => cl_value.serialized_len(),
                 StoredValue::Account(account) => account.heap_size(),
                 StoredValue::Contract(contract) => contract.heap_size(),
+                StoredValue::ContractWasm(contract_wasm) => contract_wasm.heap_size(),
             }
     }
 }
@@ -76,7 +82,13 @@ impl HeapSizeOf for Account {
 // TODO: contract has other fields (re protocol version) that are not repr here...on purpose?
 impl HeapSizeOf for Contract {
     fn heap_size(&self) -> usize {
-        self.named_keys().heap_size() + self.bytes().len()
+        self.named_keys().heap_size()
+    }
+}
+
+impl HeapSizeOf for ContractWasm {
+    fn heap_size(&self) -> usize {
+        self.bytes().len()
     }
 }
 
@@ -1,8 +1,13 @@
 use std::convert::TryInto;
 
 use engine_shared::{
-    account::Account, contract::Contract, motes::Motes, newtypes::CorrelationId,
-    stored_value::StoredValue, transform::TypeMismatch,
+    account::Account,
+    contract::Contract,
+    contract_wasm::{ContractWasm, ContractWasmHash},
+    motes::Motes,
+    newtypes::CorrelationId,
+    stored_value::StoredValue,
+    transform::TypeMismatch,
 };
 use engine_storage::global_state::StateReader;
 use types::{bytesrepr::ToBytes, CLValue, Key, URef, U512};
@@ -43,6 +48,13 @@ pub trait TrackingCopyExt<R> {
         correlation_id: CorrelationId,
         key: Key,
     ) -> Result<Contract, Self::Error>;
+
+    /// Gets the Wasm code stored under a given hash
+    fn get_contract_wasm(
+        &mut self,
+        correlation_id: CorrelationId,
+        wasm_hash: ContractWasmHash,
+    ) -> Result<ContractWasm, Self::Error>;
 }
 
 impl<R> TrackingCopyExt<R> for TrackingCopy<R>
@@ -131,4 +143,20 @@ where
             None => Err(execution::Error::KeyNotFound(key)),
         }
     }
+
+    fn get_contract_wasm(
+        &mut self,
+        correlation_id: CorrelationId,
+        wasm_hash: ContractWasmHash,
+    ) -> Result<ContractWasm, Self::Error> {
+        let key = Key::Hash(wasm_hash);
+        match self.get(correlation_id, &key).map_err(Into::into)? {
+            Some(StoredValue::ContractWasm(contract_wasm)) => Ok(contract_wasm),
+            Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "ContractWasm".to_string(),
+                other.type_name(),
+            ))),
+            None => Err(execution::Error::KeyNotFound(key)),
+        }
+    }
 }
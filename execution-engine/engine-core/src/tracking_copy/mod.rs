@@ -1,10 +1,14 @@
-mod byte_size;
+pub(crate) mod byte_size;
 mod ext;
 pub(self) mod meter;
 #[cfg(test)]
 mod tests;
 
-use std::{collections::HashMap, convert::From, iter};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::From,
+    iter,
+};
 
 use linked_hash_map::LinkedHashMap;
 
@@ -14,8 +18,11 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::{self, Transform, TypeMismatch},
 };
-use engine_storage::global_state::StateReader;
-use types::{bytesrepr, CLType, CLValueError, Key};
+use engine_storage::global_state::{StateIterator, StateReader};
+use types::{
+    bytesrepr::{self, ToBytes},
+    CLType, CLValueError, Key, TrieMerkleProof,
+};
 
 use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
 
@@ -85,11 +92,37 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
     }
 }
 
+/// A point in the changes made to a [`TrackingCopy`], returned by
+/// [`checkpoint`](TrackingCopy::checkpoint).
+#[must_use]
+pub struct Checkpoint {
+    level: usize,
+}
+
+/// The state of a key's cached mutation, op and transform before it was first changed after the
+/// innermost open checkpoint.
+struct JournalEntry {
+    key: Key,
+    cached: Option<StoredValue>,
+    op: Option<Op>,
+    transform: Option<Transform>,
+}
+
+/// An open checkpoint: where its entries start in the journal, which keys already have one, and
+/// which keys have been read since it was taken.
+struct CheckpointLevel {
+    journal_start: usize,
+    journaled_keys: HashSet<Key>,
+    read_keys: HashSet<Key>,
+}
+
 pub struct TrackingCopy<R> {
     reader: R,
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    journal: Vec<JournalEntry>,
+    checkpoints: Vec<CheckpointLevel>,
 }
 
 #[derive(Debug)]
@@ -122,6 +155,8 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                                                                  * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            journal: Vec::new(),
+            checkpoints: Vec::new(),
         }
     }
 
@@ -145,6 +180,104 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         TrackingCopy::new(self)
     }
 
+    /// Records the changes made so far, so that any made afterwards can be discarded by passing
+    /// the returned [`Checkpoint`] to [`rollback`](TrackingCopy::rollback).
+    ///
+    /// This has the same effect as making further changes in a [`fork`](TrackingCopy::fork) which
+    /// is then either merged back or dropped, but keeps the type of the `TrackingCopy`.  This
+    /// matters to the runtime, which is generic over the reader of the `TrackingCopy` it runs
+    /// against and can't run a callee against a fork without recursing on that type.
+    ///
+    /// Rather than copying the changes made so far, only the prior state of each key changed
+    /// after the checkpoint is journaled, so the cost is proportional to the changes being
+    /// tracked.  Checkpoints nest, and each must be passed either to `rollback` or to
+    /// [`release`](TrackingCopy::release), innermost first.
+    pub fn checkpoint(&mut self) -> Checkpoint {
+        self.checkpoints.push(CheckpointLevel {
+            journal_start: self.journal.len(),
+            journaled_keys: HashSet::new(),
+            read_keys: HashSet::new(),
+        });
+        Checkpoint {
+            level: self.checkpoints.len() - 1,
+        }
+    }
+
+    /// Discards every change made since `checkpoint` was taken, except that the keys read since
+    /// then are still recorded as read: whatever was done after the checkpoint may have depended
+    /// on them, so they still matter when finding conflicts between deploys.
+    pub fn rollback(&mut self, checkpoint: Checkpoint) {
+        let level = self.close(checkpoint);
+        for entry in self.journal.drain(level.journal_start..).rev() {
+            match entry.cached {
+                Some(value) => self.cache.muts_cached.insert(entry.key, value),
+                None => self.cache.muts_cached.remove(&entry.key),
+            };
+            match entry.op {
+                Some(op) => self.ops.insert(entry.key, op),
+                None => self.ops.remove(&entry.key),
+            };
+            match entry.transform {
+                Some(transform) => self.fns.insert(entry.key, transform),
+                None => self.fns.remove(&entry.key),
+            };
+        }
+        for key in &level.read_keys {
+            self.ops.insert_add(*key, Op::Read);
+        }
+        if let Some(outer) = self.checkpoints.last_mut() {
+            outer.read_keys.extend(level.read_keys);
+        }
+    }
+
+    /// Keeps every change made since `checkpoint` was taken, leaving them to be discarded only if
+    /// an enclosing checkpoint is rolled back.
+    pub fn release(&mut self, checkpoint: Checkpoint) {
+        let level = self.close(checkpoint);
+        match self.checkpoints.last_mut() {
+            Some(outer) => {
+                outer.journaled_keys.extend(level.journaled_keys);
+                outer.read_keys.extend(level.read_keys);
+            }
+            None => self.journal.clear(),
+        }
+    }
+
+    fn close(&mut self, checkpoint: Checkpoint) -> CheckpointLevel {
+        debug_assert_eq!(
+            checkpoint.level + 1,
+            self.checkpoints.len(),
+            "checkpoints must be closed innermost first"
+        );
+        self.checkpoints.pop().expect("checkpoint should be open")
+    }
+
+    /// Journals the current state of `key` if it is the first change to it since the innermost
+    /// open checkpoint.
+    fn journal(&mut self, key: Key) {
+        let level = match self.checkpoints.last_mut() {
+            Some(level) => level,
+            None => return,
+        };
+        if level.journaled_keys.insert(key) {
+            self.journal.push(JournalEntry {
+                key,
+                cached: self.cache.muts_cached.get(&key).cloned(),
+                op: self.ops.get(&key).cloned(),
+                transform: self.fns.get(&key).cloned(),
+            });
+        }
+    }
+
+    /// Journals `key` as for any other change, and records that it was read since the innermost
+    /// open checkpoint.
+    fn journal_read(&mut self, key: Key) {
+        self.journal(key);
+        if let Some(level) = self.checkpoints.last_mut() {
+            level.read_keys.insert(key);
+        }
+    }
+
     pub fn get(
         &mut self,
         correlation_id: CorrelationId,
@@ -168,6 +301,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     ) -> Result<Option<StoredValue>, R::Error> {
         let normalized_key = key.normalize();
         if let Some(value) = self.get(correlation_id, &normalized_key)? {
+            self.journal_read(normalized_key);
             self.ops.insert_add(normalized_key, Op::Read);
             self.fns.insert_add(normalized_key, Transform::Identity);
             Ok(Some(value))
@@ -178,6 +312,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
     pub fn write(&mut self, key: Key, value: StoredValue) {
         let normalized_key = key.normalize();
+        self.journal(normalized_key);
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
         self.fns.insert_add(normalized_key, Transform::Write(value));
@@ -248,6 +383,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
         match transform.clone().apply(current_value) {
             Ok(new_value) => {
+                self.journal(normalized_key);
                 self.cache.insert_write(normalized_key, new_value);
                 self.ops.insert_add(normalized_key, Op::Add);
                 self.fns.insert_add(normalized_key, transform);
@@ -264,12 +400,94 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         ExecutionEffect::new(self.ops.clone(), self.fns.clone())
     }
 
+    /// Applies `effect`, typically taken from a fork of this `TrackingCopy`, as if it had been
+    /// committed, and records its ops and transforms as part of this `TrackingCopy`'s own effect.
+    ///
+    /// Either all of the transforms are applied or, if any of them cannot be, none are.
+    pub fn apply_effect(
+        &mut self,
+        correlation_id: CorrelationId,
+        effect: ExecutionEffect,
+    ) -> Result<AddResult, R::Error> {
+        let mut new_values = Vec::new();
+        for (key, transform) in effect.transforms.iter() {
+            let new_value = match transform {
+                Transform::Identity => continue,
+                Transform::Write(value) => value.to_owned(),
+                transform => {
+                    let current_value = match self.get(correlation_id, key)? {
+                        None => return Ok(AddResult::KeyNotFound(*key)),
+                        Some(current_value) => current_value,
+                    };
+                    match transform.clone().apply(current_value) {
+                        Ok(new_value) => new_value,
+                        Err(transform::Error::TypeMismatch(type_mismatch)) => {
+                            return Ok(AddResult::TypeMismatch(type_mismatch))
+                        }
+                        Err(transform::Error::Serialization(error)) => {
+                            return Ok(AddResult::Serialization(error))
+                        }
+                    }
+                }
+            };
+            new_values.push((*key, new_value));
+        }
+
+        for key in effect.ops.keys().chain(effect.transforms.keys()) {
+            self.journal(*key);
+        }
+        for (key, op) in effect.ops.iter() {
+            if *op == Op::Read {
+                self.journal_read(*key);
+            }
+        }
+        for (key, value) in new_values {
+            self.cache.insert_write(key, value);
+        }
+        for (key, op) in effect.ops {
+            self.ops.insert_add(key, op);
+        }
+        for (key, transform) in effect.transforms {
+            self.fns.insert_add(key, transform);
+        }
+        Ok(AddResult::Success)
+    }
+
     pub fn query(
         &mut self,
         correlation_id: CorrelationId,
         base_key: Key,
         path: &[String],
     ) -> Result<TrackingCopyQueryResult, R::Error> {
+        self.query_inner(correlation_id, base_key, path, None)
+    }
+
+    /// Like [`TrackingCopy::query`], but also returns a merkle proof for every key read while
+    /// following `path` from `base_key`, in the order they were read.
+    ///
+    /// Proofs are taken from the underlying reader, so keys which the reader cannot prove (e.g.
+    /// keys with uncommitted mutations in a forked `TrackingCopy`) have no proof in the result.
+    pub fn query_with_proof(
+        &mut self,
+        correlation_id: CorrelationId,
+        base_key: Key,
+        path: &[String],
+    ) -> Result<(TrackingCopyQueryResult, Vec<TrieMerkleProof>), R::Error> {
+        let mut proofs = Vec::new();
+        let result = self.query_inner(correlation_id, base_key, path, Some(&mut proofs))?;
+        Ok((result, proofs))
+    }
+
+    fn query_inner(
+        &mut self,
+        correlation_id: CorrelationId,
+        base_key: Key,
+        path: &[String],
+        mut maybe_proofs: Option<&mut Vec<TrieMerkleProof>>,
+    ) -> Result<TrackingCopyQueryResult, R::Error> {
+        if let Some(proofs) = maybe_proofs.as_mut() {
+            self.collect_proof(correlation_id, &base_key, proofs)?;
+        }
         match self.read(correlation_id, &base_key)? {
             None => Ok(TrackingCopyQueryResult::ValueNotFound(self.error_path_msg(
                 base_key,
@@ -289,7 +507,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                         match current_value {
                             StoredValue::Account(account) => {
                                 if let Some(key) = account.named_keys().get(name) {
-                                    self.read_key_or_stop(correlation_id, *key, i)
+                                    self.read_key_or_stop(correlation_id, *key, i, maybe_proofs.as_mut())
                                 } else {
                                     Err(Ok((i, format!("Name {} not found in Account at path:", name))))
                                 }
@@ -297,7 +515,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
 
                             StoredValue::Contract(contract) => {
                                 if let Some(key) = contract.named_keys().get(name) {
-                                    self.read_key_or_stop(correlation_id, *key, i)
+                                    self.read_key_or_stop(correlation_id, *key, i, maybe_proofs.as_mut())
                                 } else {
                                     Err(Ok((i, format!("Name {} not found in Contract at path:", name))))
                                 }
@@ -326,7 +544,12 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         correlation_id: CorrelationId,
         key: Key,
         i: usize,
+        maybe_proofs: Option<&mut &mut Vec<TrieMerkleProof>>,
     ) -> Result<StoredValue, Result<(usize, String), R::Error>> {
+        if let Some(proofs) = maybe_proofs {
+            self.collect_proof(correlation_id, &key, proofs)
+                .map_err(Err)?;
+        }
         match self.read(correlation_id, &key) {
             // continue recursion
             Ok(Some(value)) => Ok(value),
@@ -337,6 +560,21 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    fn collect_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+        proofs: &mut Vec<TrieMerkleProof>,
+    ) -> Result<(), R::Error> {
+        if let Some(proof) = self
+            .reader
+            .read_with_proof(correlation_id, &key.normalize())?
+        {
+            proofs.push(proof);
+        }
+        Ok(())
+    }
+
     fn error_path_msg(
         &self,
         key: Key,
@@ -376,4 +614,44 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
             Ok(None)
         }
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof>, Self::Error> {
+        // A value written through this TrackingCopy is not committed to the underlying trie, so
+        // it cannot be proven.
+        if self.cache.muts_cached.contains_key(key) {
+            return Ok(None);
+        }
+        self.reader.read_with_proof(correlation_id, key)
+    }
+
+    /// Values written through this TrackingCopy are yielded after those of the underlying reader,
+    /// in no particular order.
+    fn iter_prefix<'a>(
+        &'a self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<StateIterator<'a, Key, StoredValue, Self::Error>, Self::Error> {
+        let muts_cached = &self.cache.muts_cached;
+        let committed = self
+            .reader
+            .iter_prefix(correlation_id, prefix)?
+            .filter(move |result| match result {
+                Ok((key, _)) => !muts_cached.contains_key(key),
+                Err(_) => true,
+            });
+        let prefix = prefix.to_vec();
+        let cached = muts_cached
+            .iter()
+            .filter(move |(key, _)| {
+                key.to_bytes()
+                    .map(|bytes| bytes.starts_with(&prefix))
+                    .unwrap_or(false)
+            })
+            .map(|(key, value)| Ok((*key, value.to_owned())));
+        Ok(Box::new(committed.chain(cached)))
+    }
 }
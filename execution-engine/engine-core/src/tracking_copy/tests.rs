@@ -1,26 +1,29 @@
 use std::{cell::Cell, collections::BTreeMap, iter, rc::Rc};
 
 use matches::assert_matches;
-use proptest::{collection::vec, prelude::*};
+use proptest::prelude::*;
 
 use engine_shared::{
     account::{Account, AssociatedKeys},
+    additive_map::AdditiveMap,
     contract::Contract,
     newtypes::CorrelationId,
     stored_value::{gens::stored_value_arb, StoredValue},
     transform::Transform,
 };
-use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader};
+use engine_storage::global_state::{
+    in_memory::InMemoryGlobalState, StateIterator, StateProvider, StateReader,
+};
 use types::{
     account::{PublicKey, PurseId, Weight, PUBLIC_KEY_LENGTH},
     gens::*,
-    AccessRights, CLValue, Key, ProtocolVersion, URef,
+    AccessRights, CLValue, Key, ProtocolVersion, TrieMerkleProof, URef,
 };
 
 use super::{
     meter::count_meter::Count, AddResult, TrackingCopy, TrackingCopyCache, TrackingCopyQueryResult,
 };
-use crate::engine_state::op::Op;
+use crate::engine_state::{conflicts::DeployConflicts, execution_effect::ExecutionEffect, op::Op};
 
 struct CountingDb {
     count: Rc<Cell<i32>>,
@@ -58,6 +61,22 @@ impl StateReader<Key, StoredValue> for CountingDb {
         self.count.set(count + 1);
         Ok(Some(value))
     }
+
+    fn read_with_proof(
+        &self,
+        _correlation_id: CorrelationId,
+        _key: &Key,
+    ) -> Result<Option<TrieMerkleProof>, Self::Error> {
+        Ok(None)
+    }
+
+    fn iter_prefix<'a>(
+        &'a self,
+        _correlation_id: CorrelationId,
+        _prefix: &[u8],
+    ) -> Result<StateIterator<'a, Key, StoredValue, Self::Error>, Self::Error> {
+        Ok(Box::new(iter::empty()))
+    }
 }
 
 #[test]
@@ -285,6 +304,177 @@ fn tracking_copy_aw() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_apply_effect() {
+    let correlation_id = CorrelationId::new();
+    let ten = StoredValue::CLValue(CLValue::from_t(10_i32).unwrap());
+    let db = CountingDb::new_init(ten);
+    let mut tc = TrackingCopy::new(db);
+    let add_key = Key::Hash([0u8; 32]);
+    let write_key = Key::Hash([1u8; 32]);
+    let three = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+    let seven = StoredValue::CLValue(CLValue::from_t(7_i32).unwrap());
+
+    let effect = {
+        let mut fork = tc.fork();
+        let _ = fork.add(correlation_id, add_key, three);
+        fork.write(write_key, seven.clone());
+        fork.effect()
+    };
+
+    // applying the fork's effect should make its changes visible in the original
+    let result = tc.apply_effect(correlation_id, effect.clone());
+    assert_matches!(result, Ok(AddResult::Success));
+    assert_eq!(tc.effect(), effect);
+    let thirteen = StoredValue::CLValue(CLValue::from_t(13_i32).unwrap());
+    assert_eq!(tc.get(correlation_id, &add_key).unwrap(), Some(thirteen));
+    assert_eq!(tc.get(correlation_id, &write_key).unwrap(), Some(seven));
+}
+
+#[test]
+fn tracking_copy_apply_effect_is_atomic() {
+    let correlation_id = CorrelationId::new();
+    let ten = StoredValue::CLValue(CLValue::from_t(10_i32).unwrap());
+    let db = CountingDb::new_init(ten.clone());
+    let mut tc = TrackingCopy::new(db);
+    let write_key = Key::Hash([0u8; 32]);
+    let bad_key = Key::Hash([1u8; 32]);
+
+    let mut ops = AdditiveMap::new();
+    ops.insert(write_key, Op::Write);
+    ops.insert(bad_key, Op::Add);
+    let mut transforms = AdditiveMap::new();
+    let seven = StoredValue::CLValue(CLValue::from_t(7_i32).unwrap());
+    transforms.insert(write_key, Transform::Write(seven));
+    // named keys cannot be added to a `CLValue`
+    transforms.insert(bad_key, Transform::AddKeys(BTreeMap::new()));
+
+    let result = tc.apply_effect(correlation_id, ExecutionEffect::new(ops, transforms));
+    assert_matches!(result, Ok(AddResult::TypeMismatch(_)));
+    assert_eq!(tc.effect(), ExecutionEffect::default());
+    assert_eq!(tc.get(correlation_id, &write_key).unwrap(), Some(ten));
+}
+
+#[test]
+fn tracking_copy_query_with_proof_of_missing_key() {
+    let correlation_id = CorrelationId::new();
+    let present_key = Key::Hash([1u8; 32]);
+    let missing_key = Key::Hash([2u8; 32]);
+    let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let (gs, root_hash) =
+        InMemoryGlobalState::from_pairs(correlation_id, &[(present_key, value)]).unwrap();
+    let view = gs.checkout(root_hash).unwrap().unwrap();
+    let mut tc = TrackingCopy::new(view);
+
+    let (result, proofs) = tc
+        .query_with_proof(correlation_id, missing_key, &[])
+        .unwrap();
+    assert_matches!(result, TrackingCopyQueryResult::ValueNotFound(_));
+
+    // the proof should show that the key is absent under the root
+    let mut state_root = [0u8; 32];
+    state_root.copy_from_slice(&root_hash.to_vec());
+    assert_eq!(proofs.len(), 1);
+    assert_eq!(proofs[0].verify(&state_root, &missing_key), Ok(None));
+}
+
+#[test]
+fn tracking_copy_rollback() {
+    let correlation_id = CorrelationId::new();
+    let ten = StoredValue::CLValue(CLValue::from_t(10_i32).unwrap());
+    let db = CountingDb::new_init(ten.clone());
+    let mut tc = TrackingCopy::new(db);
+    let kept_key = Key::Hash([0u8; 32]);
+    let discarded_key = Key::Hash([1u8; 32]);
+    let seven = StoredValue::CLValue(CLValue::from_t(7_i32).unwrap());
+    let three = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+
+    tc.write(kept_key, seven.clone());
+    let effect = tc.effect();
+    let checkpoint = tc.checkpoint();
+    tc.write(kept_key, three.clone());
+    tc.write(discarded_key, three);
+
+    // changes made after the checkpoint should be discarded, and those made before kept
+    tc.rollback(checkpoint);
+    assert_eq!(tc.effect(), effect);
+    assert_eq!(tc.get(correlation_id, &kept_key).unwrap(), Some(seven));
+    assert_eq!(tc.get(correlation_id, &discarded_key).unwrap(), Some(ten));
+}
+
+#[test]
+fn tracking_copy_rollback_keeps_reads() {
+    let correlation_id = CorrelationId::new();
+    let ten = StoredValue::CLValue(CLValue::from_t(10_i32).unwrap());
+    let read_key = Key::Hash([0u8; 32]);
+    let written_key = Key::Hash([1u8; 32]);
+    let three = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+
+    // a deploy whose try-call reads one key, writes another, then reverts
+    let mut reverted = TrackingCopy::new(CountingDb::new_init(ten.clone()));
+    let outer = reverted.checkpoint();
+    let inner = reverted.checkpoint();
+    reverted.read(correlation_id, &read_key).unwrap();
+    reverted.write(written_key, three.clone());
+    reverted.rollback(inner);
+    let inner_effect = reverted.effect();
+    assert_eq!(inner_effect.ops.len(), 1);
+    assert_eq!(inner_effect.ops.get(&read_key), Some(&Op::Read));
+    assert!(inner_effect.transforms.is_empty());
+    reverted.rollback(outer);
+    assert_eq!(reverted.effect(), inner_effect);
+
+    // the read should still conflict with a deploy writing the same key
+    let mut writer = TrackingCopy::new(CountingDb::new_init(ten));
+    writer.write(read_key, three);
+    let conflicts = DeployConflicts::from_effects(&[reverted.effect(), writer.effect()]);
+    assert!(!conflicts.commute(0, 1));
+}
+
+#[test]
+fn tracking_copy_nested_checkpoints() {
+    let correlation_id = CorrelationId::new();
+    let ten = StoredValue::CLValue(CLValue::from_t(10_i32).unwrap());
+    let db = CountingDb::new_init(ten.clone());
+    let mut tc = TrackingCopy::new(db);
+    let outer_key = Key::Hash([0u8; 32]);
+    let inner_key = Key::Hash([1u8; 32]);
+    let seven = StoredValue::CLValue(CLValue::from_t(7_i32).unwrap());
+    let three = StoredValue::CLValue(CLValue::from_t(3_i32).unwrap());
+
+    let effect = tc.effect();
+    let outer = tc.checkpoint();
+    tc.write(outer_key, seven.clone());
+
+    // changes kept by releasing an inner checkpoint should be kept along with the outer ones
+    let inner = tc.checkpoint();
+    tc.write(outer_key, three.clone());
+    tc.write(inner_key, three.clone());
+    tc.release(inner);
+    let released_effect = tc.effect();
+
+    // changes discarded by rolling back an inner checkpoint should leave the outer ones
+    let inner = tc.checkpoint();
+    tc.write(outer_key, seven.clone());
+    tc.write(inner_key, seven);
+    tc.rollback(inner);
+    assert_eq!(tc.effect(), released_effect);
+    assert_eq!(
+        tc.get(correlation_id, &outer_key).unwrap(),
+        Some(three.clone())
+    );
+    assert_eq!(tc.get(correlation_id, &inner_key).unwrap(), Some(three));
+
+    // rolling back the outer checkpoint should discard the released changes too
+    tc.rollback(outer);
+    assert_eq!(tc.effect(), effect);
+    assert_eq!(
+        tc.get(correlation_id, &outer_key).unwrap(),
+        Some(ten.clone())
+    );
+    assert_eq!(tc.get(correlation_id, &inner_key).unwrap(), Some(ten));
+}
+
 proptest! {
     #[test]
     fn query_empty_path(k in key_arb(), missing_key in key_arb(), v in stored_value_arb()) {
@@ -311,14 +501,14 @@ proptest! {
         v in stored_value_arb(), // value in contract state
         name in "\\PC*", // human-readable name for state
         missing_name in "\\PC*",
-        body in vec(any::<u8>(), 1..1000), // contract body
+        wasm_hash in u8_slice_32(), // hash of contract code
         hash in u8_slice_32(), // hash for contract key
     ) {
         let correlation_id = CorrelationId::new();
         let mut named_keys = BTreeMap::new();
         named_keys.insert(name.clone(), k);
         let contract =
-            StoredValue::Contract(Contract::new(body, named_keys, ProtocolVersion::V1_0_0));
+            StoredValue::Contract(Contract::new(wasm_hash, named_keys, ProtocolVersion::V1_0_0));
         let contract_key = Key::Hash(hash);
 
         let (gs, root_hash) = InMemoryGlobalState::from_pairs(
@@ -390,7 +580,7 @@ proptest! {
         contract_name in "\\PC*", // human-readable name for contract
         pk in u8_slice_32(), // account public key
         address in u8_slice_32(), // address for account key
-        body in vec(any::<u8>(), 1..1000), //contract body
+        wasm_hash in u8_slice_32(), // hash of contract code
         hash in u8_slice_32(), // hash for contract key
     ) {
         let correlation_id = CorrelationId::new();
@@ -398,7 +588,7 @@ proptest! {
         let mut contract_named_keys = BTreeMap::new();
         contract_named_keys.insert(state_name.clone(), k);
         let contract = StoredValue::Contract(
-            Contract::new(body, contract_named_keys, ProtocolVersion::V1_0_0)
+            Contract::new(wasm_hash, contract_named_keys, ProtocolVersion::V1_0_0)
         );
         let contract_key = Key::Hash(hash);
 
@@ -0,0 +1,171 @@
+use std::{convert::TryFrom, fs, path::PathBuf, process, str::FromStr};
+
+use clap::{App, Arg, ArgMatches};
+use dirs::home_dir;
+
+use engine_shared::{newtypes::Blake2bHash, os::get_page_size};
+use engine_storage::{
+    fsck, protocol_data_store::lmdb::LmdbProtocolDataStore,
+    transaction_source::lmdb::LmdbEnvironment, trie_store::lmdb::LmdbTrieStore,
+};
+
+// exe
+const APP_NAME: &str = "CasperLabs Execution Engine Global State Checker";
+const APP_ABOUT: &str =
+    "Checks the integrity of the global state under the given state roots without modifying it";
+
+// data-dir / lmdb
+const ARG_DATA_DIR: &str = "data-dir";
+const ARG_DATA_DIR_SHORT: &str = "d";
+const ARG_DATA_DIR_VALUE: &str = "DIR";
+const ARG_DATA_DIR_HELP: &str = "Sets the data directory";
+const DEFAULT_DATA_DIR_RELATIVE: &str = ".casperlabs";
+const GLOBAL_STATE_DIR: &str = "global_state";
+const GET_HOME_DIR_EXPECT: &str = "Could not get home directory";
+const DATA_DIR_NOT_FOUND: &str = "Global state directory not found";
+const LMDB_ENVIRONMENT_EXPECT: &str = "Could not open LmdbEnvironment";
+const LMDB_TRIE_STORE_EXPECT: &str = "Could not open LmdbTrieStore";
+const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not open LmdbProtocolDataStore";
+const FSCK_EXPECT: &str = "Could not read global state";
+
+// pages / lmdb
+const ARG_PAGES: &str = "pages";
+const ARG_PAGES_SHORT: &str = "p";
+const ARG_PAGES_VALUE: &str = "NUM";
+const ARG_PAGES_HELP: &str = "Sets the max number of pages to use for lmdb's mmap";
+const GET_PAGES_EXPECT: &str = "Could not parse pages argument";
+// 750 GiB = 805306368000 bytes
+// page size on x86_64 linux = 4096 bytes
+// 805306368000 / 4096 = 196608000
+const DEFAULT_PAGES: usize = 196_608_000;
+
+// state roots
+const ARG_STATE_ROOTS: &str = "state-root";
+const ARG_STATE_ROOTS_VALUE: &str = "HASH";
+const ARG_STATE_ROOTS_HELP: &str = "Hex-encoded state root hashes to check";
+const ARG_STATE_ROOTS_EXPECT: &str = "state root required";
+
+fn main() {
+    let matches = get_args();
+
+    let data_dir = get_data_dir(&matches);
+
+    let map_size = get_map_size(&matches);
+
+    let roots = get_state_roots(&matches);
+
+    let environment =
+        LmdbEnvironment::open_read_only(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
+
+    let trie_store = LmdbTrieStore::open(&environment, None).expect(LMDB_TRIE_STORE_EXPECT);
+
+    let protocol_data_store =
+        LmdbProtocolDataStore::open(&environment, None).expect(LMDB_PROTOCOL_DATA_STORE_EXPECT);
+
+    let report =
+        fsck::check(&environment, &trie_store, &protocol_data_store, &roots).expect(FSCK_EXPECT);
+
+    for problem in &report.problems {
+        println!("{}", problem);
+    }
+
+    println!(
+        "Checked {} trie nodes and {} protocol data entries, found {} problems",
+        report.tries_checked,
+        report.protocol_data_checked,
+        report.problems.len()
+    );
+
+    if !report.is_ok() {
+        process::exit(1);
+    }
+}
+
+/// Gets command line arguments
+fn get_args() -> ArgMatches<'static> {
+    App::new(APP_NAME)
+        .version(env!("CARGO_PKG_VERSION"))
+        .about(APP_ABOUT)
+        .arg(
+            Arg::with_name(ARG_DATA_DIR)
+                .short(ARG_DATA_DIR_SHORT)
+                .long(ARG_DATA_DIR)
+                .value_name(ARG_DATA_DIR_VALUE)
+                .help(ARG_DATA_DIR_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_PAGES)
+                .short(ARG_PAGES_SHORT)
+                .long(ARG_PAGES)
+                .value_name(ARG_PAGES_VALUE)
+                .help(ARG_PAGES_HELP)
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name(ARG_STATE_ROOTS)
+                .required(true)
+                .multiple(true)
+                .value_name(ARG_STATE_ROOTS_VALUE)
+                .help(ARG_STATE_ROOTS_HELP)
+                .index(1),
+        )
+        .get_matches()
+}
+
+/// Gets value of data-dir argument.  Unlike the server, the directory is never created.
+fn get_data_dir(matches: &ArgMatches) -> PathBuf {
+    let mut buf = matches.value_of(ARG_DATA_DIR).map_or(
+        {
+            let mut dir = home_dir().expect(GET_HOME_DIR_EXPECT);
+            dir.push(DEFAULT_DATA_DIR_RELATIVE);
+            dir
+        },
+        PathBuf::from,
+    );
+    buf.push(GLOBAL_STATE_DIR);
+    if !fs::metadata(&buf)
+        .map(|metadata| metadata.is_dir())
+        .unwrap_or(false)
+    {
+        eprintln!("{}: {:?}", DATA_DIR_NOT_FOUND, buf);
+        process::exit(2);
+    }
+    buf
+}
+
+///  Parses pages argument and returns map size
+fn get_map_size(matches: &ArgMatches) -> usize {
+    let page_size = get_page_size().unwrap();
+    let pages = matches
+        .value_of(ARG_PAGES)
+        .map_or(Ok(DEFAULT_PAGES), usize::from_str)
+        .expect(GET_PAGES_EXPECT);
+    page_size * pages
+}
+
+/// Parses the state root arguments, exiting if any of them is not a valid hash
+fn get_state_roots(matches: &ArgMatches) -> Vec<Blake2bHash> {
+    matches
+        .values_of(ARG_STATE_ROOTS)
+        .expect(ARG_STATE_ROOTS_EXPECT)
+        .map(|value| {
+            parse_hash(value).unwrap_or_else(|| {
+                eprintln!("Invalid state root: {}", value);
+                process::exit(2);
+            })
+        })
+        .collect()
+}
+
+fn parse_hash(value: &str) -> Option<Blake2bHash> {
+    let value = value.trim_start_matches("0x");
+    if value.len() % 2 != 0 || !value.is_ascii() {
+        return None;
+    }
+    let bytes = (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&value[index..index + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    Blake2bHash::try_from(bytes.as_slice()).ok()
+}
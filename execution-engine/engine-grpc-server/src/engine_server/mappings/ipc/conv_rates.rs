@@ -0,0 +1,36 @@
+use engine_shared::conv_rates::ConvRates;
+
+use crate::engine_server::ipc::ChainSpec_ConvRates;
+
+impl From<ConvRates> for ChainSpec_ConvRates {
+    fn from(conv_rates: ConvRates) -> Self {
+        ChainSpec_ConvRates {
+            min_conv_rate: conv_rates.min_conv_rate(),
+            default_conv_rate: conv_rates.default_conv_rate(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ChainSpec_ConvRates> for ConvRates {
+    fn from(pb_conv_rates: ChainSpec_ConvRates) -> Self {
+        ConvRates::new(pb_conv_rates.min_conv_rate, pb_conv_rates.default_conv_rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::conv_rates::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(conv_rates in gens::conv_rates_arb()) {
+            test_utils::protobuf_round_trip::<ConvRates, ChainSpec_ConvRates>(conv_rates);
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use crate::engine_server::ipc;
+
+impl From<(usize, usize)> for ipc::DeployConflict {
+    fn from((first, second): (usize, usize)) -> Self {
+        let mut pb_deploy_conflict = ipc::DeployConflict::new();
+        pb_deploy_conflict.set_first(first as u32);
+        pb_deploy_conflict.set_second(second as u32);
+        pb_deploy_conflict
+    }
+}
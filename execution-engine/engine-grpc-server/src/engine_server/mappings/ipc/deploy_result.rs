@@ -1,95 +1,122 @@
 use engine_core::{
     engine_state::{
-        execution_effect::ExecutionEffect, execution_result::ExecutionResult,
-        Error as EngineStateError,
+        execution_effect::ExecutionEffect,
+        execution_result::{CostBreakdown, ExecutionResult},
+        Error as EngineStateError, ErrorKind, RenderedError,
     },
-    execution::Error as ExecutionError,
+    execution::{CallFrame, Error as ExecutionError, Event, HostCall, HostCallArg},
 };
 use engine_shared::gas::Gas;
 
-use crate::engine_server::ipc::{DeployError_OutOfGasError, DeployResult};
+use crate::engine_server::ipc::{
+    DeployError_OutOfGasError, DeployResult, DeployResult_CallFrame, DeployResult_CostBreakdown,
+    DeployResult_Event, DeployResult_HostCall, DeployResult_HostCallArg,
+};
 
 impl From<ExecutionResult> for DeployResult {
     fn from(execution_result: ExecutionResult) -> DeployResult {
-        match execution_result {
-            ExecutionResult::Success { effect, cost } => detail::execution_success(effect, cost),
+        let (mut pb_deploy_result, details) = match execution_result {
+            ExecutionResult::Success {
+                effect,
+                cost,
+                details,
+            } => (detail::execution_success(effect, cost), details),
             ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
-            } => (error, effect, cost).into(),
+                details,
+            } => ((error, effect, cost).into(), details),
+        };
+        if pb_deploy_result.has_execution_result() {
+            let pb_execution_result = pb_deploy_result.mut_execution_result();
+            pb_execution_result.set_cost_breakdown(details.cost_breakdown.into());
+            pb_execution_result.set_trace(details.trace.into_iter().map(Into::into).collect());
+            pb_execution_result.set_events(details.events.into_iter().map(Into::into).collect());
         }
+        pb_deploy_result
+    }
+}
+
+impl From<CostBreakdown> for DeployResult_CostBreakdown {
+    fn from(cost_breakdown: CostBreakdown) -> Self {
+        let mut pb_cost_breakdown = DeployResult_CostBreakdown::new();
+        pb_cost_breakdown.set_payment_gas(cost_breakdown.payment_gas().value().into());
+        pb_cost_breakdown.set_session_gas(cost_breakdown.session_gas().value().into());
+        pb_cost_breakdown.set_finalize_gas(cost_breakdown.finalize_gas().value().into());
+        pb_cost_breakdown.set_motes_charged(cost_breakdown.motes_charged().value().into());
+        pb_cost_breakdown.set_motes_refunded(cost_breakdown.motes_refunded().value().into());
+        pb_cost_breakdown
+    }
+}
+
+impl From<CallFrame> for DeployResult_CallFrame {
+    fn from(call_frame: CallFrame) -> Self {
+        let mut pb_call_frame = DeployResult_CallFrame::new();
+        pb_call_frame.set_base_key(call_frame.base_key().into());
+        pb_call_frame.set_host_calls(
+            call_frame
+                .host_calls()
+                .iter()
+                .cloned()
+                .map(Into::into)
+                .collect(),
+        );
+        pb_call_frame
+    }
+}
+
+impl From<HostCall> for DeployResult_HostCall {
+    fn from(host_call: HostCall) -> Self {
+        let mut pb_host_call = DeployResult_HostCall::new();
+        pb_host_call.set_function(format!("{:?}", host_call.function()));
+        pb_host_call.set_args(host_call.args().iter().cloned().map(Into::into).collect());
+        pb_host_call.set_keys(host_call.keys().iter().cloned().map(Into::into).collect());
+        pb_host_call.set_gas_used(host_call.gas_used().value().into());
+        pb_host_call.set_calls(host_call.calls().iter().cloned().map(Into::into).collect());
+        pb_host_call
+    }
+}
+
+impl From<HostCallArg> for DeployResult_HostCallArg {
+    fn from(host_call_arg: HostCallArg) -> Self {
+        let mut pb_host_call_arg = DeployResult_HostCallArg::new();
+        match host_call_arg {
+            HostCallArg::Key(key) => pb_host_call_arg.set_key(key.into()),
+            HostCallArg::Name(name) => pb_host_call_arg.set_name(name),
+            HostCallArg::Value(cl_value) => pb_host_call_arg.set_cl_value(cl_value.into()),
+        }
+        pb_host_call_arg
+    }
+}
+
+impl From<Event> for DeployResult_Event {
+    fn from(event: Event) -> Self {
+        let mut pb_event = DeployResult_Event::new();
+        pb_event.set_name(event.name().to_string());
+        pb_event.set_value(event.value().clone().into());
+        pb_event
     }
 }
 
 impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
     fn from((engine_state_error, effect, cost): (EngineStateError, ExecutionEffect, Gas)) -> Self {
-        match engine_state_error {
-            // TODO(mateusz.gorski): Fix error model for the storage errors.
-            // We don't have separate IPC messages for storage errors so for the time being they are
-            // all reported as "wasm errors".
-            error @ EngineStateError::InvalidHashLength { .. }
-            | error @ EngineStateError::InvalidPublicKeyLength { .. }
-            | error @ EngineStateError::InvalidProtocolVersion { .. }
-            | error @ EngineStateError::InvalidUpgradeConfig
-            | error @ EngineStateError::WasmPreprocessingError(_)
-            | error @ EngineStateError::WasmSerializationError(_)
-            | error @ EngineStateError::ExecError(ExecutionError::DeploymentAuthorizationFailure)
-            | error @ EngineStateError::AuthorizationError => {
-                detail::precondition_error(error.to_string())
-            }
-            EngineStateError::StorageError(storage_error) => {
-                detail::execution_error(storage_error, effect, cost)
-            }
-            EngineStateError::MissingSystemContractError(msg) => {
-                detail::execution_error(msg, effect, cost)
-            }
-            error @ EngineStateError::InsufficientPaymentError
-            | error @ EngineStateError::DeployError
-            | error @ EngineStateError::FinalizationError
-            | error @ EngineStateError::SerializationError(_)
-            | error @ EngineStateError::MintError(_) => {
-                detail::execution_error(error, effect, cost)
-            }
-            EngineStateError::ExecError(exec_error) => (exec_error, effect, cost).into(),
-        }
+        (RenderedError::from(&engine_state_error), effect, cost).into()
     }
 }
 
 impl From<(ExecutionError, ExecutionEffect, Gas)> for DeployResult {
     fn from((exec_error, effect, cost): (ExecutionError, ExecutionEffect, Gas)) -> Self {
-        match exec_error {
-            ExecutionError::GasLimit => detail::out_of_gas_error(effect, cost),
-            ExecutionError::KeyNotFound(key) => {
-                detail::execution_error(format!("Key {:?} not found.", key), effect, cost)
-            }
-            ExecutionError::Revert(status) => {
-                detail::execution_error(format!("Exit code: {}", status), effect, cost)
-            }
-            ExecutionError::Interpreter(error) => {
-                // If the error happens during contract execution it's mapped to HostError and
-                // wrapped in Interpreter error, so we may end up with
-                // InterpreterError(HostError(InterpreterError))).  In order to provide clear error
-                // messages we have to downcast and match on the inner error, otherwise we end up
-                // with `Host(Trap(Trap(TrapKind:InterpreterError)))`.
-                // TODO: This really should be happening in the `Executor::exec`.
-                let msg = match error
-                    .as_host_error()
-                    .and_then(|host_error| host_error.downcast_ref::<ExecutionError>())
-                {
-                    Some(&ExecutionError::Revert(status)) => format!("Exit code: {}", status),
-                    Some(&ExecutionError::KeyNotFound(key)) => format!("Key {:?} not found.", key),
-                    Some(&ExecutionError::InvalidContext) => {
-                        // TODO: https://casperlabs.atlassian.net/browse/EE-771
-                        "Invalid execution context.".to_string()
-                    }
-                    Some(other) => format!("{:?}", other),
-                    None => format!("{:?}", error),
-                };
-                detail::execution_error(msg, effect, cost)
-            }
-            // TODO(mateusz.gorski): Be more specific about execution errors
-            other => detail::execution_error(format!("{:?}", other), effect, cost),
+        (RenderedError::from(&exec_error), effect, cost).into()
+    }
+}
+
+impl From<(RenderedError, ExecutionEffect, Gas)> for DeployResult {
+    fn from((rendered_error, effect, cost): (RenderedError, ExecutionEffect, Gas)) -> Self {
+        match rendered_error.kind() {
+            ErrorKind::Precondition => detail::precondition_error(rendered_error.to_string()),
+            ErrorKind::OutOfGas => detail::out_of_gas_error(effect, cost),
+            ErrorKind::Execution => detail::execution_error(rendered_error, effect, cost),
         }
     }
 }
@@ -165,8 +192,12 @@ mod detail {
 mod tests {
     use std::convert::TryInto;
 
-    use engine_shared::{additive_map::AdditiveMap, transform::Transform};
-    use types::{bytesrepr::Error as BytesReprError, AccessRights, Key, URef, U512};
+    use engine_core::{
+        engine_state::execution_result::ExecutionDetails,
+        resolvers::v1_function_index::FunctionIndex,
+    };
+    use engine_shared::{additive_map::AdditiveMap, motes::Motes, transform::Transform};
+    use types::{bytesrepr::Error as BytesReprError, AccessRights, CLValue, Key, URef, U512};
 
     use super::*;
 
@@ -185,6 +216,7 @@ mod tests {
         let execution_result = ExecutionResult::Success {
             effect: execution_effect,
             cost,
+            details: Default::default(),
         };
         let mut ipc_deploy_result: DeployResult = execution_result.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -205,11 +237,148 @@ mod tests {
         assert_eq!(input_transforms, ipc_transforms);
     }
 
+    #[test]
+    fn deploy_result_to_ipc_cost_breakdown() {
+        let cost_breakdown = CostBreakdown::new(
+            Gas::new(U512::from(10)),
+            Gas::new(U512::from(20)),
+            Gas::new(U512::from(5)),
+            Motes::new(U512::from(300)),
+            Motes::new(U512::from(700)),
+        );
+        let execution_result = ExecutionResult::Success {
+            effect: Default::default(),
+            cost: Gas::new(U512::from(30)),
+            details: ExecutionDetails {
+                cost_breakdown,
+                ..Default::default()
+            },
+        };
+        let mut ipc_deploy_result: DeployResult = execution_result.into();
+        let mut ipc_cost_breakdown = ipc_deploy_result
+            .mut_execution_result()
+            .take_cost_breakdown();
+
+        let payment_gas: U512 = ipc_cost_breakdown.take_payment_gas().try_into().unwrap();
+        let session_gas: U512 = ipc_cost_breakdown.take_session_gas().try_into().unwrap();
+        let finalize_gas: U512 = ipc_cost_breakdown.take_finalize_gas().try_into().unwrap();
+        let motes_charged: U512 = ipc_cost_breakdown.take_motes_charged().try_into().unwrap();
+        let motes_refunded: U512 = ipc_cost_breakdown.take_motes_refunded().try_into().unwrap();
+        assert_eq!(payment_gas, U512::from(10));
+        assert_eq!(session_gas, U512::from(20));
+        assert_eq!(finalize_gas, U512::from(5));
+        assert_eq!(motes_charged, U512::from(300));
+        assert_eq!(motes_refunded, U512::from(700));
+    }
+
+    #[test]
+    fn deploy_result_to_ipc_trace() {
+        let account_key = Key::Account([1u8; 32]);
+        let contract_key = Key::Hash([2u8; 32]);
+        let mut callee_frame = CallFrame::new(contract_key);
+        callee_frame.push(HostCall::new(
+            FunctionIndex::RevertFuncIndex,
+            vec![],
+            vec![],
+            Gas::new(U512::from(20)),
+            vec![],
+        ));
+        let mut session_frame = CallFrame::new(account_key);
+        session_frame.push(HostCall::new(
+            FunctionIndex::CallContractFuncIndex,
+            vec![
+                HostCallArg::Key(contract_key),
+                HostCallArg::Name("entry".to_string()),
+                HostCallArg::Value(CLValue::from_t(7u32).unwrap()),
+            ],
+            vec![contract_key],
+            Gas::new(U512::from(30)),
+            vec![callee_frame],
+        ));
+        let execution_result = ExecutionResult::Failure {
+            error: EngineStateError::ExecError(ExecutionError::Revert(7)),
+            effect: Default::default(),
+            cost: Gas::new(U512::from(30)),
+            details: ExecutionDetails {
+                trace: vec![session_frame],
+                ..Default::default()
+            },
+        };
+
+        let mut ipc_deploy_result: DeployResult = execution_result.into();
+        let mut ipc_trace = ipc_deploy_result.mut_execution_result().take_trace();
+        assert_eq!(ipc_trace.len(), 1);
+        let mut ipc_session_frame = ipc_trace.remove(0);
+        let base_key: Key = ipc_session_frame.take_base_key().try_into().unwrap();
+        assert_eq!(base_key, account_key);
+
+        let mut ipc_host_calls = ipc_session_frame.take_host_calls();
+        assert_eq!(ipc_host_calls.len(), 1);
+        let mut ipc_call_contract = ipc_host_calls.remove(0);
+        assert_eq!(ipc_call_contract.get_function(), "CallContractFuncIndex");
+        let mut ipc_args = ipc_call_contract.take_args();
+        assert_eq!(ipc_args.len(), 3);
+        let arg_key: Key = ipc_args[0].take_key().try_into().unwrap();
+        assert_eq!(arg_key, contract_key);
+        assert_eq!(ipc_args[1].get_name(), "entry");
+        let arg_value: CLValue = ipc_args[2].take_cl_value().try_into().unwrap();
+        assert_eq!(arg_value, CLValue::from_t(7u32).unwrap());
+        let keys: Vec<Key> = ipc_call_contract
+            .take_keys()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(keys, vec![contract_key]);
+        let gas_used: U512 = ipc_call_contract.take_gas_used().try_into().unwrap();
+        assert_eq!(gas_used, U512::from(30));
+
+        let ipc_callee_frames = ipc_call_contract.get_calls();
+        assert_eq!(ipc_callee_frames.len(), 1);
+        let ipc_revert = &ipc_callee_frames[0].get_host_calls()[0];
+        assert_eq!(ipc_revert.get_function(), "RevertFuncIndex");
+        assert!(ipc_revert.get_args().is_empty());
+    }
+
+    #[test]
+    fn deploy_result_to_ipc_events() {
+        let events = vec![
+            Event::new("first".to_string(), CLValue::from_t(1u64).unwrap()),
+            Event::new(
+                "second".to_string(),
+                CLValue::from_t("value".to_string()).unwrap(),
+            ),
+        ];
+        let execution_result = ExecutionResult::Success {
+            effect: Default::default(),
+            cost: Gas::new(U512::from(10)),
+            details: ExecutionDetails {
+                events: events.clone(),
+                ..Default::default()
+            },
+        };
+
+        let mut ipc_deploy_result: DeployResult = execution_result.into();
+        let ipc_events = ipc_deploy_result.mut_execution_result().take_events();
+        let names: Vec<&str> = ipc_events.iter().map(|event| event.get_name()).collect();
+        assert_eq!(names, vec!["first", "second"]);
+        let values: Vec<CLValue> = ipc_events
+            .into_iter()
+            .map(|mut event| event.take_value().try_into())
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            values,
+            vec![events[0].value().clone(), events[1].value().clone()]
+        );
+    }
+
     fn test_cost<E: Into<EngineStateError>>(expected_cost: Gas, error: E) -> Gas {
         let execution_failure = ExecutionResult::Failure {
             error: error.into(),
             effect: Default::default(),
             cost: expected_cost,
+            details: Default::default(),
         };
         let mut ipc_deploy_result: DeployResult = execution_failure.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -253,6 +422,7 @@ mod tests {
             error: EngineStateError::ExecError(revert_error),
             effect: Default::default(),
             cost: Gas::new(amount),
+            details: Default::default(),
         };
         let mut ipc_result: DeployResult = exec_result.into();
         assert!(
@@ -1,6 +1,9 @@
 use engine_core::engine_state::executable_deploy_item::ExecutableDeployItem;
 
-use crate::engine_server::ipc::{DeployPayload, DeployPayload_oneof_payload};
+use crate::engine_server::{
+    ipc::{DeployPayload, DeployPayload_oneof_payload},
+    mappings::entry_point_or_default,
+};
 
 impl From<DeployPayload_oneof_payload> for ExecutableDeployItem {
     fn from(pb_deploy_payload: DeployPayload_oneof_payload) -> Self {
@@ -14,18 +17,21 @@ impl From<DeployPayload_oneof_payload> for ExecutableDeployItem {
             DeployPayload_oneof_payload::stored_contract_hash(pb_stored_contract_hash) => {
                 ExecutableDeployItem::StoredContractByHash {
                     hash: pb_stored_contract_hash.hash,
+                    entry_point: entry_point_or_default(pb_stored_contract_hash.entry_point),
                     args: pb_stored_contract_hash.args,
                 }
             }
             DeployPayload_oneof_payload::stored_contract_name(pb_stored_contract_name) => {
                 ExecutableDeployItem::StoredContractByName {
                     name: pb_stored_contract_name.stored_contract_name,
+                    entry_point: entry_point_or_default(pb_stored_contract_name.entry_point),
                     args: pb_stored_contract_name.args,
                 }
             }
             DeployPayload_oneof_payload::stored_contract_uref(pb_stored_contract_uref) => {
                 ExecutableDeployItem::StoredContractByURef {
                     uref: pb_stored_contract_uref.uref,
+                    entry_point: entry_point_or_default(pb_stored_contract_uref.entry_point),
                     args: pb_stored_contract_uref.args,
                 }
             }
@@ -42,22 +48,66 @@ impl From<ExecutableDeployItem> for DeployPayload {
                 code.set_code(module_bytes);
                 code.set_args(args);
             }
-            ExecutableDeployItem::StoredContractByHash { hash, args } => {
+            ExecutableDeployItem::StoredContractByHash {
+                hash,
+                entry_point,
+                args,
+            } => {
                 let inner = result.mut_stored_contract_hash();
                 inner.set_hash(hash);
+                inner.set_entry_point(entry_point);
                 inner.set_args(args);
             }
-            ExecutableDeployItem::StoredContractByName { name, args } => {
+            ExecutableDeployItem::StoredContractByName {
+                name,
+                entry_point,
+                args,
+            } => {
                 let inner = result.mut_stored_contract_name();
                 inner.set_stored_contract_name(name);
+                inner.set_entry_point(entry_point);
                 inner.set_args(args);
             }
-            ExecutableDeployItem::StoredContractByURef { uref, args } => {
+            ExecutableDeployItem::StoredContractByURef {
+                uref,
+                entry_point,
+                args,
+            } => {
                 let inner = result.mut_stored_contract_uref();
                 inner.set_uref(uref);
+                inner.set_entry_point(entry_point);
                 inner.set_args(args);
             }
         }
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use types::DEFAULT_ENTRY_POINT;
+
+    use super::*;
+
+    #[test]
+    fn should_default_empty_entry_point() {
+        let mut pb_deploy_payload = DeployPayload::new();
+        pb_deploy_payload
+            .mut_stored_contract_hash()
+            .set_hash(vec![1u8; 32]);
+        let deploy_item: ExecutableDeployItem = pb_deploy_payload.payload.unwrap().into();
+        assert_eq!(deploy_item.entry_point(), DEFAULT_ENTRY_POINT);
+    }
+
+    #[test]
+    fn round_trip_entry_point() {
+        let deploy_item = ExecutableDeployItem::StoredContractByName {
+            name: "contract".to_string(),
+            entry_point: "transfer".to_string(),
+            args: vec![1, 2, 3],
+        };
+        let pb_deploy_payload: DeployPayload = deploy_item.clone().into();
+        let parsed: ExecutableDeployItem = pb_deploy_payload.payload.unwrap().into();
+        assert!(parsed == deploy_item);
+    }
+}
@@ -0,0 +1,49 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::{
+    execute_and_commit_request::ExecuteAndCommitRequest, execute_request::ExecuteRequest,
+};
+
+use crate::engine_server::ipc;
+
+impl TryFrom<ipc::ExecuteAndCommitRequest> for ExecuteAndCommitRequest {
+    type Error = ipc::ExecuteAndCommitResponse;
+
+    fn try_from(mut request: ipc::ExecuteAndCommitRequest) -> Result<Self, Self::Error> {
+        let execute_request: ExecuteRequest = request
+            .take_execute_request()
+            .try_into()
+            // The execute request can only fail to parse on its parent state hash.
+            .map_err(|mut execute_response: ipc::ExecuteResponse| {
+                let mut response = ipc::ExecuteAndCommitResponse::new();
+                response.set_missing_parent(execute_response.take_missing_parent());
+                response
+            })?;
+
+        let committed_deploys = request
+            .get_committed_deploys()
+            .iter()
+            .map(|position| *position as usize)
+            .collect();
+
+        Ok(ExecuteAndCommitRequest {
+            execute_request,
+            committed_deploys,
+        })
+    }
+}
+
+impl From<ExecuteAndCommitRequest> for ipc::ExecuteAndCommitRequest {
+    fn from(request: ExecuteAndCommitRequest) -> Self {
+        let mut result = ipc::ExecuteAndCommitRequest::new();
+        result.set_execute_request(request.execute_request.into());
+        result.set_committed_deploys(
+            request
+                .committed_deploys
+                .into_iter()
+                .map(|position| position as u32)
+                .collect(),
+        );
+        result
+    }
+}
@@ -41,12 +41,11 @@ impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
 
         let protocol_version = request.take_protocol_version().into();
 
-        Ok(ExecuteRequest::new(
-            parent_state_hash,
-            block_time,
-            deploys,
-            protocol_version,
-        ))
+        let mut execute_request =
+            ExecuteRequest::new(parent_state_hash, block_time, deploys, protocol_version);
+        execute_request.chain_deploys = request.get_chain_deploys();
+        execute_request.trace = request.get_trace();
+        Ok(execute_request)
     }
 }
 
@@ -65,6 +64,8 @@ impl From<ExecuteRequest> for ipc::ExecuteRequest {
                 .collect(),
         );
         result.set_protocol_version(req.protocol_version.into());
+        result.set_chain_deploys(req.chain_deploys);
+        result.set_trace(req.trace);
         result
     }
 }
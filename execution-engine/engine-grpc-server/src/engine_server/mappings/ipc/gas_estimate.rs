@@ -0,0 +1,55 @@
+use engine_core::engine_state::gas_estimate::GasEstimate;
+
+use crate::engine_server::ipc::{self, DeployResult};
+
+impl From<GasEstimate> for ipc::GasEstimate {
+    fn from(gas_estimate: GasEstimate) -> Self {
+        let mut pb_gas_estimate = ipc::GasEstimate::new();
+        pb_gas_estimate.set_payment_cost(gas_estimate.payment_cost().value().into());
+        pb_gas_estimate.set_session_cost(gas_estimate.session_cost().value().into());
+        let suggested_payment = gas_estimate.suggested_payment().expect("motes overflow");
+        pb_gas_estimate.set_suggested_payment(suggested_payment.value().into());
+
+        let cost = gas_estimate.cost();
+        if let Some(error) = gas_estimate.take_error() {
+            let failure: DeployResult = (error, Default::default(), cost).into();
+            pb_gas_estimate.set_failure(failure);
+        }
+        pb_gas_estimate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use engine_core::engine_state::{Error as EngineStateError, CONV_RATE};
+    use engine_shared::gas::Gas;
+    use types::U512;
+
+    use super::*;
+
+    #[test]
+    fn gas_estimate_to_ipc() {
+        let gas_estimate =
+            GasEstimate::new(Gas::new(10.into()), Gas::new(32.into()), CONV_RATE, None);
+        let mut pb_gas_estimate: ipc::GasEstimate = gas_estimate.into();
+
+        let payment_cost: U512 = pb_gas_estimate.take_payment_cost().try_into().unwrap();
+        let session_cost: U512 = pb_gas_estimate.take_session_cost().try_into().unwrap();
+        let suggested_payment: U512 = pb_gas_estimate.take_suggested_payment().try_into().unwrap();
+        assert_eq!(payment_cost, U512::from(10));
+        assert_eq!(session_cost, U512::from(32));
+        assert_eq!(suggested_payment, U512::from(42 * CONV_RATE));
+        assert!(!pb_gas_estimate.has_failure());
+    }
+
+    #[test]
+    fn failed_gas_estimate_to_ipc() {
+        let gas_estimate = GasEstimate::precondition_failure(EngineStateError::AuthorizationError);
+        let pb_gas_estimate: ipc::GasEstimate = gas_estimate.into();
+
+        assert!(pb_gas_estimate.has_failure());
+        assert!(pb_gas_estimate.get_failure().has_precondition_failure());
+    }
+}
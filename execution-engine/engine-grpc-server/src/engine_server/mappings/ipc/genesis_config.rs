@@ -1,6 +1,7 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
+use engine_shared::conv_rates::ConvRates;
 
 use crate::engine_server::{
     ipc::{ChainSpec_GenesisAccount, ChainSpec_GenesisConfig},
@@ -30,6 +31,13 @@ impl From<GenesisConfig> for ChainSpec_GenesisConfig {
             .mut_costs()
             .set_wasm(genesis_config.wasm_costs().into());
         pb_genesis_config
+            .mut_costs()
+            .set_host(genesis_config.host_function_costs().into());
+        pb_genesis_config
+            .mut_costs()
+            .set_storage(genesis_config.storage_costs().into());
+        pb_genesis_config.set_conv_rates(genesis_config.conv_rates().into());
+        pb_genesis_config
     }
 }
 
@@ -45,7 +53,15 @@ impl TryFrom<ChainSpec_GenesisConfig> for GenesisConfig {
             .into_iter()
             .map(TryInto::try_into)
             .collect::<Result<Vec<GenesisAccount>, Self::Error>>()?;
-        let wasm_costs = pb_genesis_config.take_costs().take_wasm().into();
+        let mut costs = pb_genesis_config.take_costs();
+        let wasm_costs = costs.take_wasm().into();
+        let host_function_costs = costs.take_host().into();
+        let storage_costs = costs.take_storage().into();
+        let conv_rates = if !pb_genesis_config.has_conv_rates() {
+            ConvRates::default()
+        } else {
+            pb_genesis_config.take_conv_rates().into()
+        };
         let mint_initializer_bytes = pb_genesis_config.mint_installer;
         let proof_of_stake_initializer_bytes = pb_genesis_config.pos_installer;
         Ok(GenesisConfig::new(
@@ -56,6 +72,9 @@ impl TryFrom<ChainSpec_GenesisConfig> for GenesisConfig {
             proof_of_stake_initializer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            conv_rates,
         ))
     }
 }
@@ -0,0 +1,121 @@
+use engine_wasm_prep::host_function_costs::HostFunctionCosts;
+
+use crate::engine_server::ipc::ChainSpec_CostTable_HostFunctionCosts;
+
+impl From<HostFunctionCosts> for ChainSpec_CostTable_HostFunctionCosts {
+    fn from(host_function_costs: HostFunctionCosts) -> Self {
+        ChainSpec_CostTable_HostFunctionCosts {
+            read_value: host_function_costs.read_value,
+            read_value_local: host_function_costs.read_value_local,
+            write: host_function_costs.write,
+            write_local: host_function_costs.write_local,
+            add: host_function_costs.add,
+            add_local: host_function_costs.add_local,
+            new_uref: host_function_costs.new_uref,
+            load_named_keys: host_function_costs.load_named_keys,
+            ret: host_function_costs.ret,
+            get_key: host_function_costs.get_key,
+            has_key: host_function_costs.has_key,
+            put_key: host_function_costs.put_key,
+            remove_key: host_function_costs.remove_key,
+            call_contract: host_function_costs.call_contract,
+            get_arg_size: host_function_costs.get_arg_size,
+            get_arg: host_function_costs.get_arg,
+            get_caller: host_function_costs.get_caller,
+            get_blocktime: host_function_costs.get_blocktime,
+            store_function: host_function_costs.store_function,
+            store_function_at_hash: host_function_costs.store_function_at_hash,
+            is_valid_uref: host_function_costs.is_valid_uref,
+            revert: host_function_costs.revert,
+            add_associated_key: host_function_costs.add_associated_key,
+            remove_associated_key: host_function_costs.remove_associated_key,
+            update_associated_key: host_function_costs.update_associated_key,
+            set_action_threshold: host_function_costs.set_action_threshold,
+            create_purse: host_function_costs.create_purse,
+            transfer_to_account: host_function_costs.transfer_to_account,
+            transfer_from_purse_to_account: host_function_costs.transfer_from_purse_to_account,
+            transfer_from_purse_to_purse: host_function_costs.transfer_from_purse_to_purse,
+            get_balance: host_function_costs.get_balance,
+            get_phase: host_function_costs.get_phase,
+            upgrade_contract_at_uref: host_function_costs.upgrade_contract_at_uref,
+            get_system_contract: host_function_costs.get_system_contract,
+            get_main_purse: host_function_costs.get_main_purse,
+            read_host_buffer: host_function_costs.read_host_buffer,
+            emit_event: host_function_costs.emit_event,
+            store_contract: host_function_costs.store_contract,
+            store_contract_at_hash: host_function_costs.store_contract_at_hash,
+            try_call_contract: host_function_costs.try_call_contract,
+            read_value_byte: host_function_costs.read_value_byte,
+            event_byte: host_function_costs.event_byte,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<ChainSpec_CostTable_HostFunctionCosts> for HostFunctionCosts {
+    fn from(pb_host_function_costs: ChainSpec_CostTable_HostFunctionCosts) -> Self {
+        HostFunctionCosts {
+            read_value: pb_host_function_costs.read_value,
+            read_value_local: pb_host_function_costs.read_value_local,
+            write: pb_host_function_costs.write,
+            write_local: pb_host_function_costs.write_local,
+            add: pb_host_function_costs.add,
+            add_local: pb_host_function_costs.add_local,
+            new_uref: pb_host_function_costs.new_uref,
+            load_named_keys: pb_host_function_costs.load_named_keys,
+            ret: pb_host_function_costs.ret,
+            get_key: pb_host_function_costs.get_key,
+            has_key: pb_host_function_costs.has_key,
+            put_key: pb_host_function_costs.put_key,
+            remove_key: pb_host_function_costs.remove_key,
+            call_contract: pb_host_function_costs.call_contract,
+            get_arg_size: pb_host_function_costs.get_arg_size,
+            get_arg: pb_host_function_costs.get_arg,
+            get_caller: pb_host_function_costs.get_caller,
+            get_blocktime: pb_host_function_costs.get_blocktime,
+            store_function: pb_host_function_costs.store_function,
+            store_function_at_hash: pb_host_function_costs.store_function_at_hash,
+            is_valid_uref: pb_host_function_costs.is_valid_uref,
+            revert: pb_host_function_costs.revert,
+            add_associated_key: pb_host_function_costs.add_associated_key,
+            remove_associated_key: pb_host_function_costs.remove_associated_key,
+            update_associated_key: pb_host_function_costs.update_associated_key,
+            set_action_threshold: pb_host_function_costs.set_action_threshold,
+            create_purse: pb_host_function_costs.create_purse,
+            transfer_to_account: pb_host_function_costs.transfer_to_account,
+            transfer_from_purse_to_account: pb_host_function_costs.transfer_from_purse_to_account,
+            transfer_from_purse_to_purse: pb_host_function_costs.transfer_from_purse_to_purse,
+            get_balance: pb_host_function_costs.get_balance,
+            get_phase: pb_host_function_costs.get_phase,
+            upgrade_contract_at_uref: pb_host_function_costs.upgrade_contract_at_uref,
+            get_system_contract: pb_host_function_costs.get_system_contract,
+            get_main_purse: pb_host_function_costs.get_main_purse,
+            read_host_buffer: pb_host_function_costs.read_host_buffer,
+            emit_event: pb_host_function_costs.emit_event,
+            store_contract: pb_host_function_costs.store_contract,
+            store_contract_at_hash: pb_host_function_costs.store_contract_at_hash,
+            try_call_contract: pb_host_function_costs.try_call_contract,
+            read_value_byte: pb_host_function_costs.read_value_byte,
+            event_byte: pb_host_function_costs.event_byte,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_wasm_prep::host_function_costs::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(host_function_costs in gens::host_function_costs_arb()) {
+            test_utils::protobuf_round_trip::<HostFunctionCosts, ChainSpec_CostTable_HostFunctionCosts>(
+                host_function_costs
+            );
+        }
+    }
+}
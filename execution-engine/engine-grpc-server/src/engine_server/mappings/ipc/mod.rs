@@ -2,13 +2,20 @@
 //! defined in protobuf/io/casperlabs/ipc/ipc.proto
 
 mod bond;
+mod conv_rates;
+mod deploy_conflict;
 mod deploy_item;
 mod deploy_result;
 mod executable_deploy_item;
+mod execute_and_commit_request;
 mod execute_request;
 mod execution_effect;
+mod gas_estimate;
 mod genesis_account;
 mod genesis_config;
+mod host_function_costs;
 mod query_request;
+mod storage_costs;
 mod upgrade_request;
+mod view_request;
 mod wasm_costs;
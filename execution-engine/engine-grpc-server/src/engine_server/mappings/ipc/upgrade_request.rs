@@ -30,10 +30,31 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
                 (bytes, args)
             };
 
-        let wasm_costs = if !upgrade_point.has_new_costs() {
+        let (wasm_costs, host_function_costs, storage_costs) = if !upgrade_point.has_new_costs() {
+            (None, None, None)
+        } else {
+            let new_costs = upgrade_point.mut_new_costs();
+            let wasm_costs = if !new_costs.has_wasm() {
+                None
+            } else {
+                Some(new_costs.take_wasm().into())
+            };
+            let host_function_costs = if !new_costs.has_host() {
+                None
+            } else {
+                Some(new_costs.take_host().into())
+            };
+            let storage_costs = if !new_costs.has_storage() {
+                None
+            } else {
+                Some(new_costs.take_storage().into())
+            };
+            (wasm_costs, host_function_costs, storage_costs)
+        };
+        let conv_rates = if !upgrade_point.has_new_conv_rates() {
             None
         } else {
-            Some(upgrade_point.mut_new_costs().take_wasm().into())
+            Some(upgrade_point.take_new_conv_rates().into())
         };
         let activation_point = if !upgrade_point.has_activation_point() {
             None
@@ -48,6 +69,9 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_args,
             upgrade_installer_bytes,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            conv_rates,
             activation_point,
         ))
     }
@@ -0,0 +1,59 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::view::ViewRequest;
+use engine_shared::{gas::Gas, newtypes::BLAKE2B_DIGEST_LENGTH};
+use types::{BlockTime, U512};
+
+use crate::engine_server::{
+    ipc,
+    mappings::{self, MappingError},
+};
+
+impl TryFrom<ipc::ViewRequest> for ViewRequest {
+    type Error = MappingError;
+
+    fn try_from(mut view_request: ipc::ViewRequest) -> Result<Self, Self::Error> {
+        let state_hash = {
+            let state_hash = view_request.get_state_hash();
+            let length = state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSliceError)?
+        };
+
+        let contract_key = view_request
+            .take_contract_key()
+            .try_into()
+            .map_err(MappingError::ParsingError)?;
+
+        let caller = view_request
+            .get_caller()
+            .try_into()
+            .map_err(|_| MappingError::invalid_public_key_length(view_request.caller.len()))?;
+
+        let block_time = BlockTime::new(view_request.get_block_time());
+
+        let gas_limit = Gas::new(U512::from(view_request.get_gas_limit()));
+
+        let protocol_version = view_request.take_protocol_version().into();
+
+        let entry_point = mappings::entry_point_or_default(view_request.take_entry_point());
+
+        Ok(ViewRequest::new(
+            state_hash,
+            contract_key,
+            view_request.take_args(),
+            caller,
+            block_time,
+            gas_limit,
+            protocol_version,
+        )
+        .with_entry_point(entry_point))
+    }
+}
@@ -11,7 +11,7 @@ use std::{
 };
 
 use engine_core::{engine_state, DEPLOY_HASH_LENGTH};
-use types::account::PUBLIC_KEY_LENGTH;
+use types::{account::PUBLIC_KEY_LENGTH, DEFAULT_ENTRY_POINT};
 
 pub use transforms::TransformMap;
 
@@ -23,6 +23,15 @@ pub(crate) fn vec_to_array(input: Vec<u8>, input_name: &str) -> Result<[u8; 32],
         .map_err(|_| format!("{} must be 32 bytes.", input_name).into())
 }
 
+/// An empty entry point, as sent by clients which predate named entry points, means the default.
+pub(crate) fn entry_point_or_default(entry_point: String) -> String {
+    if entry_point.is_empty() {
+        DEFAULT_ENTRY_POINT.to_string()
+    } else {
+        entry_point
+    }
+}
+
 #[derive(Debug)]
 pub enum MappingError {
     InvalidStateHashLength { expected: usize, actual: usize },
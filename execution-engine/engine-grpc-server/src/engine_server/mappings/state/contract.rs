@@ -4,18 +4,22 @@ use engine_shared::contract::Contract;
 
 use super::NamedKeyMap;
 use crate::engine_server::{
-    mappings::ParsingError,
+    mappings::{self, ParsingError},
     state::{self, NamedKey},
 };
 
 impl From<Contract> for state::Contract {
     fn from(contract: Contract) -> Self {
-        let (bytes, named_keys, protocol_version) = contract.destructure();
+        let (wasm_hash, named_keys, protocol_version, entry_points, abi) = contract.destructure();
         let mut pb_contract = state::Contract::new();
         let named_keys: Vec<NamedKey> = NamedKeyMap::new(named_keys).into();
-        pb_contract.set_body(bytes);
+        pb_contract.set_wasm_hash(wasm_hash.to_vec());
         pb_contract.set_named_keys(named_keys.into());
         pb_contract.set_protocol_version(protocol_version.into());
+        pb_contract.set_entry_points(entry_points.into());
+        if let Some(abi) = abi {
+            pb_contract.set_abi(abi.into());
+        }
         pb_contract
     }
 }
@@ -26,8 +30,20 @@ impl TryFrom<state::Contract> for Contract {
     fn try_from(mut pb_contract: state::Contract) -> Result<Self, Self::Error> {
         let named_keys: NamedKeyMap = pb_contract.take_named_keys().into_vec().try_into()?;
         let protocol_version = pb_contract.take_protocol_version().into();
-        let contract = Contract::new(pb_contract.body, named_keys.into_inner(), protocol_version);
-        Ok(contract)
+        let entry_points = pb_contract.take_entry_points().into_vec();
+        let maybe_abi = if pb_contract.has_abi() {
+            Some(pb_contract.take_abi().try_into()?)
+        } else {
+            None
+        };
+        let wasm_hash =
+            mappings::vec_to_array(pb_contract.wasm_hash, "Protobuf Contract::WasmHash")?;
+        let contract = Contract::new(wasm_hash, named_keys.into_inner(), protocol_version)
+            .with_entry_points(entry_points);
+        match maybe_abi {
+            Some(abi) => Ok(contract.with_abi(abi)),
+            None => Ok(contract),
+        }
     }
 }
 
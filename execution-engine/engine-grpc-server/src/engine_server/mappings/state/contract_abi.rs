@@ -0,0 +1,99 @@
+use std::convert::{TryFrom, TryInto};
+
+use types::{ContractAbi, EntryPointAbi, Parameter};
+
+use crate::engine_server::{
+    mappings::ParsingError,
+    state::{self, ContractAbi_EntryPoint, ContractAbi_Parameter},
+};
+
+impl From<&Parameter> for ContractAbi_Parameter {
+    fn from(parameter: &Parameter) -> Self {
+        let mut pb_parameter = ContractAbi_Parameter::new();
+        pb_parameter.set_name(parameter.name().to_string());
+        pb_parameter.set_cl_type(parameter.cl_type().clone().into());
+        pb_parameter
+    }
+}
+
+impl TryFrom<ContractAbi_Parameter> for Parameter {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_parameter: ContractAbi_Parameter) -> Result<Self, Self::Error> {
+        let cl_type = pb_parameter.take_cl_type().try_into()?;
+        Ok(Parameter::new(pb_parameter.name, cl_type))
+    }
+}
+
+impl From<&EntryPointAbi> for ContractAbi_EntryPoint {
+    fn from(entry_point: &EntryPointAbi) -> Self {
+        let mut pb_entry_point = ContractAbi_EntryPoint::new();
+        pb_entry_point.set_name(entry_point.name().to_string());
+        let parameters: Vec<ContractAbi_Parameter> =
+            entry_point.parameters().iter().map(Into::into).collect();
+        pb_entry_point.set_parameters(parameters.into());
+        pb_entry_point.set_ret(entry_point.ret().clone().into());
+        pb_entry_point
+    }
+}
+
+impl TryFrom<ContractAbi_EntryPoint> for EntryPointAbi {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_entry_point: ContractAbi_EntryPoint) -> Result<Self, Self::Error> {
+        let parameters = pb_entry_point
+            .take_parameters()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<Parameter>, ParsingError>>()?;
+        let ret = pb_entry_point.take_ret().try_into()?;
+        Ok(EntryPointAbi::new(pb_entry_point.name, parameters, ret))
+    }
+}
+
+impl From<ContractAbi> for state::ContractAbi {
+    fn from(abi: ContractAbi) -> Self {
+        let mut pb_abi = state::ContractAbi::new();
+        let entry_points: Vec<ContractAbi_EntryPoint> =
+            abi.entry_points().iter().map(Into::into).collect();
+        pb_abi.set_entry_points(entry_points.into());
+        pb_abi
+    }
+}
+
+impl TryFrom<state::ContractAbi> for ContractAbi {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_abi: state::ContractAbi) -> Result<Self, Self::Error> {
+        let entry_points = pb_abi
+            .take_entry_points()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<EntryPointAbi>, ParsingError>>()?;
+        let abi = ContractAbi::new(entry_points);
+        if let Some(name) = abi.duplicate_entry_point() {
+            return Err(ParsingError(format!(
+                "Protobuf ContractAbi describes entry point {} more than once",
+                name
+            )));
+        }
+        Ok(abi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(abi in gens::contract_abi_arb()) {
+            test_utils::protobuf_round_trip::<ContractAbi, state::ContractAbi>(abi);
+        }
+    }
+}
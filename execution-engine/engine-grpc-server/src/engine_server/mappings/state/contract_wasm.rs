@@ -0,0 +1,34 @@
+use engine_shared::contract_wasm::ContractWasm;
+
+use crate::engine_server::state;
+
+impl From<ContractWasm> for state::ContractWasm {
+    fn from(contract_wasm: ContractWasm) -> Self {
+        let mut pb_contract_wasm = state::ContractWasm::new();
+        pb_contract_wasm.set_body(contract_wasm.take_bytes());
+        pb_contract_wasm
+    }
+}
+
+impl From<state::ContractWasm> for ContractWasm {
+    fn from(pb_contract_wasm: state::ContractWasm) -> Self {
+        ContractWasm::new(pb_contract_wasm.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::contract_wasm::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(contract_wasm in gens::contract_wasm_arb()) {
+            test_utils::protobuf_round_trip::<ContractWasm, state::ContractWasm>(contract_wasm);
+        }
+    }
+}
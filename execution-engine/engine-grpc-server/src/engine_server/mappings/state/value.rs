@@ -23,6 +23,10 @@ impl TryFrom<StoredValue> for Value {
                 ret.set_contract(contract.into());
                 return Ok(ret);
             }
+            StoredValue::ContractWasm(contract_wasm) => {
+                ret.set_contract_wasm(contract_wasm.into());
+                return Ok(ret);
+            }
         };
 
         let conversion_error = |cl_value: CLValue| {
@@ -135,6 +139,9 @@ impl TryFrom<Value> for StoredValue {
             Value_oneof_value::key(pb_key) => try_stored_value_from(Key::try_from(pb_key)?),
             Value_oneof_value::unit(_) => try_stored_value_from(()),
             Value_oneof_value::long_value(x) => try_stored_value_from(x),
+            Value_oneof_value::contract_wasm(pb_contract_wasm) => {
+                Ok(StoredValue::ContractWasm(pb_contract_wasm.into()))
+            }
         }?;
 
         Ok(stored_value)
@@ -145,7 +152,10 @@ impl TryFrom<Value> for StoredValue {
 mod tests {
     use proptest::proptest;
 
-    use engine_shared::{account::gens::account_arb, contract::gens::contract_arb};
+    use engine_shared::{
+        account::gens::account_arb, contract::gens::contract_arb,
+        contract_wasm::gens::contract_wasm_arb,
+    };
     use types::gens::cl_value_arb;
 
     use super::*;
@@ -209,9 +219,15 @@ mod tests {
 
     proptest! {
         #[test]
-        fn round_trip(account in account_arb(), contract in contract_arb(), cl_value in cl_value_arb()) {
+        fn round_trip(
+            account in account_arb(),
+            contract in contract_arb(),
+            contract_wasm in contract_wasm_arb(),
+            cl_value in cl_value_arb()
+        ) {
             do_round_trip(StoredValue::Account(account));
             do_round_trip(StoredValue::Contract(contract));
+            do_round_trip(StoredValue::ContractWasm(contract_wasm));
 
             if cl_value_is_convertible_to_value(&cl_value) {
                 do_round_trip(StoredValue::CLValue(cl_value));
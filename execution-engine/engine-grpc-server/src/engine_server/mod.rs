@@ -17,23 +17,28 @@ use std::{
 use grpc::{RequestOptions, ServerBuilder, SingleResponse};
 
 use engine_core::engine_state::{
+    conflicts::DeployConflicts,
+    execute_and_commit_request::ExecuteAndCommitRequest,
     execute_request::ExecuteRequest,
     genesis::{GenesisConfig, GenesisResult},
     query::{QueryRequest, QueryResult},
     upgrade::{UpgradeConfig, UpgradeResult},
+    view::{ViewRequest, ViewResult},
     EngineState, Error as EngineError,
 };
 use engine_shared::{
     logging::{self, log_duration, log_info, log_level::LogLevel},
     newtypes::{Blake2bHash, CorrelationId},
 };
-use engine_storage::global_state::{CommitResult, StateProvider};
-use types::ProtocolVersion;
+use engine_storage::global_state::{CommitResult, PutTrieResult, StateProvider};
+use types::{bytesrepr::ToBytes, ProtocolVersion};
 
 use self::{
     ipc::{
-        ChainSpec_GenesisConfig, CommitRequest, CommitResponse, ExecuteResponse, GenesisResponse,
-        QueryResponse, UpgradeRequest, UpgradeResponse,
+        ChainSpec_GenesisConfig, CommitRequest, CommitResponse, EstimateGasResponse,
+        ExecuteAndCommitResponse, ExecuteResponse, GenesisResponse, GetTriesRequest,
+        GetTriesResponse, MissingTrieKeysRequest, MissingTrieKeysResponse, PutTriesRequest,
+        PutTriesResponse, QueryResponse, TrieNode, UpgradeRequest, UpgradeResponse, ViewResponse,
     },
     ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
     mappings::{ParsingError, TransformMap},
@@ -44,12 +49,24 @@ const METRIC_DURATION_EXEC: &str = "exec_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_GET_TRIES: &str = "get_tries_duration";
+const METRIC_DURATION_MISSING_TRIE_KEYS: &str = "missing_trie_keys_duration";
+const METRIC_DURATION_PUT_TRIES: &str = "put_tries_duration";
+const METRIC_DURATION_EXECUTE_AND_COMMIT: &str = "execute_and_commit_duration";
+const METRIC_DURATION_ESTIMATE_GAS: &str = "estimate_gas_duration";
+const METRIC_DURATION_VIEW: &str = "view_duration";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_GET_TRIES: &str = "get_tries_response";
+const TAG_RESPONSE_MISSING_TRIE_KEYS: &str = "missing_trie_keys_response";
+const TAG_RESPONSE_PUT_TRIES: &str = "put_tries_response";
+const TAG_RESPONSE_EXECUTE_AND_COMMIT: &str = "execute_and_commit_response";
+const TAG_RESPONSE_ESTIMATE_GAS: &str = "estimate_gas_response";
+const TAG_RESPONSE_VIEW: &str = "view_response";
 
 const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 
@@ -60,7 +77,7 @@ const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 // (outer layer) leading to cleaner design.
 impl<S> ExecutionEngineService for EngineState<S>
 where
-    S: StateProvider,
+    S: StateProvider + Sync,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
@@ -92,15 +109,35 @@ where
         let result = self.run_query(correlation_id, request);
 
         let response = match result {
-            Ok(QueryResult::Success(value)) => {
+            Ok(QueryResult::Success {
+                value,
+                contract_wasm,
+                proofs,
+            }) => {
                 let mut result = ipc::QueryResponse::new();
-                match value.try_into() {
-                    Ok(pb_value) => {
-                        let log_message =
-                            format!("query successful; correlation_id: {}", correlation_id);
-                        log_info(&log_message);
-                        result.set_success(pb_value);
-                    }
+                match state::Value::try_from(value) {
+                    Ok(mut pb_value) => match proofs
+                        .into_iter()
+                        .map(ToBytes::into_bytes)
+                        .collect::<Result<Vec<_>, _>>()
+                    {
+                        Ok(pb_proofs) => {
+                            if let Some(contract_wasm) = contract_wasm {
+                                pb_value.mut_contract().set_body(contract_wasm.take_bytes());
+                            }
+                            let log_message =
+                                format!("query successful; correlation_id: {}", correlation_id);
+                            log_info(&log_message);
+                            result.set_success(pb_value);
+                            result.set_proofs(pb_proofs.into());
+                        }
+                        Err(error) => {
+                            let log_message =
+                                format!("Failed to serialize merkle proofs: {:?}", error);
+                            logging::log_error(&log_message);
+                            result.set_failure(log_message);
+                        }
+                    },
                     Err(ParsingError(error_msg)) => {
                         let log_message =
                             format!("Failed to convert StoredValue to Value: {}", error_msg);
@@ -110,11 +147,26 @@ where
                 }
                 result
             }
-            Ok(QueryResult::ValueNotFound(full_path)) => {
-                let log_message = format!("Value not found: {:?}", full_path);
-                logging::log_warning(&log_message);
+            Ok(QueryResult::ValueNotFound { full_path, proofs }) => {
                 let mut result = ipc::QueryResponse::new();
-                result.set_failure(log_message);
+                match proofs
+                    .into_iter()
+                    .map(ToBytes::into_bytes)
+                    .collect::<Result<Vec<_>, _>>()
+                {
+                    Ok(pb_proofs) => {
+                        let log_message = format!("Value not found: {:?}", full_path);
+                        logging::log_warning(&log_message);
+                        result.set_failure(log_message);
+                        result.set_proofs(pb_proofs.into());
+                    }
+                    Err(error) => {
+                        let log_message =
+                            format!("Failed to serialize merkle proofs: {:?}", error);
+                        logging::log_error(&log_message);
+                        result.set_failure(log_message);
+                    }
+                }
                 result
             }
             Ok(QueryResult::RootNotFound) => {
@@ -160,8 +212,19 @@ where
 
         let mut exec_response = ExecuteResponse::new();
 
-        let results = match self.run_execute(correlation_id, exec_request) {
-            Ok(results) => results,
+        let maybe_results = if exec_request.chain_deploys {
+            self.run_execute_chained(correlation_id, exec_request)
+                .map(|(results, chained_effect)| (results, Some(chained_effect)))
+        } else if self.config().execution_threads() > 1 {
+            self.run_execute_parallel(correlation_id, exec_request)
+                .map(|results| (results, None))
+        } else {
+            self.run_execute(correlation_id, exec_request)
+                .map(|results| (results, None))
+        };
+
+        let (results, maybe_chained_effect) = match maybe_results {
+            Ok(ret) => ret,
             Err(error) => {
                 logging::log_error("deploy results error: RootNotFound");
                 exec_response
@@ -177,6 +240,18 @@ where
             }
         };
 
+        if let Some(chained_effect) = maybe_chained_effect {
+            exec_response
+                .mut_success()
+                .set_chained_effect(chained_effect.into());
+        }
+
+        let conflicts = DeployConflicts::from_results(&results);
+        let protobuf_conflicts_iter = conflicts.pairs().map(Into::into);
+        exec_response
+            .mut_success()
+            .set_conflicts(FromIterator::from_iter(protobuf_conflicts_iter));
+
         let protobuf_results_iter = results.into_iter().map(Into::into);
         exec_response
             .mut_success()
@@ -435,6 +510,350 @@ where
 
         SingleResponse::completed(upgrade_response)
     }
+
+    fn get_tries(
+        &self,
+        _request_options: RequestOptions,
+        get_tries_request: GetTriesRequest,
+    ) -> SingleResponse<GetTriesResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut get_tries_response = GetTriesResponse::new();
+
+        match parse_trie_keys(get_tries_request.get_trie_keys()).and_then(|trie_keys| {
+            trie_keys
+                .into_iter()
+                .filter_map(|trie_key| match self.get_trie(correlation_id, &trie_key) {
+                    Ok(Some(trie_bytes)) => {
+                        let mut trie_node = TrieNode::new();
+                        trie_node.set_trie_key(trie_key.to_vec());
+                        trie_node.set_trie_bytes(trie_bytes);
+                        Some(Ok(trie_node))
+                    }
+                    Ok(None) => None,
+                    Err(error) => Some(Err(format!("{:?}", error))),
+                })
+                .collect::<Result<Vec<TrieNode>, String>>()
+        }) {
+            Ok(tries) => get_tries_response.mut_success().set_tries(tries.into()),
+            Err(error_message) => {
+                logging::log_error(&error_message);
+                get_tries_response.set_failure(error_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_TRIES,
+            TAG_RESPONSE_GET_TRIES,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(get_tries_response)
+    }
+
+    fn missing_trie_keys(
+        &self,
+        _request_options: RequestOptions,
+        missing_trie_keys_request: MissingTrieKeysRequest,
+    ) -> SingleResponse<MissingTrieKeysResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut missing_trie_keys_response = MissingTrieKeysResponse::new();
+
+        match parse_trie_keys(missing_trie_keys_request.get_trie_keys()).and_then(|trie_keys| {
+            self.missing_trie_keys(correlation_id, trie_keys)
+                .map_err(|error| format!("{:?}", error))
+        }) {
+            Ok(missing_trie_keys) => {
+                let missing_trie_keys: Vec<Vec<u8>> =
+                    missing_trie_keys.iter().map(Blake2bHash::to_vec).collect();
+                missing_trie_keys_response
+                    .mut_success()
+                    .set_missing_trie_keys(missing_trie_keys.into());
+            }
+            Err(error_message) => {
+                logging::log_error(&error_message);
+                missing_trie_keys_response.set_failure(error_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_MISSING_TRIE_KEYS,
+            TAG_RESPONSE_MISSING_TRIE_KEYS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(missing_trie_keys_response)
+    }
+
+    fn put_tries(
+        &self,
+        _request_options: RequestOptions,
+        mut put_tries_request: PutTriesRequest,
+    ) -> SingleResponse<PutTriesResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut put_tries_response = PutTriesResponse::new();
+
+        let tries = put_tries_request
+            .take_tries()
+            .into_iter()
+            .map(|mut trie_node| {
+                let trie_key: Blake2bHash = trie_node
+                    .get_trie_key()
+                    .try_into()
+                    .map_err(|_| "Could not parse trie key".to_string())?;
+                Ok((trie_key, trie_node.take_trie_bytes()))
+            })
+            .collect::<Result<Vec<(Blake2bHash, Vec<u8>)>, String>>();
+
+        match tries.and_then(|tries| {
+            self.put_tries(correlation_id, &tries)
+                .map_err(|error| format!("{:?}", error))
+        }) {
+            Ok(put_trie_results) => {
+                let rejected_trie_keys: Vec<Vec<u8>> = put_trie_results
+                    .into_iter()
+                    .filter_map(|put_trie_result| match put_trie_result {
+                        PutTrieResult::Stored(_) => None,
+                        PutTrieResult::HashMismatch { expected, .. } => Some(expected.to_vec()),
+                    })
+                    .collect();
+                if !rejected_trie_keys.is_empty() {
+                    logging::log_warning("Rejected trie nodes which did not match their keys");
+                }
+                put_tries_response
+                    .mut_success()
+                    .set_rejected_trie_keys(rejected_trie_keys.into());
+            }
+            Err(error_message) => {
+                logging::log_error(&error_message);
+                put_tries_response.set_failure(error_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_PUT_TRIES,
+            TAG_RESPONSE_PUT_TRIES,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(put_tries_response)
+    }
+
+    fn execute_and_commit(
+        &self,
+        _request_options: RequestOptions,
+        execute_and_commit_request: ipc::ExecuteAndCommitRequest,
+    ) -> SingleResponse<ExecuteAndCommitResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let execute_and_commit_request: ExecuteAndCommitRequest =
+            match execute_and_commit_request.try_into() {
+                Ok(ret) => ret,
+                Err(response) => return SingleResponse::completed(response),
+            };
+        let pre_state_hash = execute_and_commit_request
+            .execute_request
+            .parent_state_hash;
+
+        let mut response = ExecuteAndCommitResponse::new();
+
+        match self.run_execute_and_commit(correlation_id, execute_and_commit_request) {
+            Ok((
+                results,
+                CommitResult::Success {
+                    state_root,
+                    bonded_validators,
+                },
+            )) => {
+                let properties = {
+                    let mut tmp = BTreeMap::new();
+                    tmp.insert("post-state-hash".to_string(), format!("{:?}", state_root));
+                    tmp.insert("success".to_string(), true.to_string());
+                    tmp
+                };
+                logging::log_details(
+                    LogLevel::Info,
+                    "deploys executed and committed; new state hash is: {post-state-hash}"
+                        .to_owned(),
+                    properties,
+                );
+
+                let bonds = bonded_validators.into_iter().map(Into::into).collect();
+                let protobuf_results_iter = results.into_iter().map(Into::into);
+                let result = response.mut_success();
+                result.set_poststate_hash(state_root.to_vec());
+                result.set_bonded_validators(bonds);
+                result.set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+            }
+            Ok((_, CommitResult::RootNotFound)) => {
+                logging::log_warning("RootNotFound");
+
+                response
+                    .mut_missing_parent()
+                    .set_hash(pre_state_hash.to_vec());
+            }
+            Ok((_, CommitResult::KeyNotFound(key))) => {
+                logging::log_warning("KeyNotFound");
+
+                response.set_key_not_found(key.into());
+            }
+            Ok((_, CommitResult::TypeMismatch(type_mismatch))) => {
+                logging::log_warning("TypeMismatch");
+
+                response.set_type_mismatch(type_mismatch.into());
+            }
+            Ok((_, CommitResult::Serialization(error))) => {
+                logging::log_warning("Serialization");
+
+                response
+                    .mut_failed_transform()
+                    .set_message(format!("{:?}", error));
+            }
+            Err(error) => {
+                let log_message = format!("State error {:?} when applying transforms", error);
+                logging::log_error(&log_message);
+
+                response
+                    .mut_failed_transform()
+                    .set_message(format!("{:?}", error));
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_EXECUTE_AND_COMMIT,
+            TAG_RESPONSE_EXECUTE_AND_COMMIT,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn estimate_gas(
+        &self,
+        _request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> SingleResponse<EstimateGasResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let exec_request: ExecuteRequest = match exec_request.try_into() {
+            Ok(ret) => ret,
+            // The request can only fail to parse on its parent state hash.
+            Err(mut exec_response) => {
+                let mut response = EstimateGasResponse::new();
+                response.set_missing_parent(exec_response.take_missing_parent());
+                return SingleResponse::completed(response);
+            }
+        };
+
+        let mut response = EstimateGasResponse::new();
+
+        // Inherent methods take precedence, so this is `EngineState::estimate_gas`.
+        match self.estimate_gas(correlation_id, exec_request) {
+            Ok(estimates) => {
+                let protobuf_estimates_iter = estimates.into_iter().map(Into::into);
+                response
+                    .mut_success()
+                    .set_estimates(FromIterator::from_iter(protobuf_estimates_iter));
+            }
+            Err(error) => {
+                logging::log_error("gas estimates error: RootNotFound");
+                response.mut_missing_parent().set_hash(error.0.to_vec());
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_ESTIMATE_GAS,
+            TAG_RESPONSE_ESTIMATE_GAS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn view(
+        &self,
+        _request_options: RequestOptions,
+        view_request: ipc::ViewRequest,
+    ) -> SingleResponse<ViewResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = ViewResponse::new();
+
+        let view_request: ViewRequest = match view_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                logging::log_error(&log_message);
+                response.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_VIEW,
+                    TAG_RESPONSE_VIEW,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(response);
+            }
+        };
+        let state_hash = view_request.state_hash();
+
+        match self.run_view(correlation_id, view_request) {
+            Ok(ViewResult::Success { value, cost }) => {
+                let log_message = format!("view successful; correlation_id: {}", correlation_id);
+                log_info(&log_message);
+                let result = response.mut_success();
+                result.set_value(value.into());
+                result.set_cost(cost.value().into());
+            }
+            Ok(ViewResult::Failure { error, cost }) => {
+                let log_message = format!("view failed: {:?}", error);
+                logging::log_warning(&log_message);
+                response.set_failed_call((error, Default::default(), cost).into());
+            }
+            Ok(ViewResult::RootNotFound) => {
+                logging::log_error("Root not found");
+                response.mut_missing_state().set_hash(state_hash.to_vec());
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                logging::log_error(&log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_VIEW,
+            TAG_RESPONSE_VIEW,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+}
+
+fn parse_trie_keys(trie_keys: &[Vec<u8>]) -> Result<Vec<Blake2bHash>, String> {
+    trie_keys
+        .iter()
+        .map(|trie_key| {
+            trie_key
+                .as_slice()
+                .try_into()
+                .map_err(|_| "Could not parse trie key".to_string())
+        })
+        .collect()
 }
 
 // Helper method which returns single DeployResult that is set to be a
@@ -86,6 +86,21 @@ const ARG_THREAD_COUNT_VALUE: &str = "NUM";
 const ARG_THREAD_COUNT_HELP: &str = "Worker thread count";
 const ARG_THREAD_COUNT_EXPECT: &str = "expected valid thread count";
 
+// trie cache size
+const ARG_TRIE_CACHE_SIZE: &str = "trie-cache-size";
+const ARG_TRIE_CACHE_SIZE_VALUE: &str = "BYTES";
+const ARG_TRIE_CACHE_SIZE_HELP: &str =
+    "Sets the max size in bytes of the decoded trie nodes to keep in memory.  Zero disables the \
+     cache.";
+const ARG_TRIE_CACHE_SIZE_EXPECT: &str = "expected valid trie cache size";
+
+// execution threads
+const ARG_EXECUTION_THREADS: &str = "execution-threads";
+const ARG_EXECUTION_THREADS_VALUE: &str = "NUM";
+const ARG_EXECUTION_THREADS_HELP: &str =
+    "Sets the number of threads used to execute the deploys of a single request in parallel";
+const ARG_EXECUTION_THREADS_EXPECT: &str = "expected valid execution thread count";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -196,6 +211,20 @@ fn get_args() -> ArgMatches<'static> {
                 .value_name(ARG_THREAD_COUNT_VALUE)
                 .help(ARG_THREAD_COUNT_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_TRIE_CACHE_SIZE)
+                .long(ARG_TRIE_CACHE_SIZE)
+                .takes_value(true)
+                .value_name(ARG_TRIE_CACHE_SIZE_VALUE)
+                .help(ARG_TRIE_CACHE_SIZE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_EXECUTION_THREADS)
+                .long(ARG_EXECUTION_THREADS)
+                .takes_value(true)
+                .value_name(ARG_EXECUTION_THREADS_VALUE)
+                .help(ARG_EXECUTION_THREADS_HELP),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
                 .required(true)
@@ -257,9 +286,23 @@ fn get_thread_count(matches: &ArgMatches) -> usize {
 }
 
 /// Returns an [`EngineConfig`].
-fn get_engine_config(_matches: &ArgMatches) -> EngineConfig {
+fn get_engine_config(matches: &ArgMatches) -> EngineConfig {
     // feature flags go here
-    EngineConfig::new()
+    let engine_config = EngineConfig::new();
+    let engine_config = match matches.value_of(ARG_TRIE_CACHE_SIZE) {
+        Some(value) => {
+            let trie_cache_size = value.parse().expect(ARG_TRIE_CACHE_SIZE_EXPECT);
+            engine_config.with_trie_cache_size(trie_cache_size)
+        }
+        None => engine_config,
+    };
+    match matches.value_of(ARG_EXECUTION_THREADS) {
+        Some(value) => {
+            let execution_threads = value.parse().expect(ARG_EXECUTION_THREADS_EXPECT);
+            engine_config.with_execution_threads(execution_threads)
+        }
+        None => engine_config,
+    }
 }
 
 /// Builds and returns a gRPC server.
@@ -300,8 +343,13 @@ fn get_engine_state(
         Arc::new(ret)
     };
 
-    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-        .expect(LMDB_GLOBAL_STATE_EXPECT);
+    let global_state = LmdbGlobalState::empty_with_trie_cache_size(
+        environment,
+        trie_store,
+        protocol_data_store,
+        engine_config.trie_cache_size(),
+    )
+    .expect(LMDB_GLOBAL_STATE_EXPECT);
 
     EngineState::new(global_state, engine_config)
 }
@@ -2,27 +2,57 @@ use std::collections::BTreeMap;
 
 use types::{
     bytesrepr::{Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH, U64_SERIALIZED_LENGTH},
-    Key, ProtocolVersion, KEY_UREF_SERIALIZED_LENGTH,
+    ContractAbi, Key, ProtocolVersion, DEFAULT_ENTRY_POINT, KEY_HASH_LENGTH,
+    KEY_UREF_SERIALIZED_LENGTH,
 };
 
+use crate::contract_wasm::ContractWasmHash;
+
+/// A stored contract.  Its Wasm code is stored separately as a
+/// [`ContractWasm`](crate::contract_wasm::ContractWasm) under `Key::Hash(wasm_hash)`.
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Contract {
-    bytes: Vec<u8>,
+    wasm_hash: ContractWasmHash,
     named_keys: BTreeMap<String, Key>,
     protocol_version: ProtocolVersion,
+    // Names of the exported functions which can be called, sorted and without duplicates
+    entry_points: Vec<String>,
+    abi: Option<ContractAbi>,
 }
 
 impl Contract {
+    /// Creates a contract whose only entry point is [`DEFAULT_ENTRY_POINT`].
     pub fn new(
-        bytes: Vec<u8>,
+        wasm_hash: ContractWasmHash,
         named_keys: BTreeMap<String, Key>,
         protocol_version: ProtocolVersion,
     ) -> Self {
         Contract {
-            bytes,
+            wasm_hash,
             named_keys,
             protocol_version,
+            entry_points: vec![DEFAULT_ENTRY_POINT.to_string()],
+            abi: None,
+        }
+    }
+
+    /// Replaces the entry points of the contract.  A contract without any keeps
+    /// [`DEFAULT_ENTRY_POINT`] as its only one.
+    pub fn with_entry_points<I: IntoIterator<Item = String>>(mut self, entry_points: I) -> Self {
+        let mut entry_points: Vec<String> = entry_points.into_iter().collect();
+        if entry_points.is_empty() {
+            return self;
         }
+        entry_points.sort();
+        entry_points.dedup();
+        self.entry_points = entry_points;
+        self
+    }
+
+    /// Sets the ABI describing the arguments and return values of the entry points.
+    pub fn with_abi(mut self, abi: ContractAbi) -> Self {
+        self.abi = Some(abi);
+        self
     }
 
     pub fn named_keys_append(&mut self, keys: &mut BTreeMap<String, Key>) {
@@ -37,18 +67,46 @@ impl Contract {
         &mut self.named_keys
     }
 
-    pub fn destructure(self) -> (Vec<u8>, BTreeMap<String, Key>, ProtocolVersion) {
-        (self.bytes, self.named_keys, self.protocol_version)
+    pub fn destructure(
+        self,
+    ) -> (
+        ContractWasmHash,
+        BTreeMap<String, Key>,
+        ProtocolVersion,
+        Vec<String>,
+        Option<ContractAbi>,
+    ) {
+        (
+            self.wasm_hash,
+            self.named_keys,
+            self.protocol_version,
+            self.entry_points,
+            self.abi,
+        )
     }
 
-    pub fn bytes(&self) -> &[u8] {
-        &self.bytes
+    pub fn wasm_hash(&self) -> ContractWasmHash {
+        self.wasm_hash
     }
 
     pub fn protocol_version(&self) -> ProtocolVersion {
         self.protocol_version
     }
 
+    pub fn entry_points(&self) -> &[String] {
+        &self.entry_points
+    }
+
+    pub fn has_entry_point(&self, name: &str) -> bool {
+        self.entry_points
+            .binary_search_by(|entry_point| entry_point.as_str().cmp(name))
+            .is_ok()
+    }
+
+    pub fn abi(&self) -> Option<&ContractAbi> {
+        self.abi.as_ref()
+    }
+
     pub fn take_named_keys(self) -> BTreeMap<String, Key> {
         self.named_keys
     }
@@ -56,56 +114,127 @@ impl Contract {
 
 impl ToBytes for Contract {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
-        if self.bytes.len()
+        if KEY_HASH_LENGTH
             + KEY_UREF_SERIALIZED_LENGTH * self.named_keys.len()
             + U64_SERIALIZED_LENGTH
-            >= u32::max_value() as usize - U32_SERIALIZED_LENGTH * 2
+            >= u32::max_value() as usize - U32_SERIALIZED_LENGTH
         {
             return Err(Error::OutOfMemoryError);
         }
-        let size: usize = U32_SERIALIZED_LENGTH +                        //size for length of bytes
-                    self.bytes.len() +                                   //size for elements of bytes
+        let size: usize = KEY_HASH_LENGTH +                              //size for wasm_hash
                     U32_SERIALIZED_LENGTH +                              //size for length of named_keys
                     KEY_UREF_SERIALIZED_LENGTH * self.named_keys.len() + //size for named_keys elements
-                    U64_SERIALIZED_LENGTH; //size for protocol_version
+                    U64_SERIALIZED_LENGTH +                              //size for protocol_version
+                    U32_SERIALIZED_LENGTH; //size for length of entry_points
 
         let mut result = Vec::with_capacity(size);
-        result.append(&mut self.bytes.to_bytes()?);
+        result.append(&mut self.wasm_hash.to_bytes()?);
         result.append(&mut self.named_keys.to_bytes()?);
         result.append(&mut self.protocol_version.to_bytes()?);
+        result.append(&mut self.entry_points.to_bytes()?);
+        result.append(&mut self.abi.to_bytes()?);
         Ok(result)
     }
 }
 
 impl FromBytes for Contract {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
-        let (bytes, rem1): (Vec<u8>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (wasm_hash, rem1): (ContractWasmHash, &[u8]) = FromBytes::from_bytes(bytes)?;
         let (named_keys, rem2): (BTreeMap<String, Key>, &[u8]) = FromBytes::from_bytes(rem1)?;
         let (protocol_version, rem3): (ProtocolVersion, &[u8]) = FromBytes::from_bytes(rem2)?;
-        Ok((
-            Contract {
-                bytes,
-                named_keys,
-                protocol_version,
-            },
-            rem3,
-        ))
+        let (entry_points, rem4): (Vec<String>, &[u8]) = from_bytes_or_default(rem3)?;
+        let (abi, rem5): (Option<ContractAbi>, &[u8]) = from_bytes_or_default(rem4)?;
+        let contract =
+            Contract::new(wasm_hash, named_keys, protocol_version).with_entry_points(entry_points);
+        match abi {
+            Some(abi) => Ok((contract.with_abi(abi), rem5)),
+            None => Ok((contract, rem5)),
+        }
+    }
+}
+
+/// Deserializes a field appended to the encoding of [`Contract`] after contracts were first
+/// stored, taking its default if a contract stored before the field existed has ended.
+fn from_bytes_or_default<T: FromBytes + Default>(bytes: &[u8]) -> Result<(T, &[u8]), Error> {
+    if bytes.is_empty() {
+        Ok((T::default(), bytes))
+    } else {
+        T::from_bytes(bytes)
     }
 }
 
 pub mod gens {
-    use proptest::{collection::vec, prelude::*};
+    use proptest::{collection::vec, option, prelude::*};
 
-    use types::gens::{named_keys_arb, protocol_version_arb};
+    use types::gens::{contract_abi_arb, named_keys_arb, protocol_version_arb};
 
     use super::Contract;
 
     pub fn contract_arb() -> impl Strategy<Value = Contract> {
         protocol_version_arb().prop_flat_map(move |protocol_version_arb| {
             named_keys_arb(20).prop_flat_map(move |urefs| {
-                vec(any::<u8>(), 1..1000)
-                    .prop_map(move |body| Contract::new(body, urefs.clone(), protocol_version_arb))
+                vec("[a-z_]{1,16}", 1..5).prop_flat_map(move |entry_points| {
+                    let urefs = urefs.clone();
+                    option::of(contract_abi_arb()).prop_flat_map(move |abi| {
+                        let urefs = urefs.clone();
+                        let entry_points = entry_points.clone();
+                        any::<[u8; 32]>().prop_map(move |wasm_hash| {
+                            let contract =
+                                Contract::new(wasm_hash, urefs.clone(), protocol_version_arb)
+                                    .with_entry_points(entry_points.clone());
+                            match abi.clone() {
+                                Some(abi) => contract.with_abi(abi),
+                                None => contract,
+                            }
+                        })
+                    })
+                })
             })
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr;
+
+    use super::*;
+
+    #[test]
+    fn should_deserialize_contract_stored_before_entry_points_were_added() {
+        let named_keys = BTreeMap::new();
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let legacy_bytes = {
+            let mut ret = [1u8; 32].to_bytes().unwrap();
+            ret.append(&mut named_keys.to_bytes().unwrap());
+            ret.append(&mut protocol_version.to_bytes().unwrap());
+            ret
+        };
+
+        let contract: Contract =
+            bytesrepr::deserialize(legacy_bytes).expect("should deserialize legacy contract");
+
+        assert_eq!(
+            contract,
+            Contract::new([1u8; 32], named_keys, protocol_version)
+        );
+        assert_eq!(contract.entry_points(), &[DEFAULT_ENTRY_POINT.to_string()]);
+        assert!(contract.abi().is_none());
+    }
+
+    #[test]
+    fn should_keep_default_entry_point_without_entry_points() {
+        let contract = Contract::new([1u8; 32], BTreeMap::new(), ProtocolVersion::V1_0_0)
+            .with_entry_points(Vec::new());
+        assert!(contract.has_entry_point(DEFAULT_ENTRY_POINT));
+    }
+
+    proptest! {
+        #[test]
+        fn serialization_roundtrip(contract in gens::contract_arb()) {
+            bytesrepr::test_serialization_roundtrip(&contract);
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use types::{
+    bytesrepr::{Error, FromBytes, ToBytes},
+    KEY_HASH_LENGTH,
+};
+
+use crate::newtypes::Blake2bHash;
+
+/// The hash of a [`ContractWasm`], under which it is stored as a `Key::Hash`.
+pub type ContractWasmHash = [u8; KEY_HASH_LENGTH];
+
+/// The Wasm code of one or more [`Contract`](crate::contract::Contract)s, stored once in global
+/// state and shared by every contract which refers to it by hash.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct ContractWasm {
+    bytes: Vec<u8>,
+}
+
+impl ContractWasm {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        ContractWasm { bytes }
+    }
+
+    /// Returns the hash of the Wasm bytes.
+    pub fn hash(&self) -> ContractWasmHash {
+        Blake2bHash::new(&self.bytes).into()
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn take_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl ToBytes for ContractWasm {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.bytes.to_bytes()
+    }
+}
+
+impl FromBytes for ContractWasm {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (bytes, remainder): (Vec<u8>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((ContractWasm { bytes }, remainder))
+    }
+}
+
+pub mod gens {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::ContractWasm;
+
+    pub fn contract_wasm_arb() -> impl Strategy<Value = ContractWasm> {
+        vec(any::<u8>(), 1..1000).prop_map(ContractWasm::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr;
+
+    use super::*;
+
+    #[test]
+    fn equal_code_should_have_equal_hashes() {
+        let contract_wasm = ContractWasm::new(vec![0, 97, 115, 109]);
+        assert_eq!(contract_wasm.hash(), contract_wasm.clone().hash());
+        assert_ne!(contract_wasm.hash(), ContractWasm::new(vec![0]).hash());
+    }
+
+    proptest! {
+        #[test]
+        fn serialization_roundtrip(contract_wasm in gens::contract_wasm_arb()) {
+            bytesrepr::test_serialization_roundtrip(&contract_wasm);
+        }
+    }
+}
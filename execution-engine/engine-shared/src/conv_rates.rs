@@ -0,0 +1,130 @@
+use types::bytesrepr::{self, FromBytes, ToBytes, U64_SERIALIZED_LENGTH};
+
+/// The rate deploys which don't set a gas price pay at, unless configured otherwise.
+pub const DEFAULT_CONV_RATE: u64 = 10;
+/// The lowest gas price a deploy may set, unless configured otherwise.
+pub const DEFAULT_MIN_CONV_RATE: u64 = 1;
+
+const NUM_FIELDS: usize = 2;
+pub const CONV_RATES_SERIALIZED_LENGTH: usize = NUM_FIELDS * U64_SERIALIZED_LENGTH;
+
+/// The rates, in motes per unit of gas, at which deploys pay for the gas they use.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ConvRates {
+    min_conv_rate: u64,
+    default_conv_rate: u64,
+}
+
+impl ConvRates {
+    pub fn new(min_conv_rate: u64, default_conv_rate: u64) -> Self {
+        ConvRates {
+            min_conv_rate,
+            default_conv_rate,
+        }
+    }
+
+    /// Returns the lowest gas price a deploy may set.
+    pub fn min_conv_rate(&self) -> u64 {
+        self.min_conv_rate
+    }
+
+    /// Returns the rate deploys which don't set a gas price pay at.
+    pub fn default_conv_rate(&self) -> u64 {
+        self.default_conv_rate
+    }
+
+    /// Returns the rate a deploy with the given gas price pays at, or `None` if the price is too
+    /// low.  A gas price of zero means the default rate.
+    pub fn conv_rate(&self, gas_price: u64) -> Option<u64> {
+        let conv_rate = if gas_price == 0 {
+            self.default_conv_rate
+        } else {
+            gas_price
+        };
+        if conv_rate == 0 || conv_rate < self.min_conv_rate {
+            return None;
+        }
+        Some(conv_rate)
+    }
+}
+
+impl Default for ConvRates {
+    fn default() -> Self {
+        ConvRates::new(DEFAULT_MIN_CONV_RATE, DEFAULT_CONV_RATE)
+    }
+}
+
+impl ToBytes for ConvRates {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = Vec::with_capacity(CONV_RATES_SERIALIZED_LENGTH);
+        ret.append(&mut self.min_conv_rate.to_bytes()?);
+        ret.append(&mut self.default_conv_rate.to_bytes()?);
+        Ok(ret)
+    }
+}
+
+impl FromBytes for ConvRates {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (min_conv_rate, rem): (u64, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (default_conv_rate, rem): (u64, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((ConvRates::new(min_conv_rate, default_conv_rate), rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{num, prop_compose};
+
+    use crate::conv_rates::ConvRates;
+
+    prop_compose! {
+        pub fn conv_rates_arb()(
+            min_conv_rate in num::u64::ANY,
+            default_conv_rate in num::u64::ANY,
+        ) -> ConvRates {
+            ConvRates::new(min_conv_rate, default_conv_rate)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr;
+
+    use super::{gens, ConvRates, DEFAULT_CONV_RATE};
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        bytesrepr::test_serialization_roundtrip(&ConvRates::default());
+        bytesrepr::test_serialization_roundtrip(&ConvRates::new(5, 20));
+    }
+
+    #[test]
+    fn should_use_default_rate_for_zero_gas_price() {
+        assert_eq!(ConvRates::default().conv_rate(0), Some(DEFAULT_CONV_RATE));
+        assert_eq!(ConvRates::new(5, 20).conv_rate(0), Some(20));
+    }
+
+    #[test]
+    fn should_use_gas_price_at_or_above_minimum() {
+        let conv_rates = ConvRates::new(5, 20);
+        assert_eq!(conv_rates.conv_rate(5), Some(5));
+        assert_eq!(conv_rates.conv_rate(100), Some(100));
+    }
+
+    #[test]
+    fn should_reject_gas_price_below_minimum() {
+        assert_eq!(ConvRates::new(5, 20).conv_rate(4), None);
+        assert_eq!(ConvRates::new(0, 0).conv_rate(0), None);
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            conv_rates in gens::conv_rates_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&conv_rates);
+        }
+    }
+}
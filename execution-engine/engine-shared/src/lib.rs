@@ -5,6 +5,8 @@ pub mod additive_map;
 pub mod gas;
 pub mod account;
 pub mod contract;
+pub mod contract_wasm;
+pub mod conv_rates;
 pub mod logging;
 pub mod motes;
 pub mod newtypes;
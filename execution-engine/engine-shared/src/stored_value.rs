@@ -5,13 +5,16 @@ use types::{
     CLValue,
 };
 
-use crate::{account::Account, contract::Contract, transform::TypeMismatch};
+use crate::{
+    account::Account, contract::Contract, contract_wasm::ContractWasm, transform::TypeMismatch,
+};
 
 #[repr(u8)]
 enum Tag {
     CLValue = 0,
     Account = 1,
     Contract = 2,
+    ContractWasm = 3,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -19,6 +22,7 @@ pub enum StoredValue {
     CLValue(CLValue),
     Account(Account),
     Contract(Contract),
+    ContractWasm(ContractWasm),
 }
 
 impl StoredValue {
@@ -43,11 +47,19 @@ impl StoredValue {
         }
     }
 
+    pub fn as_contract_wasm(&self) -> Option<&ContractWasm> {
+        match self {
+            StoredValue::ContractWasm(contract_wasm) => Some(contract_wasm),
+            _ => None,
+        }
+    }
+
     pub fn type_name(&self) -> String {
         match self {
             StoredValue::CLValue(cl_value) => format!("{:?}", cl_value.cl_type()),
             StoredValue::Account(_) => "Account".to_string(),
             StoredValue::Contract(_) => "Contract".to_string(),
+            StoredValue::ContractWasm(_) => "ContractWasm".to_string(),
         }
     }
 }
@@ -94,6 +106,20 @@ impl TryFrom<StoredValue> for Contract {
     }
 }
 
+impl TryFrom<StoredValue> for ContractWasm {
+    type Error = TypeMismatch;
+
+    fn try_from(stored_value: StoredValue) -> Result<Self, Self::Error> {
+        match stored_value {
+            StoredValue::ContractWasm(contract_wasm) => Ok(contract_wasm),
+            _ => Err(TypeMismatch::new(
+                "ContractWasm".to_string(),
+                stored_value.type_name(),
+            )),
+        }
+    }
+}
+
 fn to_bytes<T: ToBytes>(value: &T, tag: Tag) -> Result<Vec<u8>, bytesrepr::Error> {
     let mut bytes = value.to_bytes()?;
     if bytes.len() >= u32::max_value() as usize - mem::size_of::<Tag>() {
@@ -112,6 +138,7 @@ impl ToBytes for StoredValue {
             StoredValue::CLValue(cl_value) => to_bytes(cl_value, Tag::CLValue),
             StoredValue::Account(account) => to_bytes(account, Tag::Account),
             StoredValue::Contract(contract) => to_bytes(contract, Tag::Contract),
+            StoredValue::ContractWasm(contract_wasm) => to_bytes(contract_wasm, Tag::ContractWasm),
         }
     }
 }
@@ -126,6 +153,11 @@ impl FromBytes for StoredValue {
                 .map(|(account, remainder)| (StoredValue::Account(account), remainder)),
             tag if tag == Tag::Contract as u8 => Contract::from_bytes(remainder)
                 .map(|(contract, remainder)| (StoredValue::Contract(contract), remainder)),
+            tag if tag == Tag::ContractWasm as u8 => {
+                ContractWasm::from_bytes(remainder).map(|(contract_wasm, remainder)| {
+                    (StoredValue::ContractWasm(contract_wasm), remainder)
+                })
+            }
             _ => Err(bytesrepr::Error::FormattingError),
         }
     }
@@ -137,13 +169,17 @@ pub mod gens {
     use types::gens::cl_value_arb;
 
     use super::StoredValue;
-    use crate::{account::gens::account_arb, contract::gens::contract_arb};
+    use crate::{
+        account::gens::account_arb, contract::gens::contract_arb,
+        contract_wasm::gens::contract_wasm_arb,
+    };
 
     pub fn stored_value_arb() -> impl Strategy<Value = StoredValue> {
         prop_oneof![
             cl_value_arb().prop_map(StoredValue::CLValue),
             account_arb().prop_map(StoredValue::Account),
             contract_arb().prop_map(StoredValue::Contract),
+            contract_wasm_arb().prop_map(StoredValue::ContractWasm),
         ]
     }
 }
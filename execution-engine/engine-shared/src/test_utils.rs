@@ -6,7 +6,9 @@ use parity_wasm::{
     elements::{MemorySection, MemoryType, Module, Section, Serialize},
 };
 
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{
+    host_function_costs::HostFunctionCosts, storage_costs::StorageCosts, wasm_costs::WasmCosts,
+};
 use types::{account::PurseId, AccessRights, Key, URef};
 
 use crate::{account::Account, stored_value::StoredValue};
@@ -57,3 +59,54 @@ pub fn wasm_costs_free() -> WasmCosts {
         opcodes_div: 1,
     }
 }
+
+pub fn host_function_costs_mock() -> HostFunctionCosts {
+    HostFunctionCosts {
+        read_value: 10_000,
+        read_value_local: 10_000,
+        write: 10_000,
+        write_local: 10_000,
+        add: 10_000,
+        add_local: 10_000,
+        new_uref: 10_000,
+        load_named_keys: 10_000,
+        ret: 10_000,
+        get_key: 10_000,
+        has_key: 10_000,
+        put_key: 10_000,
+        remove_key: 10_000,
+        call_contract: 10_000,
+        get_arg_size: 10_000,
+        get_arg: 10_000,
+        get_caller: 10_000,
+        get_blocktime: 10_000,
+        store_function: 10_000,
+        store_function_at_hash: 10_000,
+        is_valid_uref: 10_000,
+        revert: 10_000,
+        add_associated_key: 10_000,
+        remove_associated_key: 10_000,
+        update_associated_key: 10_000,
+        set_action_threshold: 10_000,
+        create_purse: 10_000,
+        transfer_to_account: 10_000,
+        transfer_from_purse_to_account: 10_000,
+        transfer_from_purse_to_purse: 10_000,
+        get_balance: 10_000,
+        get_phase: 10_000,
+        upgrade_contract_at_uref: 10_000,
+        get_system_contract: 10_000,
+        get_main_purse: 10_000,
+        read_host_buffer: 10_000,
+        emit_event: 10_000,
+        store_contract: 10_000,
+        store_contract_at_hash: 10_000,
+        try_call_contract: 10_000,
+        read_value_byte: 10,
+        event_byte: 100,
+    }
+}
+
+pub fn storage_costs_mock() -> StorageCosts {
+    StorageCosts::new(10)
+}
@@ -71,6 +71,8 @@ pub enum Transform {
     AddUInt256(U256),
     AddUInt512(U512),
     AddKeys(BTreeMap<String, Key>),
+    /// Removes the value stored under the key, if any.
+    Delete,
     Failure(Error),
 }
 
@@ -148,6 +150,10 @@ where
 }
 
 impl Transform {
+    /// Applies this transform to `stored_value`, returning the updated value.
+    ///
+    /// A [`Transform::Delete`] leaves no value behind, so it yields a [`TypeMismatch`] error here;
+    /// it is handled by removing the key when committing instead.
     pub fn apply(self, stored_value: StoredValue) -> Result<StoredValue, Error> {
         match self {
             Transform::Identity => Ok(stored_value),
@@ -171,7 +177,17 @@ impl Transform {
                     let found = format!("{:?}", cl_value.cl_type());
                     Err(TypeMismatch::new(expected, found).into())
                 }
+                StoredValue::ContractWasm(_) => {
+                    let expected = "Contract or Account".to_string();
+                    let found = "ContractWasm".to_string();
+                    Err(TypeMismatch::new(expected, found).into())
+                }
             },
+            Transform::Delete => Err(TypeMismatch::new(
+                "a transform which produces a value".to_string(),
+                "Delete".to_string(),
+            )
+            .into()),
             Transform::Failure(error) => Err(error),
         }
     }
@@ -219,6 +235,11 @@ impl Add for Transform {
             (a @ Transform::Failure(_), _) => a,
             (_, b @ Transform::Failure(_)) => b,
             (_, b @ Transform::Write(_)) => b,
+            (_, Transform::Delete) => Transform::Delete,
+            // only a write can follow the removal of a value
+            (Transform::Delete, other) => Transform::Failure(
+                TypeMismatch::new("Write".to_owned(), format!("{:?}", other)).into(),
+            ),
             (Transform::Write(v), b) => {
                 // second transform changes value being written
                 match b.apply(v) {
@@ -289,6 +310,7 @@ pub mod gens {
     pub fn transform_arb() -> impl Strategy<Value = Transform> {
         prop_oneof![
             Just(Transform::Identity),
+            Just(Transform::Delete),
             stored_value_arb().prop_map(Transform::Write),
             any::<i32>().prop_map(Transform::AddInt32),
             any::<u64>().prop_map(Transform::AddUInt64),
@@ -443,7 +465,7 @@ mod tests {
         }
 
         let contract = StoredValue::Contract(Contract::new(
-            vec![],
+            [0; 32],
             BTreeMap::new(),
             ProtocolVersion::default(),
         ));
@@ -753,4 +775,28 @@ mod tests {
         assert_eq!(ZERO_U512, add(MAX_U512, ONE_U512));
         assert_eq!(MAX_U512 - 1, add(MAX_U512, MAX_U512));
     }
+
+    #[test]
+    fn delete_should_combine_with_other_transforms() {
+        let value = StoredValue::CLValue(CLValue::from_t(ONE_U64).unwrap());
+        let write = Transform::Write(value.clone());
+
+        assert_eq!(Transform::Identity + Transform::Delete, Transform::Delete);
+        assert_eq!(Transform::Delete + Transform::Identity, Transform::Delete);
+        assert_eq!(write.clone() + Transform::Delete, Transform::Delete);
+        assert_eq!(
+            Transform::AddUInt64(ONE_U64) + Transform::Delete,
+            Transform::Delete
+        );
+        assert_eq!(Transform::Delete + write.clone(), write);
+
+        match Transform::Delete + Transform::AddUInt64(ONE_U64) {
+            Transform::Failure(Error::TypeMismatch(_)) => (),
+            other => panic!("adding to a deleted value should fail, got {:?}", other),
+        }
+        match Transform::Delete.apply(value) {
+            Err(Error::TypeMismatch(_)) => (),
+            other => panic!("applying a delete should fail, got {:?}", other),
+        }
+    }
 }
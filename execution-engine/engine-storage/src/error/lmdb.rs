@@ -4,6 +4,7 @@ use failure::Fail;
 use lmdb as lmdb_external;
 use wasmi;
 
+use engine_shared::newtypes::Blake2bHash;
 use types::bytesrepr;
 
 use super::in_memory;
@@ -18,6 +19,9 @@ pub enum Error {
 
     #[fail(display = "Another thread panicked while holding a lock")]
     PoisonError,
+
+    #[fail(display = "State root {} not found", _0)]
+    RootNotFound(Blake2bHash),
 }
 
 impl wasmi::HostError for Error {}
@@ -0,0 +1,438 @@
+//! Integrity checks of an LMDB-backed global state.
+//!
+//! [`check`] walks every trie node reachable from a set of state roots and every protocol data
+//! entry, and reports the problems it finds.  It only ever reads from the store.
+
+use std::{collections::HashSet, fmt};
+
+use lmdb::{self, Cursor};
+
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use types::{
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion,
+};
+
+use crate::{
+    error,
+    protocol_data::ProtocolData,
+    protocol_data_store::lmdb::LmdbProtocolDataStore,
+    store::Store,
+    transaction_source::{lmdb::LmdbEnvironment, Readable, Transaction, TransactionSource},
+    trie::{Pointer, Trie},
+    trie_store::lmdb::LmdbTrieStore,
+};
+
+/// A problem found by [`check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// A state root or the target of a pointer is not in the trie store.
+    MissingTrie {
+        trie_key: Blake2bHash,
+        parent: Option<Blake2bHash>,
+    },
+    /// The bytes stored under a trie key do not hash to that key.
+    HashMismatch {
+        trie_key: Blake2bHash,
+        actual: Blake2bHash,
+    },
+    /// The bytes stored under a trie key are not a valid trie, including an invalid leaf key or
+    /// [`StoredValue`].
+    InvalidTrie {
+        trie_key: Blake2bHash,
+        error: bytesrepr::Error,
+    },
+    /// A leaf pointer targets a node or extension, or a node pointer targets a leaf.
+    PointerMismatch {
+        trie_key: Blake2bHash,
+        parent: Blake2bHash,
+    },
+    /// The key of a leaf does not start with the path leading to it.
+    PathMismatch {
+        trie_key: Blake2bHash,
+        path: Vec<u8>,
+    },
+    /// A protocol data entry is not a valid [`ProtocolVersion`] and [`ProtocolData`] pair.
+    InvalidProtocolData {
+        key_bytes: Vec<u8>,
+        error: bytesrepr::Error,
+    },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Problem::MissingTrie {
+                trie_key,
+                parent: Some(parent),
+            } => write!(f, "Missing trie {} referenced by {}", trie_key, parent),
+            Problem::MissingTrie {
+                trie_key,
+                parent: None,
+            } => write!(f, "Missing state root {}", trie_key),
+            Problem::HashMismatch { trie_key, actual } => {
+                write!(f, "Trie stored under {} hashes to {}", trie_key, actual)
+            }
+            Problem::InvalidTrie { trie_key, error } => {
+                write!(f, "Invalid trie {}: {}", trie_key, error)
+            }
+            Problem::PointerMismatch { trie_key, parent } => write!(
+                f,
+                "Trie {} does not match the kind of pointer to it in {}",
+                trie_key, parent
+            ),
+            Problem::PathMismatch { trie_key, path } => {
+                write!(f, "Leaf {} is out of place at path {:?}", trie_key, path)
+            }
+            Problem::InvalidProtocolData { key_bytes, error } => {
+                write!(f, "Invalid protocol data under {:?}: {}", key_bytes, error)
+            }
+        }
+    }
+}
+
+/// The outcome of [`check`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FsckReport {
+    /// The number of distinct trie nodes read.
+    pub tries_checked: u64,
+    /// The number of protocol data entries read.
+    pub protocol_data_checked: u64,
+    pub problems: Vec<Problem>,
+}
+
+impl FsckReport {
+    /// Returns `true` if no problem was found.
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Checks the integrity of the tries reachable from `roots` and of every protocol data entry.
+///
+/// Each trie node is checked the first time it is reached, so a node shared between several
+/// roots is only checked against the path leading to it from the first of them.
+///
+/// Nothing is written, so `environment` can be opened with
+/// [`LmdbEnvironment::open_read_only`].
+pub fn check(
+    environment: &LmdbEnvironment,
+    trie_store: &LmdbTrieStore,
+    protocol_data_store: &LmdbProtocolDataStore,
+    roots: &[Blake2bHash],
+) -> Result<FsckReport, error::Error> {
+    let mut report = FsckReport::default();
+    let txn = environment.create_read_txn()?;
+
+    let trie_handle = Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(trie_store);
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut pending: Vec<(Pointer, Option<Blake2bHash>, Vec<u8>)> = roots
+        .iter()
+        .rev()
+        .map(|root| (Pointer::NodePointer(*root), None, Vec::new()))
+        .collect();
+
+    while let Some((pointer, parent, path)) = pending.pop() {
+        let trie_key = *pointer.hash();
+        if !visited.insert(trie_key) {
+            continue;
+        }
+
+        let trie_bytes = match txn.read(trie_handle, &trie_key.to_bytes()?)? {
+            Some(trie_bytes) => trie_bytes,
+            None => {
+                report
+                    .problems
+                    .push(Problem::MissingTrie { trie_key, parent });
+                continue;
+            }
+        };
+        report.tries_checked += 1;
+
+        let actual = Blake2bHash::new(&trie_bytes);
+        if actual != trie_key {
+            report
+                .problems
+                .push(Problem::HashMismatch { trie_key, actual });
+        }
+
+        let trie: Trie<Key, StoredValue> = match bytesrepr::deserialize(trie_bytes) {
+            Ok(trie) => trie,
+            Err(error) => {
+                report
+                    .problems
+                    .push(Problem::InvalidTrie { trie_key, error });
+                continue;
+            }
+        };
+
+        if let Some(parent) = parent {
+            let pointer_matches = match (pointer, &trie) {
+                (Pointer::LeafPointer(_), Trie::Leaf { .. }) => true,
+                (Pointer::NodePointer(_), Trie::Leaf { .. }) => false,
+                (Pointer::LeafPointer(_), _) => false,
+                (Pointer::NodePointer(_), _) => true,
+            };
+            if !pointer_matches {
+                report
+                    .problems
+                    .push(Problem::PointerMismatch { trie_key, parent });
+            }
+        }
+
+        match trie {
+            Trie::Leaf { key, .. } => {
+                if !key.to_bytes()?.starts_with(&path) {
+                    report
+                        .problems
+                        .push(Problem::PathMismatch { trie_key, path });
+                }
+            }
+            Trie::Node { pointer_block } => {
+                for (index, maybe_pointer) in pointer_block[..].iter().enumerate().rev() {
+                    if let Some(child) = maybe_pointer {
+                        let mut child_path = path.clone();
+                        child_path.push(index as u8);
+                        pending.push((*child, Some(trie_key), child_path));
+                    }
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                let mut child_path = path;
+                child_path.extend(affix);
+                pending.push((pointer, Some(trie_key), child_path));
+            }
+        }
+    }
+
+    {
+        let handle = protocol_data_store.handle();
+        let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, handle)?;
+        for entry in cursor.iter_start() {
+            let (key_bytes, value_bytes) = entry?;
+            report.protocol_data_checked += 1;
+            let result = bytesrepr::deserialize::<ProtocolVersion>(key_bytes.to_vec())
+                .and_then(|_| bytesrepr::deserialize::<ProtocolData>(value_bytes.to_vec()));
+            if let Err(error) = result {
+                report.problems.push(Problem::InvalidProtocolData {
+                    key_bytes: key_bytes.to_vec(),
+                    error,
+                });
+            }
+        }
+    }
+
+    txn.commit()?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use lmdb::DatabaseFlags;
+    use tempfile::{tempdir, TempDir};
+
+    use engine_shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform};
+    use types::{AccessRights, CLValue, URef};
+
+    use super::*;
+    use crate::{
+        global_state::{lmdb::LmdbGlobalState, CommitResult, StateProvider},
+        transaction_source::Writable,
+        TEST_MAP_SIZE,
+    };
+
+    fn create_test_state() -> (TempDir, LmdbGlobalState, Blake2bHash) {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let environment =
+            Arc::new(LmdbEnvironment::new(&temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap());
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let protocol_data_store = Arc::new(
+            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+
+        let effects: AdditiveMap<Key, Transform> = (0..20u8)
+            .map(|i| {
+                let value = StoredValue::CLValue(CLValue::from_t(i32::from(i)).unwrap());
+                (Key::Account([i; 32]), Transform::Write(value))
+            })
+            .collect();
+        let root_hash = match state
+            .commit(correlation_id, state.empty_root(), effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        let protocol_data = ProtocolData::new(
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            URef::new([1; 32], AccessRights::READ),
+            URef::new([2; 32], AccessRights::READ),
+        );
+        state
+            .put_protocol_data(ProtocolVersion::V1_0_0, &protocol_data)
+            .unwrap();
+        (temp_dir, state, root_hash)
+    }
+
+    fn check_state(state: &LmdbGlobalState, roots: &[Blake2bHash]) -> FsckReport {
+        check(
+            &state.environment,
+            &state.trie_store,
+            &state.protocol_data_store,
+            roots,
+        )
+        .unwrap()
+    }
+
+    fn write_trie_bytes(state: &LmdbGlobalState, trie_key: Blake2bHash, trie_bytes: &[u8]) {
+        let mut txn = state.environment.create_read_write_txn().unwrap();
+        let handle = Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(&*state.trie_store);
+        txn.write(handle, &trie_key.to_bytes().unwrap(), trie_bytes)
+            .unwrap();
+        txn.commit().unwrap();
+    }
+
+    fn write_trie(state: &LmdbGlobalState, trie: &Trie<Key, StoredValue>) -> Blake2bHash {
+        let trie_bytes = trie.to_bytes().unwrap();
+        let trie_key = Blake2bHash::new(&trie_bytes);
+        write_trie_bytes(state, trie_key, &trie_bytes);
+        trie_key
+    }
+
+    fn test_leaf() -> Trie<Key, StoredValue> {
+        Trie::leaf(
+            Key::Account([42; 32]),
+            StoredValue::CLValue(CLValue::from_t(42i32).unwrap()),
+        )
+    }
+
+    #[test]
+    fn should_report_no_problems_for_a_healthy_state() {
+        let (_temp_dir, state, root_hash) = create_test_state();
+        let report = check_state(&state, &[root_hash, state.empty_root()]);
+        assert!(report.is_ok(), "{:?}", report.problems);
+        assert!(report.tries_checked > 20);
+        assert_eq!(report.protocol_data_checked, 1);
+    }
+
+    #[test]
+    fn should_report_missing_root() {
+        let (_temp_dir, state, _) = create_test_state();
+        let missing_root = Blake2bHash::new(b"missing");
+        let report = check_state(&state, &[missing_root]);
+        assert_eq!(
+            report.problems,
+            vec![Problem::MissingTrie {
+                trie_key: missing_root,
+                parent: None
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_missing_child() {
+        let (_temp_dir, state, _) = create_test_state();
+        let missing_leaf = Blake2bHash::new(b"missing");
+        let root = write_trie(
+            &state,
+            &Trie::node(&[(0, Pointer::LeafPointer(missing_leaf))]),
+        );
+        let report = check_state(&state, &[root]);
+        assert_eq!(
+            report.problems,
+            vec![Problem::MissingTrie {
+                trie_key: missing_leaf,
+                parent: Some(root)
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_hash_mismatch() {
+        let (_temp_dir, state, _) = create_test_state();
+        let trie_bytes = test_leaf().to_bytes().unwrap();
+        let wrong_key = Blake2bHash::new(b"wrong");
+        write_trie_bytes(&state, wrong_key, &trie_bytes);
+        let report = check_state(&state, &[wrong_key]);
+        assert_eq!(
+            report.problems,
+            vec![Problem::HashMismatch {
+                trie_key: wrong_key,
+                actual: Blake2bHash::new(&trie_bytes)
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_invalid_trie() {
+        let (_temp_dir, state, _) = create_test_state();
+        let trie_bytes = vec![255u8; 8];
+        let trie_key = Blake2bHash::new(&trie_bytes);
+        write_trie_bytes(&state, trie_key, &trie_bytes);
+        let report = check_state(&state, &[trie_key]);
+        assert_eq!(report.problems.len(), 1);
+        match report.problems[0] {
+            Problem::InvalidTrie { trie_key: key, .. } => assert_eq!(key, trie_key),
+            ref problem => panic!("unexpected problem: {}", problem),
+        }
+    }
+
+    #[test]
+    fn should_report_misplaced_leaf_and_pointer_mismatch() {
+        let (_temp_dir, state, _) = create_test_state();
+        let leaf = write_trie(&state, &test_leaf());
+        let root = write_trie(
+            &state,
+            &Trie::node(&[
+                (200, Pointer::LeafPointer(leaf)),
+                (201, Pointer::NodePointer(leaf)),
+            ]),
+        );
+        let report = check_state(&state, &[root]);
+        assert_eq!(
+            report.problems,
+            vec![Problem::PathMismatch {
+                trie_key: leaf,
+                path: vec![200]
+            }]
+        );
+
+        let other_root = write_trie(&state, &Trie::node(&[(0, Pointer::NodePointer(leaf))]));
+        let report = check_state(&state, &[other_root]);
+        assert_eq!(
+            report.problems,
+            vec![Problem::PointerMismatch {
+                trie_key: leaf,
+                parent: other_root
+            }]
+        );
+    }
+
+    #[test]
+    fn should_report_invalid_protocol_data() {
+        let (_temp_dir, state, root_hash) = create_test_state();
+        {
+            let mut txn = state.environment.create_read_write_txn().unwrap();
+            txn.write(state.protocol_data_store.handle(), &[1, 2, 3], &[4, 5, 6])
+                .unwrap();
+            txn.commit().unwrap();
+        }
+        let report = check_state(&state, &[root_hash]);
+        assert_eq!(report.protocol_data_checked, 2);
+        assert_eq!(report.problems.len(), 1);
+        match report.problems[0] {
+            Problem::InvalidProtocolData { ref key_bytes, .. } => {
+                assert_eq!(key_bytes, &vec![1, 2, 3])
+            }
+            ref problem => panic!("unexpected problem: {}", problem),
+        }
+    }
+}
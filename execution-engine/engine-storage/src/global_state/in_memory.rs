@@ -6,11 +6,14 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::Transform,
 };
-use types::{Key, ProtocolVersion};
+use types::{Key, ProtocolVersion, TrieMerkleProof};
 
 use crate::{
     error::{self, in_memory},
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{
+        commit, diff, get_trie, missing_trie_keys, put_tries, CommitResult, StateIterator,
+        StateProvider, StateReader,
+    },
     protocol_data::ProtocolData,
     protocol_data_store::in_memory::InMemoryProtocolDataStore,
     store::Store,
@@ -21,13 +24,18 @@ use crate::{
     trie::{operations::create_hashed_empty_trie, Trie},
     trie_store::{
         in_memory::InMemoryTrieStore,
-        operations::{self, read, ReadResult, WriteResult},
+        operations::{
+            self, iter_prefix, read, read_with_proof, PutTrieResult, ReadResult,
+            ReadWithProofResult, WriteResult,
+        },
+        STAGED_TRIE_STORE_NAME,
     },
 };
 
 pub struct InMemoryGlobalState {
     pub environment: Arc<InMemoryEnvironment>,
     pub trie_store: Arc<InMemoryTrieStore>,
+    pub staged_trie_store: Arc<InMemoryTrieStore>,
     pub protocol_data_store: Arc<InMemoryProtocolDataStore>,
     pub empty_root_hash: Blake2bHash,
 }
@@ -44,6 +52,10 @@ impl InMemoryGlobalState {
     pub fn empty() -> Result<Self, error::Error> {
         let environment = Arc::new(InMemoryEnvironment::new());
         let trie_store = Arc::new(InMemoryTrieStore::new(&environment, None));
+        let staged_trie_store = Arc::new(InMemoryTrieStore::new(
+            &environment,
+            Some(STAGED_TRIE_STORE_NAME),
+        ));
         let protocol_data_store = Arc::new(InMemoryProtocolDataStore::new(&environment, None));
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
@@ -55,6 +67,7 @@ impl InMemoryGlobalState {
         Ok(InMemoryGlobalState::new(
             environment,
             trie_store,
+            staged_trie_store,
             protocol_data_store,
             root_hash,
         ))
@@ -65,12 +78,14 @@ impl InMemoryGlobalState {
     pub(crate) fn new(
         environment: Arc<InMemoryEnvironment>,
         trie_store: Arc<InMemoryTrieStore>,
+        staged_trie_store: Arc<InMemoryTrieStore>,
         protocol_data_store: Arc<InMemoryProtocolDataStore>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
         InMemoryGlobalState {
             environment,
             trie_store,
+            staged_trie_store,
             protocol_data_store,
             empty_root_hash,
         }
@@ -137,6 +152,55 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let ret = match read_with_proof::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            key,
+        )? {
+            ReadWithProofResult::Found(_, proof) => Some(proof),
+            ReadWithProofResult::NotFound(proof) => Some(proof),
+            ReadWithProofResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        Ok(ret)
+    }
+
+    fn iter_prefix<'a>(
+        &'a self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<StateIterator<'a, Key, StoredValue, Self::Error>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let iter = iter_prefix::<
+            Key,
+            StoredValue,
+            InMemoryReadTransaction,
+            InMemoryTrieStore,
+            Self::Error,
+        >(
+            correlation_id,
+            txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        Ok(Box::new(iter))
+    }
 }
 
 impl StateProvider for InMemoryGlobalState {
@@ -197,11 +261,67 @@ impl StateProvider for InMemoryGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Blake2bHash,
+        new_root: Blake2bHash,
+    ) -> Result<Option<AdditiveMap<Key, Transform>>, Self::Error> {
+        diff::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            old_root,
+            new_root,
+        )
+    }
+
+    fn get_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        get_trie::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            trie_key,
+        )
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        missing_trie_keys::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &self.staged_trie_store,
+            correlation_id,
+            trie_keys,
+        )
+    }
+
+    fn put_tries(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[(Blake2bHash, Vec<u8>)],
+    ) -> Result<Vec<PutTrieResult>, Self::Error> {
+        put_tries::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &self.staged_trie_store,
+            correlation_id,
+            tries,
+        )
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use types::CLValue;
+    use types::{bytesrepr::ToBytes, CLValue};
 
     use super::*;
 
@@ -343,6 +463,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn commit_deletes_keys() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            tmp.insert(test_pairs[0].key, Transform::Delete);
+            // Deleting an absent key has no effect.
+            tmp.insert(Key::Account([3u8; 32]), Transform::Delete);
+            tmp
+        };
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        assert_eq!(
+            None,
+            updated_checkout
+                .read(correlation_id, &test_pairs[0].key)
+                .unwrap()
+        );
+        assert_eq!(
+            Some(test_pairs[1].value.clone()),
+            updated_checkout
+                .read(correlation_id, &test_pairs[1].key)
+                .unwrap()
+        );
+
+        // The result is the same as if the deleted key had never been written.
+        let (_, expected_hash) = InMemoryGlobalState::from_pairs(
+            correlation_id,
+            &[(test_pairs[1].key, test_pairs[1].value.clone())],
+        )
+        .unwrap();
+        assert_eq!(expected_hash, updated_hash);
+    }
+
+    #[test]
+    fn iter_prefix_yields_keys_with_the_given_prefix() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let hash_pair = (
+            Key::Hash([1u8; 32]),
+            StoredValue::CLValue(CLValue::from_t(3_i32).unwrap()),
+        );
+
+        let (state, root_hash) = create_test_state();
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            tmp.insert(hash_pair.0, Transform::Write(hash_pair.1.clone()));
+            tmp
+        };
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        let checkout = state.checkout(updated_hash).unwrap().unwrap();
+
+        // Every key of the same variant shares the serialized tag as its first byte.
+        let account_prefix = &Key::Account([0u8; 32]).to_bytes().unwrap()[..1];
+        let accounts = checkout
+            .iter_prefix(correlation_id, account_prefix)
+            .unwrap()
+            .collect::<Result<Vec<(Key, StoredValue)>, _>>()
+            .unwrap();
+        let expected: Vec<(Key, StoredValue)> = test_pairs
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, value))
+            .collect();
+        assert_eq!(accounts, expected);
+
+        let all = checkout
+            .iter_prefix(correlation_id, &[])
+            .unwrap()
+            .collect::<Result<Vec<(Key, StoredValue)>, _>>()
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert!(all.contains(&hash_pair));
+    }
+
+    #[test]
+    fn committing_a_diff_results_in_the_new_root() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs = create_test_pairs();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+        let effects: AdditiveMap<Key, Transform> = {
+            let mut tmp = AdditiveMap::new();
+            tmp.insert(test_pairs[0].key, Transform::Delete);
+            tmp.insert(
+                test_pairs_updated[1].key,
+                Transform::Write(test_pairs_updated[1].value.clone()),
+            );
+            tmp.insert(
+                test_pairs_updated[2].key,
+                Transform::Write(test_pairs_updated[2].value.clone()),
+            );
+            tmp
+        };
+        let updated_hash = match state
+            .commit(correlation_id, root_hash, effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let diff = state
+            .diff(correlation_id, root_hash, updated_hash)
+            .unwrap()
+            .unwrap();
+        assert_eq!(diff, effects);
+        let diff_hash = match state.commit(correlation_id, root_hash, diff).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        assert_eq!(diff_hash, updated_hash);
+
+        let empty_diff = state
+            .diff(correlation_id, updated_hash, updated_hash)
+            .unwrap()
+            .unwrap();
+        assert!(empty_diff.is_empty());
+
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        assert!(state
+            .diff(correlation_id, root_hash, fake_hash)
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn initial_state_has_the_expected_hash() {
         let correlation_id = CorrelationId::new();
@@ -1,6 +1,6 @@
 use std::{ops::Deref, sync::Arc};
 
-use lmdb;
+use lmdb::{self, DatabaseFlags};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -8,26 +8,39 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::Transform,
 };
-use types::{Key, ProtocolVersion};
+use types::{Key, ProtocolVersion, TrieMerkleProof};
 
 use crate::{
     error,
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{
+        commit, diff, get_trie, missing_trie_keys, put_tries, CommitResult, StateIterator,
+        StateProvider, StateReader,
+    },
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     store::Store,
     transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
     trie::{operations::create_hashed_empty_trie, Trie},
     trie_store::{
+        cache::{CachedTrieStore, TrieCache, DEFAULT_TRIE_CACHE_SIZE},
         lmdb::LmdbTrieStore,
-        operations::{read, ReadResult},
+        operations::{
+            iter_prefix, read, read_with_proof, PutTrieResult, ReadResult, ReadWithProofResult,
+        },
+        STAGED_TRIE_STORE_NAME,
     },
 };
 
 pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
+    pub staged_trie_store: Arc<LmdbTrieStore>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
+    /// The decoded tries shared by this state and its views.  Reads of values, their proofs and
+    /// diffs between states go through the cache, as do commits.  Iterating over a prefix does
+    /// not, as the iterator outlives the call which creates it, and neither does exchanging
+    /// serialized tries with other nodes.
+    pub trie_cache: Arc<TrieCache<Key, StoredValue>>,
     pub empty_root_hash: Blake2bHash,
 }
 
@@ -35,6 +48,7 @@ pub struct LmdbGlobalState {
 pub struct LmdbGlobalStateView {
     pub environment: Arc<LmdbEnvironment>,
     pub store: Arc<LmdbTrieStore>,
+    pub trie_cache: Arc<TrieCache<Key, StoredValue>>,
     pub root_hash: Blake2bHash,
 }
 
@@ -44,6 +58,25 @@ impl LmdbGlobalState {
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+    ) -> Result<Self, error::Error> {
+        Self::empty_with_trie_cache_size(
+            environment,
+            trie_store,
+            protocol_data_store,
+            DEFAULT_TRIE_CACHE_SIZE,
+        )
+    }
+
+    /// Creates an empty state from an existing environment and trie_store, caching at most
+    /// `trie_cache_size` bytes of decoded tries.
+    ///
+    /// The store for staging synced tries is created in the same environment if it does not exist
+    /// yet.
+    pub fn empty_with_trie_cache_size(
+        environment: Arc<LmdbEnvironment>,
+        trie_store: Arc<LmdbTrieStore>,
+        protocol_data_store: Arc<LmdbProtocolDataStore>,
+        trie_cache_size: usize,
     ) -> Result<Self, error::Error> {
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
@@ -52,10 +85,17 @@ impl LmdbGlobalState {
             txn.commit()?;
             root_hash
         };
+        let staged_trie_store = Arc::new(LmdbTrieStore::new(
+            &environment,
+            Some(STAGED_TRIE_STORE_NAME),
+            DatabaseFlags::empty(),
+        )?);
         Ok(LmdbGlobalState::new(
             environment,
             trie_store,
+            staged_trie_store,
             protocol_data_store,
+            Arc::new(TrieCache::new(trie_cache_size)),
             root_hash,
         ))
     }
@@ -65,13 +105,17 @@ impl LmdbGlobalState {
     pub(crate) fn new(
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
+        staged_trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        trie_cache: Arc<TrieCache<Key, StoredValue>>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
         LmdbGlobalState {
             environment,
             trie_store,
+            staged_trie_store,
             protocol_data_store,
+            trie_cache,
             empty_root_hash,
         }
     }
@@ -86,10 +130,11 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
         let txn = self.environment.create_read_txn()?;
-        let ret = match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+        let store = CachedTrieStore::new(self.store.deref(), self.trie_cache.deref());
+        let ret = match read::<Key, StoredValue, lmdb::RoTransaction, _, Self::Error>(
             correlation_id,
             &txn,
-            self.store.deref(),
+            &store,
             &self.root_hash,
             key,
         )? {
@@ -98,8 +143,50 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
             ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
         };
         txn.commit()?;
+        store.log_metrics(correlation_id);
+        Ok(ret)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let store = CachedTrieStore::new(self.store.deref(), self.trie_cache.deref());
+        let ret = match read_with_proof::<Key, StoredValue, lmdb::RoTransaction, _, Self::Error>(
+            correlation_id,
+            &txn,
+            &store,
+            &self.root_hash,
+            key,
+        )? {
+            ReadWithProofResult::Found(_, proof) => Some(proof),
+            ReadWithProofResult::NotFound(proof) => Some(proof),
+            ReadWithProofResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+        };
+        txn.commit()?;
+        store.log_metrics(correlation_id);
         Ok(ret)
     }
+
+    fn iter_prefix<'a>(
+        &'a self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<StateIterator<'a, Key, StoredValue, Self::Error>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        // The iterator borrows the store it reads from for as long as it lives, so it reads the
+        // trie store directly rather than through a `CachedTrieStore` local to this call.
+        let iter = iter_prefix::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+            correlation_id,
+            txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        Ok(Box::new(iter))
+    }
 }
 
 impl StateProvider for LmdbGlobalState {
@@ -113,6 +200,7 @@ impl StateProvider for LmdbGlobalState {
         let maybe_state = maybe_root.map(|_| LmdbGlobalStateView {
             environment: Arc::clone(&self.environment),
             store: Arc::clone(&self.trie_store),
+            trie_cache: Arc::clone(&self.trie_cache),
             root_hash: state_hash,
         });
         txn.commit()?;
@@ -125,13 +213,20 @@ impl StateProvider for LmdbGlobalState {
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
-        let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+        // The commit's transaction is aborted unless it succeeds, so the tries it reads (some of
+        // which it may have written itself) are only cached once it has.
+        let store = CachedTrieStore::deferred(self.trie_store.deref(), self.trie_cache.deref());
+        let commit_result = commit::<LmdbEnvironment, _, _, Self::Error>(
             &self.environment,
-            &self.trie_store,
+            &store,
             correlation_id,
             prestate_hash,
             effects,
         )?;
+        if let CommitResult::Success { .. } = commit_result {
+            store.cache_deferred();
+        }
+        store.log_metrics(correlation_id);
         Ok(commit_result)
     }
 
@@ -159,6 +254,65 @@ impl StateProvider for LmdbGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Blake2bHash,
+        new_root: Blake2bHash,
+    ) -> Result<Option<AdditiveMap<Key, Transform>>, Self::Error> {
+        let store = CachedTrieStore::new(self.trie_store.deref(), self.trie_cache.deref());
+        let diff_result = diff::<LmdbEnvironment, _, Self::Error>(
+            &self.environment,
+            &store,
+            correlation_id,
+            old_root,
+            new_root,
+        )?;
+        store.log_metrics(correlation_id);
+        Ok(diff_result)
+    }
+
+    fn get_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Vec<u8>>, Self::Error> {
+        get_trie::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            correlation_id,
+            trie_key,
+        )
+    }
+
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error> {
+        missing_trie_keys::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &self.staged_trie_store,
+            correlation_id,
+            trie_keys,
+        )
+    }
+
+    fn put_tries(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[(Blake2bHash, Vec<u8>)],
+    ) -> Result<Vec<PutTrieResult>, Self::Error> {
+        put_tries::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            &self.staged_trie_store,
+            correlation_id,
+            tries,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -10,23 +10,33 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::{self, Transform, TypeMismatch},
 };
-use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion, TrieMerkleProof, U512,
+};
 
 use crate::{
     protocol_data::ProtocolData,
-    transaction_source::{Transaction, TransactionSource},
+    transaction_source::{Readable, Transaction, TransactionSource},
     trie::Trie,
     trie_store::{
-        operations::{read, write, ReadResult, WriteResult},
+        operations::{
+            self, delete, read, write, DeleteResult, DiffResult, ReadResult, TrieChange,
+            WriteResult,
+        },
         TrieStore,
     },
 };
 
+pub use crate::trie_store::operations::PutTrieResult;
+
 const GLOBAL_STATE_COMMIT_READS: &str = "global_state_commit_reads";
 const GLOBAL_STATE_COMMIT_WRITES: &str = "global_state_commit_writes";
 const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
 const GLOBAL_STATE_COMMIT_READ_DURATION: &str = "global_state_commit_read_duration";
 const GLOBAL_STATE_COMMIT_WRITE_DURATION: &str = "global_state_commit_write_duration";
+const GLOBAL_STATE_COMMIT_DELETE_DURATION: &str = "global_state_commit_delete_duration";
 const COMMIT: &str = "commit";
 
 /// A reader of state
@@ -36,8 +46,27 @@ pub trait StateReader<K, V> {
 
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
+
+    /// Returns a merkle proof of the state value (or of its absence) at the corresponding key, or
+    /// `None` if this reader is not able to prove its answer for that key.
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleProof>, Self::Error>;
+
+    /// Returns a lazy iterator over the state keys and values whose serialized keys start with
+    /// `prefix`
+    fn iter_prefix<'a>(
+        &'a self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<StateIterator<'a, K, V, Self::Error>, Self::Error>;
 }
 
+/// An iterator over the keys and values held in state
+pub type StateIterator<'a, K, V, E> = Box<dyn Iterator<Item = Result<(K, V), E>> + 'a>;
+
 #[derive(Debug)]
 pub enum CommitResult {
     RootNotFound,
@@ -110,6 +139,155 @@ pub trait StateProvider {
     ) -> Result<Option<ProtocolData>, Self::Error>;
 
     fn empty_root(&self) -> Blake2bHash;
+
+    /// Returns the effects which, committed on top of `old_root`, result in `new_root`, or `None`
+    /// if either root is unknown.
+    fn diff(
+        &self,
+        correlation_id: CorrelationId,
+        old_root: Blake2bHash,
+        new_root: Blake2bHash,
+    ) -> Result<Option<AdditiveMap<Key, Transform>>, Self::Error>;
+
+    /// Returns the serialized trie stored under `trie_key`, if any.
+    fn get_trie(
+        &self,
+        correlation_id: CorrelationId,
+        trie_key: &Blake2bHash,
+    ) -> Result<Option<Vec<u8>>, Self::Error>;
+
+    /// Returns the keys of the tries which are missing among the tries at `trie_keys` and their
+    /// descendants.
+    ///
+    /// Tries whose subtries this finds complete become readable as state roots.
+    fn missing_trie_keys(
+        &self,
+        correlation_id: CorrelationId,
+        trie_keys: Vec<Blake2bHash>,
+    ) -> Result<Vec<Blake2bHash>, Self::Error>;
+
+    /// Stores serialized tries under their keys, rejecting any which does not hash to its key.
+    ///
+    /// A trie whose subtrie is not yet complete is staged apart from the tries which can be
+    /// checked out, until [`missing_trie_keys`](StateProvider::missing_trie_keys) reports nothing
+    /// missing below it.
+    fn put_tries(
+        &self,
+        correlation_id: CorrelationId,
+        tries: &[(Blake2bHash, Vec<u8>)],
+    ) -> Result<Vec<PutTrieResult>, Self::Error>;
+}
+
+pub fn get_trie<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    _correlation_id: CorrelationId,
+    trie_key: &Blake2bHash,
+) -> Result<Option<Vec<u8>>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let ret = txn.read(store.handle(), &trie_key.to_bytes()?)?;
+    txn.commit()?;
+    Ok(ret)
+}
+
+pub fn missing_trie_keys<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    staging_store: &S,
+    correlation_id: CorrelationId,
+    trie_keys: Vec<Blake2bHash>,
+) -> Result<Vec<Blake2bHash>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let ret = operations::missing_trie_keys::<Key, StoredValue, _, _, E>(
+        correlation_id,
+        &mut txn,
+        store,
+        staging_store,
+        trie_keys,
+    )?;
+    txn.commit()?;
+    Ok(ret)
+}
+
+pub fn put_tries<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    staging_store: &S,
+    correlation_id: CorrelationId,
+    tries: &[(Blake2bHash, Vec<u8>)],
+) -> Result<Vec<PutTrieResult>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let ret = tries
+        .iter()
+        .map(|(trie_key, trie_bytes)| {
+            operations::put_trie::<Key, StoredValue, _, _, E>(
+                correlation_id,
+                &mut txn,
+                store,
+                staging_store,
+                trie_key,
+                trie_bytes,
+            )
+        })
+        .collect::<Result<Vec<PutTrieResult>, E>>()?;
+    txn.commit()?;
+    Ok(ret)
+}
+
+pub fn diff<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    correlation_id: CorrelationId,
+    old_root: Blake2bHash,
+    new_root: Blake2bHash,
+) -> Result<Option<AdditiveMap<Key, Transform>>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let diff_result = operations::diff::<Key, StoredValue, _, _, E>(
+        correlation_id,
+        &txn,
+        store,
+        &old_root,
+        &new_root,
+    )?;
+    txn.commit()?;
+
+    let changes = match diff_result {
+        DiffResult::Found(changes) => changes,
+        DiffResult::RootNotFound => return Ok(None),
+    };
+    let effects = changes
+        .into_iter()
+        .map(|change| match change {
+            TrieChange::Added { key, value } => (key, Transform::Write(value)),
+            TrieChange::Changed { key, new_value, .. } => (key, Transform::Write(new_value)),
+            TrieChange::Removed { key, .. } => (key, Transform::Delete),
+        })
+        .collect();
+    Ok(Some(effects))
 }
 
 pub fn commit<'a, R, S, H, E>(
@@ -152,6 +330,34 @@ where
         reads += 1;
 
         let value = match (read_result, transform) {
+            // Deleting an absent key leaves the state unchanged.
+            (ReadResult::NotFound, Transform::Delete) => continue,
+            (ReadResult::Found(_), Transform::Delete) => {
+                let delete_result = delete::<_, StoredValue, _, _, E>(
+                    correlation_id,
+                    &mut txn,
+                    store,
+                    &state_root,
+                    &key,
+                )?;
+
+                log_duration(
+                    correlation_id,
+                    GLOBAL_STATE_COMMIT_DELETE_DURATION,
+                    COMMIT,
+                    start.elapsed(),
+                );
+
+                match delete_result {
+                    DeleteResult::Deleted(root_hash) => {
+                        state_root = root_hash;
+                        writes += 1;
+                    }
+                    _x @ DeleteResult::DoesNotExist => panic!(stringify!(_x)),
+                    _x @ DeleteResult::RootNotFound => panic!(stringify!(_x)),
+                }
+                continue;
+            }
             (ReadResult::NotFound, Transform::Write(new_value)) => new_value,
             (ReadResult::NotFound, _) => {
                 return Ok(CommitResult::KeyNotFound(key));
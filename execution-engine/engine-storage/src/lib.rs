@@ -2,9 +2,11 @@
 
 // modules
 pub mod error;
+pub mod fsck;
 pub mod global_state;
 pub mod protocol_data;
 pub mod protocol_data_store;
+pub mod snapshot;
 pub mod store;
 pub mod transaction_source;
 pub mod trie;
@@ -13,7 +15,7 @@ pub mod trie_store;
 #[cfg(test)]
 use lazy_static::lazy_static;
 
-const MAX_DBS: u32 = 2;
+const MAX_DBS: u32 = 3;
 
 #[cfg(test)]
 lazy_static! {
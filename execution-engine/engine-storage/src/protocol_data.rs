@@ -1,16 +1,28 @@
-use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
+use engine_shared::conv_rates::{ConvRates, CONV_RATES_SERIALIZED_LENGTH};
+use engine_wasm_prep::{
+    host_function_costs::{HostFunctionCosts, HOST_FUNCTION_COSTS_SERIALIZED_LENGTH},
+    storage_costs::{StorageCosts, STORAGE_COSTS_SERIALIZED_LENGTH},
+    wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH},
+};
 use types::{
     bytesrepr::{self, FromBytes, ToBytes},
     AccessRights, URef, UREF_SERIALIZED_LENGTH,
 };
 
-const PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
-    WASM_COSTS_SERIALIZED_LENGTH + UREF_SERIALIZED_LENGTH + UREF_SERIALIZED_LENGTH;
+const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = WASM_COSTS_SERIALIZED_LENGTH
+    + HOST_FUNCTION_COSTS_SERIALIZED_LENGTH
+    + STORAGE_COSTS_SERIALIZED_LENGTH
+    + CONV_RATES_SERIALIZED_LENGTH
+    + UREF_SERIALIZED_LENGTH
+    + UREF_SERIALIZED_LENGTH;
 
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct ProtocolData {
     wasm_costs: WasmCosts,
+    host_function_costs: HostFunctionCosts,
+    storage_costs: StorageCosts,
+    conv_rates: ConvRates,
     mint: URef,
     proof_of_stake: URef,
 }
@@ -23,6 +35,9 @@ impl Default for ProtocolData {
     fn default() -> ProtocolData {
         ProtocolData {
             wasm_costs: WasmCosts::default(),
+            host_function_costs: HostFunctionCosts::default(),
+            storage_costs: StorageCosts::default(),
+            conv_rates: ConvRates::default(),
             mint: URef::new([0; 32], AccessRights::READ),
             proof_of_stake: URef::new([0; 32], AccessRights::READ),
         }
@@ -30,10 +45,21 @@ impl Default for ProtocolData {
 }
 
 impl ProtocolData {
-    /// Creates a new [`ProtocolData`] value from a given [`WasmCosts`] value.
-    pub fn new(wasm_costs: WasmCosts, mint: URef, proof_of_stake: URef) -> Self {
+    /// Creates a new [`ProtocolData`] value from given [`WasmCosts`], [`HostFunctionCosts`],
+    /// [`StorageCosts`] and [`ConvRates`] values.
+    pub fn new(
+        wasm_costs: WasmCosts,
+        host_function_costs: HostFunctionCosts,
+        storage_costs: StorageCosts,
+        conv_rates: ConvRates,
+        mint: URef,
+        proof_of_stake: URef,
+    ) -> Self {
         ProtocolData {
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            conv_rates,
             mint,
             proof_of_stake,
         }
@@ -44,6 +70,21 @@ impl ProtocolData {
         &self.wasm_costs
     }
 
+    /// Gets the [`HostFunctionCosts`] value from a given [`ProtocolData`] value.
+    pub fn host_function_costs(&self) -> &HostFunctionCosts {
+        &self.host_function_costs
+    }
+
+    /// Gets the [`StorageCosts`] value from a given [`ProtocolData`] value.
+    pub fn storage_costs(&self) -> &StorageCosts {
+        &self.storage_costs
+    }
+
+    /// Gets the [`ConvRates`] value from a given [`ProtocolData`] value.
+    pub fn conv_rates(&self) -> &ConvRates {
+        &self.conv_rates
+    }
+
     pub fn mint(&self) -> URef {
         self.mint
     }
@@ -71,18 +112,40 @@ impl ToBytes for ProtocolData {
         ret.append(&mut self.wasm_costs.to_bytes()?);
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
+        ret.append(&mut self.host_function_costs.to_bytes()?);
+        ret.append(&mut self.storage_costs.to_bytes()?);
+        ret.append(&mut self.conv_rates.to_bytes()?);
         Ok(ret)
     }
 }
 
+/// Deserializes a field appended to the encoding of [`ProtocolData`] after it was first persisted,
+/// taking its default if protocol data stored before the field existed has ended.
+fn from_bytes_or_default<T: FromBytes + Default>(
+    bytes: &[u8],
+) -> Result<(T, &[u8]), bytesrepr::Error> {
+    if bytes.is_empty() {
+        Ok((T::default(), bytes))
+    } else {
+        T::from_bytes(bytes)
+    }
+}
+
 impl FromBytes for ProtocolData {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (wasm_costs, rem): (WasmCosts, &[u8]) = FromBytes::from_bytes(bytes)?;
         let (mint_reference, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
         let (proof_of_stake_reference, rem): (URef, &[u8]) = FromBytes::from_bytes(rem)?;
+        // The defaults charge exactly what was charged before these costs were added.
+        let (host_function_costs, rem): (HostFunctionCosts, &[u8]) = from_bytes_or_default(rem)?;
+        let (storage_costs, rem): (StorageCosts, &[u8]) = from_bytes_or_default(rem)?;
+        let (conv_rates, rem): (ConvRates, &[u8]) = from_bytes_or_default(rem)?;
         Ok((
             ProtocolData {
                 wasm_costs,
+                host_function_costs,
+                storage_costs,
+                conv_rates,
                 mint: mint_reference,
                 proof_of_stake: proof_of_stake_reference,
             },
@@ -95,7 +158,11 @@ impl FromBytes for ProtocolData {
 pub(crate) mod gens {
     use proptest::prop_compose;
 
-    use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
+    use engine_shared::conv_rates::gens as conv_rates_gens;
+    use engine_wasm_prep::{
+        host_function_costs::gens as host_function_costs_gens,
+        storage_costs::gens as storage_costs_gens, wasm_costs::gens as wasm_costs_gens,
+    };
     use types::gens;
 
     use super::ProtocolData;
@@ -103,11 +170,17 @@ pub(crate) mod gens {
     prop_compose! {
         pub fn protocol_data_arb()(
             wasm_costs in wasm_costs_gens::wasm_costs_arb(),
+            host_function_costs in host_function_costs_gens::host_function_costs_arb(),
+            storage_costs in storage_costs_gens::storage_costs_arb(),
+            conv_rates in conv_rates_gens::conv_rates_arb(),
             mint in gens::uref_arb(),
             proof_of_stake in gens::uref_arb(),
         ) -> ProtocolData {
             ProtocolData {
                 wasm_costs,
+                host_function_costs,
+                storage_costs,
+                conv_rates,
                 mint,
                 proof_of_stake,
             }
@@ -119,8 +192,12 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
-    use engine_shared::test_utils;
-    use types::{bytesrepr, AccessRights, URef};
+    use engine_shared::{conv_rates::ConvRates, test_utils};
+    use engine_wasm_prep::{host_function_costs::HostFunctionCosts, storage_costs::StorageCosts};
+    use types::{
+        bytesrepr::{self, ToBytes},
+        AccessRights, URef,
+    };
 
     use super::{gens, ProtocolData};
 
@@ -128,27 +205,83 @@ mod tests {
     fn should_serialize_and_deserialize() {
         let mock = {
             let costs = test_utils::wasm_costs_mock();
+            let host_function_costs = test_utils::host_function_costs_mock();
+            let storage_costs = test_utils::storage_costs_mock();
+            let conv_rates = ConvRates::new(5, 20);
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                host_function_costs,
+                storage_costs,
+                conv_rates,
+                mint_reference,
+                proof_of_stake_reference,
+            )
         };
         let free = {
             let costs = test_utils::wasm_costs_free();
+            let host_function_costs = HostFunctionCosts::default();
+            let storage_costs = StorageCosts::default();
+            let conv_rates = ConvRates::default();
             let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
             let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            ProtocolData::new(
+                costs,
+                host_function_costs,
+                storage_costs,
+                conv_rates,
+                mint_reference,
+                proof_of_stake_reference,
+            )
         };
         bytesrepr::test_serialization_roundtrip(&mock);
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_deserialize_protocol_data_stored_before_costs_were_added() {
+        let wasm_costs = test_utils::wasm_costs_mock();
+        let mint_reference = URef::new([0u8; 32], AccessRights::READ_ADD_WRITE);
+        let proof_of_stake_reference = URef::new([1u8; 32], AccessRights::READ_ADD_WRITE);
+        let legacy_bytes = {
+            let mut ret = wasm_costs.to_bytes().unwrap();
+            ret.append(&mut mint_reference.to_bytes().unwrap());
+            ret.append(&mut proof_of_stake_reference.to_bytes().unwrap());
+            ret
+        };
+
+        let protocol_data: ProtocolData =
+            bytesrepr::deserialize(legacy_bytes).expect("should deserialize legacy protocol data");
+
+        let expected = ProtocolData::new(
+            wasm_costs,
+            HostFunctionCosts::default(),
+            StorageCosts::default(),
+            ConvRates::default(),
+            mint_reference,
+            proof_of_stake_reference,
+        );
+        assert_eq!(protocol_data, expected);
+    }
+
     #[test]
     fn should_return_all_system_contracts() {
         let mint_reference = URef::new([197u8; 32], AccessRights::READ_ADD_WRITE);
         let proof_of_stake_reference = URef::new([198u8; 32], AccessRights::READ_ADD_WRITE);
         let protocol_data = {
             let costs = test_utils::wasm_costs_mock();
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            let host_function_costs = test_utils::host_function_costs_mock();
+            let storage_costs = test_utils::storage_costs_mock();
+            let conv_rates = ConvRates::new(5, 20);
+            ProtocolData::new(
+                costs,
+                host_function_costs,
+                storage_costs,
+                conv_rates,
+                mint_reference,
+                proof_of_stake_reference,
+            )
         };
 
         let actual = {
@@ -170,7 +303,17 @@ mod tests {
         let proof_of_stake_reference = URef::new([0u8; 32], AccessRights::READ);
         let protocol_data = {
             let costs = test_utils::wasm_costs_mock();
-            ProtocolData::new(costs, mint_reference, proof_of_stake_reference)
+            let host_function_costs = test_utils::host_function_costs_mock();
+            let storage_costs = test_utils::storage_costs_mock();
+            let conv_rates = ConvRates::new(5, 20);
+            ProtocolData::new(
+                costs,
+                host_function_costs,
+                storage_costs,
+                conv_rates,
+                mint_reference,
+                proof_of_stake_reference,
+            )
         };
 
         let actual = {
@@ -0,0 +1,544 @@
+//! Export and import of global state snapshots.
+//!
+//! A snapshot holds every trie node reachable from a single state root, together with the
+//! [`ProtocolData`] for each protocol version, so that a node can be bootstrapped from it instead
+//! of replaying every block since genesis.
+//!
+//! A snapshot file is laid out as follows, with every integer in little-endian order:
+//!
+//! * the 8 byte [`SNAPSHOT_MAGIC`]
+//! * the `u32` [`SNAPSHOT_FORMAT_VERSION`]
+//! * the 32 byte state root
+//! * a sequence of records, each made of a `u8` tag, a `u32` length and that many bytes of payload:
+//!   * protocol data records hold a serialized `(ProtocolVersion, ProtocolData)` pair
+//!   * trie node records hold a serialized [`Trie`], and are ordered so that every node comes after
+//!     all of its children
+//! * an empty end record
+//! * the 32 byte BLAKE2b checksum of everything preceding it
+
+use std::{
+    collections::HashSet,
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Take, Write},
+};
+
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
+use failure::Fail;
+use lmdb::{self, Cursor};
+
+use engine_shared::{
+    newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH},
+    stored_value::StoredValue,
+};
+use types::{
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion,
+};
+
+use crate::{
+    error,
+    global_state::lmdb::LmdbGlobalState,
+    protocol_data::ProtocolData,
+    store::Store,
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    trie::{Pointer, Trie},
+};
+
+/// The bytes every snapshot starts with.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"CLSNAPSH";
+
+/// The version of the snapshot format written by [`export`].
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+const END_TAG: u8 = 0;
+const PROTOCOL_DATA_TAG: u8 = 1;
+const TRIE_NODE_TAG: u8 = 2;
+
+#[derive(Debug, Fail)]
+pub enum Error {
+    #[fail(display = "{}", _0)]
+    Io(#[fail(cause)] io::Error),
+
+    #[fail(display = "{}", _0)]
+    Storage(#[fail(cause)] error::Error),
+
+    #[fail(display = "Not a global state snapshot")]
+    InvalidMagic,
+
+    #[fail(display = "Unsupported snapshot format version: {}", _0)]
+    UnsupportedFormatVersion(u32),
+
+    #[fail(display = "Invalid snapshot record tag: {}", _0)]
+    InvalidRecordTag(u8),
+
+    #[fail(display = "Snapshot record too long: {} bytes", _0)]
+    InvalidRecordLength(u32),
+
+    #[fail(display = "Snapshot checksum mismatch")]
+    ChecksumMismatch,
+
+    #[fail(display = "Trie node not found: {}", _0)]
+    MissingTrieNode(Blake2bHash),
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+impl From<error::Error> for Error {
+    fn from(error: error::Error) -> Self {
+        Error::Storage(error)
+    }
+}
+
+impl From<lmdb::Error> for Error {
+    fn from(error: lmdb::Error) -> Self {
+        Error::Storage(error.into())
+    }
+}
+
+impl From<bytesrepr::Error> for Error {
+    fn from(error: bytesrepr::Error) -> Self {
+        Error::Storage(error.into())
+    }
+}
+
+/// A description of the contents of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotSummary {
+    pub state_root: Blake2bHash,
+    pub protocol_versions: Vec<ProtocolVersion>,
+    pub trie_nodes: u64,
+}
+
+fn new_hasher() -> VarBlake2b {
+    // Safe to unwrap here because our digest length is constant and valid
+    VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).unwrap()
+}
+
+fn finish(hasher: VarBlake2b) -> [u8; BLAKE2B_DIGEST_LENGTH] {
+    let mut ret = [0u8; BLAKE2B_DIGEST_LENGTH];
+    hasher.variable_result(|hash| ret.clone_from_slice(hash));
+    ret
+}
+
+/// Writes to an inner writer while computing the checksum of everything written.
+struct ChecksumWriter<W> {
+    inner: W,
+    hasher: VarBlake2b,
+}
+
+impl<W: Write> ChecksumWriter<W> {
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.hasher.input(bytes);
+        self.inner.write_all(bytes)?;
+        Ok(())
+    }
+
+    fn write_record(&mut self, tag: u8, payload: &[u8]) -> Result<(), Error> {
+        self.write_all(&[tag])?;
+        self.write_all(&(payload.len() as u32).to_le_bytes())?;
+        self.write_all(payload)
+    }
+}
+
+/// Reads the records of a snapshot whose checksum has already been verified, never reading past
+/// the checksummed data.
+struct RecordReader<R> {
+    inner: Take<R>,
+}
+
+impl<R: Read> RecordReader<R> {
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let mut bytes = [0u8; 4];
+        self.inner.read_exact(&mut bytes)?;
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn read_record(&mut self) -> Result<(u8, Vec<u8>), Error> {
+        let mut tag = [0u8; 1];
+        self.inner.read_exact(&mut tag)?;
+        let length = self.read_u32()?;
+        // Bound the allocation by what is left of the snapshot rather than trusting the length.
+        if u64::from(length) > self.inner.limit() {
+            return Err(Error::InvalidRecordLength(length));
+        }
+        let mut payload = vec![0u8; length as usize];
+        self.inner.read_exact(&mut payload)?;
+        Ok((tag[0], payload))
+    }
+}
+
+/// Checks the checksum at the end of the snapshot against everything preceding it, returning the
+/// length of the checksummed data.
+fn verify_checksum<R: Read + Seek>(reader: &mut R) -> Result<u64, Error> {
+    let total_length = reader.seek(SeekFrom::End(0))?;
+    let data_length = total_length
+        .checked_sub(BLAKE2B_DIGEST_LENGTH as u64)
+        .ok_or(Error::ChecksumMismatch)?;
+    reader.seek(SeekFrom::Start(0))?;
+
+    let mut hasher = new_hasher();
+    let mut data = reader.take(data_length);
+    let mut buffer = [0u8; 8192];
+    loop {
+        let read = data.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.input(&buffer[..read]);
+    }
+    if data.limit() != 0 {
+        return Err(Error::ChecksumMismatch);
+    }
+
+    let mut checksum = [0u8; BLAKE2B_DIGEST_LENGTH];
+    data.into_inner().read_exact(&mut checksum)?;
+    if checksum != finish(hasher) {
+        return Err(Error::ChecksumMismatch);
+    }
+    Ok(data_length)
+}
+
+/// Writes a snapshot of the state at `state_root` and of all the protocol data held in
+/// `global_state` to `writer`.
+pub fn export<W: Write>(
+    global_state: &LmdbGlobalState,
+    state_root: Blake2bHash,
+    writer: W,
+) -> Result<SnapshotSummary, Error> {
+    let mut writer = ChecksumWriter {
+        inner: BufWriter::new(writer),
+        hasher: new_hasher(),
+    };
+    writer.write_all(&SNAPSHOT_MAGIC)?;
+    writer.write_all(&SNAPSHOT_FORMAT_VERSION.to_le_bytes())?;
+    writer.write_all(&state_root.to_bytes()?)?;
+
+    let txn = global_state.environment.create_read_txn()?;
+
+    let mut protocol_versions = Vec::new();
+    {
+        let handle = global_state.protocol_data_store.handle();
+        let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, handle)?;
+        for entry in cursor.iter_start() {
+            let (key_bytes, value_bytes) = entry?;
+            let protocol_version: ProtocolVersion = bytesrepr::deserialize(key_bytes.to_vec())?;
+            let _: ProtocolData = bytesrepr::deserialize(value_bytes.to_vec())?;
+            let mut payload = key_bytes.to_vec();
+            payload.extend_from_slice(value_bytes);
+            writer.write_record(PROTOCOL_DATA_TAG, &payload)?;
+            protocol_versions.push(protocol_version);
+        }
+    }
+
+    // Nodes are written in post-order, so that the children of a node are always written first.
+    let trie_handle =
+        Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(&*global_state.trie_store);
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    let mut pending: Vec<(Pointer, Option<Vec<u8>>)> =
+        vec![(Pointer::NodePointer(state_root), None)];
+    while let Some((pointer, maybe_trie_bytes)) = pending.pop() {
+        let hash = *pointer.hash();
+        if let Some(trie_bytes) = maybe_trie_bytes {
+            // Every child of this node has been written.
+            writer.write_record(TRIE_NODE_TAG, &trie_bytes)?;
+            continue;
+        }
+        if !visited.insert(hash) {
+            continue;
+        }
+        let trie_bytes = txn
+            .read(trie_handle, &hash.to_bytes()?)?
+            .ok_or_else(|| Error::MissingTrieNode(hash))?;
+        if let Pointer::LeafPointer(_) = pointer {
+            writer.write_record(TRIE_NODE_TAG, &trie_bytes)?;
+            continue;
+        }
+        let trie: Trie<Key, StoredValue> = bytesrepr::deserialize(trie_bytes.clone())?;
+        pending.push((pointer, Some(trie_bytes)));
+        match trie {
+            Trie::Leaf { .. } => {}
+            Trie::Node { pointer_block } => pending.extend(
+                pointer_block[..]
+                    .iter()
+                    .flatten()
+                    .rev()
+                    .map(|pointer| (*pointer, None)),
+            ),
+            Trie::Extension { pointer, .. } => pending.push((pointer, None)),
+        }
+    }
+    txn.commit()?;
+
+    writer.write_record(END_TAG, &[])?;
+    let ChecksumWriter { mut inner, hasher } = writer;
+    inner.write_all(&finish(hasher))?;
+    inner.flush()?;
+
+    Ok(SnapshotSummary {
+        state_root,
+        protocol_versions,
+        trie_nodes: visited.len() as u64,
+    })
+}
+
+/// Reads a snapshot from `reader` into `global_state`.
+///
+/// The snapshot is read twice: once to verify its checksum, and only then to import its records.
+/// Nothing is written to `global_state` unless the whole snapshot is valid.  The state root of the
+/// snapshot can be checked out of `global_state` once this returns successfully.
+pub fn import<R: Read + Seek>(
+    global_state: &LmdbGlobalState,
+    reader: R,
+) -> Result<SnapshotSummary, Error> {
+    let mut reader = BufReader::new(reader);
+
+    let mut header = [0u8; 12];
+    reader.read_exact(&mut header)?;
+    if header[..8] != SNAPSHOT_MAGIC {
+        return Err(Error::InvalidMagic);
+    }
+    let mut format_version_bytes = [0u8; 4];
+    format_version_bytes.copy_from_slice(&header[8..]);
+    let format_version = u32::from_le_bytes(format_version_bytes);
+    if format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(Error::UnsupportedFormatVersion(format_version));
+    }
+
+    let data_length = verify_checksum(&mut reader)?;
+    reader.seek(SeekFrom::Start(header.len() as u64))?;
+    let mut reader = RecordReader {
+        inner: reader.take(
+            data_length
+                .checked_sub(header.len() as u64)
+                .ok_or(Error::ChecksumMismatch)?,
+        ),
+    };
+
+    let mut state_root_bytes = [0u8; BLAKE2B_DIGEST_LENGTH];
+    reader.inner.read_exact(&mut state_root_bytes)?;
+    let state_root = Blake2bHash::from(state_root_bytes);
+
+    let mut txn = global_state.environment.create_read_write_txn()?;
+    let trie_handle =
+        Store::<Blake2bHash, Trie<Key, StoredValue>>::handle(&*global_state.trie_store);
+    let mut protocol_versions = Vec::new();
+    let mut imported: HashSet<Blake2bHash> = HashSet::new();
+
+    loop {
+        let (tag, payload) = reader.read_record()?;
+        match tag {
+            END_TAG => break,
+            PROTOCOL_DATA_TAG => {
+                let (protocol_version, protocol_data): (ProtocolVersion, ProtocolData) =
+                    bytesrepr::deserialize(payload)?;
+                global_state.protocol_data_store.put(
+                    &mut txn,
+                    &protocol_version,
+                    &protocol_data,
+                )?;
+                protocol_versions.push(protocol_version);
+            }
+            TRIE_NODE_TAG => {
+                let hash = Blake2bHash::new(&payload);
+                let trie: Trie<Key, StoredValue> = bytesrepr::deserialize(payload.clone())?;
+                let children: Vec<Pointer> = match trie {
+                    Trie::Leaf { .. } => Vec::new(),
+                    Trie::Node { pointer_block } => {
+                        pointer_block[..].iter().flatten().cloned().collect()
+                    }
+                    Trie::Extension { pointer, .. } => vec![pointer],
+                };
+                if let Some(missing) = children
+                    .iter()
+                    .map(Pointer::hash)
+                    .find(|child| !imported.contains(*child))
+                {
+                    return Err(Error::MissingTrieNode(*missing));
+                }
+                txn.write(trie_handle, &hash.to_bytes()?, &payload)?;
+                imported.insert(hash);
+            }
+            tag => return Err(Error::InvalidRecordTag(tag)),
+        }
+    }
+
+    if !imported.contains(&state_root) {
+        return Err(Error::MissingTrieNode(state_root));
+    }
+    txn.commit()?;
+
+    Ok(SnapshotSummary {
+        state_root,
+        protocol_versions,
+        trie_nodes: imported.len() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Cursor, sync::Arc};
+
+    use lmdb::DatabaseFlags;
+    use tempfile::{tempdir, TempDir};
+
+    use engine_shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform};
+    use types::{AccessRights, CLValue, URef};
+
+    use super::*;
+    use crate::{
+        global_state::{CommitResult, StateProvider, StateReader},
+        protocol_data_store::lmdb::LmdbProtocolDataStore,
+        transaction_source::lmdb::LmdbEnvironment,
+        trie_store::lmdb::LmdbTrieStore,
+        TEST_MAP_SIZE,
+    };
+
+    fn create_empty_state() -> (TempDir, LmdbGlobalState) {
+        let temp_dir = tempdir().unwrap();
+        let environment =
+            Arc::new(LmdbEnvironment::new(&temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap());
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let protocol_data_store = Arc::new(
+            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        (temp_dir, state)
+    }
+
+    fn create_test_pairs() -> Vec<(Key, StoredValue)> {
+        (0..20u8)
+            .map(|i| {
+                (
+                    Key::Account([i; 32]),
+                    StoredValue::CLValue(CLValue::from_t(i32::from(i)).unwrap()),
+                )
+            })
+            .collect()
+    }
+
+    fn create_test_protocol_data() -> ProtocolData {
+        ProtocolData::new(
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            URef::new([1; 32], AccessRights::READ),
+            URef::new([2; 32], AccessRights::READ),
+        )
+    }
+
+    fn create_test_state() -> (TempDir, LmdbGlobalState, Blake2bHash) {
+        let correlation_id = CorrelationId::new();
+        let (temp_dir, state) = create_empty_state();
+        let effects: AdditiveMap<Key, Transform> = create_test_pairs()
+            .into_iter()
+            .map(|(key, value)| (key, Transform::Write(value)))
+            .collect();
+        let root_hash = match state
+            .commit(correlation_id, state.empty_root(), effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        state
+            .put_protocol_data(ProtocolVersion::V1_0_0, &create_test_protocol_data())
+            .unwrap();
+        (temp_dir, state, root_hash)
+    }
+
+    #[test]
+    fn should_import_exported_snapshot() {
+        let correlation_id = CorrelationId::new();
+        let (_source_dir, source, root_hash) = create_test_state();
+        let mut snapshot = Vec::new();
+        let exported = export(&source, root_hash, &mut snapshot).unwrap();
+        assert_eq!(exported.state_root, root_hash);
+        assert_eq!(exported.protocol_versions, vec![ProtocolVersion::V1_0_0]);
+
+        let (_target_dir, target) = create_empty_state();
+        let imported = import(&target, Cursor::new(snapshot)).unwrap();
+        assert_eq!(imported, exported);
+
+        let checkout = target.checkout(root_hash).unwrap().unwrap();
+        for (key, value) in create_test_pairs() {
+            assert_eq!(checkout.read(correlation_id, &key).unwrap(), Some(value));
+        }
+        assert_eq!(
+            target.get_protocol_data(ProtocolVersion::V1_0_0).unwrap(),
+            Some(create_test_protocol_data())
+        );
+    }
+
+    #[test]
+    fn should_reject_corrupted_snapshot() {
+        let (_source_dir, source, root_hash) = create_test_state();
+        let mut snapshot = Vec::new();
+        export(&source, root_hash, &mut snapshot).unwrap();
+        // Flip a bit in the checksum.
+        let last = snapshot.len() - 1;
+        snapshot[last] ^= 1;
+
+        let (_target_dir, target) = create_empty_state();
+        match import(&target, Cursor::new(snapshot)) {
+            Err(Error::ChecksumMismatch) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+        // Nothing was imported.
+        assert!(target.checkout(root_hash).unwrap().is_none());
+        assert!(target
+            .get_protocol_data(ProtocolVersion::V1_0_0)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn should_reject_truncated_snapshot() {
+        let (_source_dir, source, root_hash) = create_test_state();
+        let mut snapshot = Vec::new();
+        export(&source, root_hash, &mut snapshot).unwrap();
+        snapshot.truncate(snapshot.len() / 2);
+
+        let (_target_dir, target) = create_empty_state();
+        match import(&target, Cursor::new(snapshot)) {
+            Err(Error::ChecksumMismatch) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+        assert!(target.checkout(root_hash).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_reject_record_longer_than_snapshot() {
+        let mut hasher = new_hasher();
+        let mut snapshot = Vec::new();
+        snapshot.extend_from_slice(&SNAPSHOT_MAGIC);
+        snapshot.extend_from_slice(&SNAPSHOT_FORMAT_VERSION.to_le_bytes());
+        snapshot.extend_from_slice(&[0u8; BLAKE2B_DIGEST_LENGTH]);
+        snapshot.push(TRIE_NODE_TAG);
+        snapshot.extend_from_slice(&u32::max_value().to_le_bytes());
+        hasher.input(&snapshot);
+        snapshot.extend_from_slice(&finish(hasher));
+
+        let (_target_dir, target) = create_empty_state();
+        match import(&target, Cursor::new(snapshot)) {
+            Err(Error::InvalidRecordLength(length)) => assert_eq!(length, u32::max_value()),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_format() {
+        let (_target_dir, target) = create_empty_state();
+        match import(&target, Cursor::new(b"not a snapshot")) {
+            Err(Error::InvalidMagic) => (),
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::path::PathBuf;
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb::{
+    self, Database, Environment, EnvironmentFlags, RoTransaction, RwTransaction, WriteFlags,
+};
 
 use crate::{
     error,
@@ -53,6 +55,13 @@ impl<'a> Writable for RwTransaction<'a> {
         self.put(handle, &key, &value, WriteFlags::empty())
             .map_err(Into::into)
     }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        match self.del(handle, &key, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
 }
 
 /// The environment for an LMDB-backed trie store.
@@ -74,6 +83,19 @@ impl LmdbEnvironment {
         Ok(LmdbEnvironment { path, env })
     }
 
+    /// Opens an existing environment which can only be read from.
+    ///
+    /// Creating a read-write transaction in the returned environment fails.
+    pub fn open_read_only(path: &PathBuf, map_size: usize) -> Result<Self, error::Error> {
+        let env = Environment::new()
+            .set_flags(EnvironmentFlags::READ_ONLY)
+            .set_max_dbs(MAX_DBS)
+            .set_map_size(map_size)
+            .open(path)?;
+        let path = path.to_owned();
+        Ok(LmdbEnvironment { path, env })
+    }
+
     pub fn path(&self) -> &PathBuf {
         &self.path
     }
@@ -31,6 +31,9 @@ pub trait Readable: Transaction {
 pub trait Writable: Transaction {
     /// Inserts a key-value pair into a given [`Transaction::Handle`].
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error>;
+
+    /// Removes a key and its value from a given [`Transaction::Handle`], if present.
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error>;
 }
 
 /// A source of transactions e.g. values that implement [`Readable`]
@@ -0,0 +1,303 @@
+//! A cache of decoded [`Trie`] values bounded by their size in bytes, shared between threads, and
+//! a [`TrieStore`] which reads through it.
+//!
+//! Tries are stored at their hashes, so a cached trie can never become stale with respect to the
+//! store it was read from.  Only tries read from the underlying store are cached, and a
+//! [`CachedTrieStore`] reading within a transaction which may yet be aborted, such as one writing
+//! tries, must be made with [`CachedTrieStore::deferred`]: the tries it reads could have been
+//! written earlier in that same transaction, so they are only cached once
+//! [`cache_deferred`](CachedTrieStore::cache_deferred) is called after the transaction commits.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
+    mem,
+};
+
+use parking_lot::Mutex;
+
+use engine_shared::{
+    logging::{log_metric, GAUGE},
+    newtypes::{Blake2bHash, CorrelationId},
+};
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+use crate::{
+    store::Store,
+    transaction_source::{Readable, Writable},
+    trie::{PointerBlock, Trie},
+    trie_store::TrieStore,
+};
+
+/// The default size in bytes of the tries held by a [`TrieCache`] (32 MiB).
+pub const DEFAULT_TRIE_CACHE_SIZE: usize = 32 * 1024 * 1024;
+
+const TRIE_CACHE_HITS: &str = "trie_cache_hits";
+const TRIE_CACHE_MISSES: &str = "trie_cache_misses";
+const GET: &str = "get";
+
+/// Estimates the memory held by a decoded trie, given the length of its serialized form.
+///
+/// A node always holds a whole pointer block, however few of its pointers are set, while the size
+/// of a leaf or an extension is dominated by its serialized contents.
+pub(crate) fn trie_size<K, V>(trie: &Trie<K, V>, serialized_length: usize) -> usize {
+    let contents_size = match trie {
+        Trie::Node { .. } => mem::size_of::<PointerBlock>(),
+        Trie::Leaf { .. } | Trie::Extension { .. } => serialized_length,
+    };
+    mem::size_of::<Trie<K, V>>() + contents_size
+}
+
+struct Entries<K, V> {
+    tries: HashMap<Blake2bHash, (u64, usize, Trie<K, V>)>,
+    recency: BTreeMap<u64, Blake2bHash>,
+    tick: u64,
+    size: usize,
+}
+
+impl<K, V> Entries<K, V> {
+    fn touch(&mut self, trie_key: Blake2bHash, last_used: u64) -> u64 {
+        self.recency.remove(&last_used);
+        self.tick += 1;
+        self.recency.insert(self.tick, trie_key);
+        self.tick
+    }
+}
+
+/// A thread-safe, least recently used cache of decoded [`Trie`] values keyed by their hashes.
+///
+/// The capacity of the cache is the estimated size in bytes of the tries it holds, so that large
+/// tries cannot make it grow without bound.  A trie larger than the whole capacity is not cached,
+/// and a cache with a capacity of zero holds nothing.
+pub struct TrieCache<K, V> {
+    capacity: usize,
+    entries: Mutex<Entries<K, V>>,
+}
+
+impl<K: Clone, V: Clone> TrieCache<K, V> {
+    /// Creates an empty cache holding at most `capacity` bytes of tries.
+    pub fn new(capacity: usize) -> Self {
+        let entries = Entries {
+            tries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            size: 0,
+        };
+        TrieCache {
+            capacity,
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Returns the maximum size in bytes of the tries held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the estimated size in bytes of the tries currently held by the cache.
+    pub fn size(&self) -> usize {
+        self.entries.lock().size
+    }
+
+    /// Returns the number of tries currently held by the cache.
+    pub fn len(&self) -> usize {
+        self.entries.lock().tries.len()
+    }
+
+    /// Returns `true` if the cache holds no tries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a copy of the trie at `trie_key`, marking it as the most recently used.
+    pub fn get(&self, trie_key: &Blake2bHash) -> Option<Trie<K, V>> {
+        let mut entries = self.entries.lock();
+        let last_used = entries.tries.get(trie_key)?.0;
+        let tick = entries.touch(*trie_key, last_used);
+        let (last_used, _, trie) = entries.tries.get_mut(trie_key)?;
+        *last_used = tick;
+        Some(trie.clone())
+    }
+
+    /// Inserts `trie` at `trie_key`, evicting the least recently used tries until it fits.
+    ///
+    /// `serialized_length` is the length of the serialized form of `trie`, from which its size is
+    /// estimated.
+    pub fn insert(&self, trie_key: Blake2bHash, trie: Trie<K, V>, serialized_length: usize) {
+        let size = trie_size(&trie, serialized_length);
+        if size > self.capacity {
+            return;
+        }
+        let mut entries = self.entries.lock();
+        if let Some(&(last_used, _, _)) = entries.tries.get(&trie_key) {
+            // Tries are stored at their hashes, so the cached trie is the same as `trie`.
+            let tick = entries.touch(trie_key, last_used);
+            if let Some(entry) = entries.tries.get_mut(&trie_key) {
+                entry.0 = tick;
+            }
+            return;
+        }
+        while entries.size + size > self.capacity {
+            let least_recently_used = entries
+                .recency
+                .iter()
+                .next()
+                .map(|(last_used, trie_key)| (*last_used, *trie_key));
+            let (last_used, evicted_key) = match least_recently_used {
+                Some(entry) => entry,
+                None => break,
+            };
+            entries.recency.remove(&last_used);
+            if let Some((_, evicted_size, _)) = entries.tries.remove(&evicted_key) {
+                entries.size -= evicted_size;
+            }
+        }
+        entries.tick += 1;
+        let tick = entries.tick;
+        entries.recency.insert(tick, trie_key);
+        entries.tries.insert(trie_key, (tick, size, trie));
+        entries.size += size;
+    }
+}
+
+/// A [`TrieStore`] which serves reads from a [`TrieCache`] before falling back to another store.
+///
+/// It counts its own cache hits and misses, which can be logged with
+/// [`log_metrics`](CachedTrieStore::log_metrics).
+pub struct CachedTrieStore<'a, S, K, V> {
+    store: &'a S,
+    cache: &'a TrieCache<K, V>,
+    // The tries read from the underlying store and not yet cached, if caching them is deferred.
+    deferred: Option<RefCell<Vec<(Blake2bHash, Trie<K, V>, usize)>>>,
+    hits: Cell<u64>,
+    misses: Cell<u64>,
+}
+
+impl<'a, S, K, V> CachedTrieStore<'a, S, K, V> {
+    /// Creates a store which caches the tries it reads from `store` straight away.  It must only
+    /// be used with read-only transactions.
+    pub fn new(store: &'a S, cache: &'a TrieCache<K, V>) -> Self {
+        CachedTrieStore {
+            store,
+            cache,
+            deferred: None,
+            hits: Cell::new(0),
+            misses: Cell::new(0),
+        }
+    }
+
+    /// Creates a store which holds on to the tries it reads from `store` until
+    /// [`cache_deferred`](CachedTrieStore::cache_deferred) is called.
+    pub fn deferred(store: &'a S, cache: &'a TrieCache<K, V>) -> Self {
+        CachedTrieStore {
+            deferred: Some(RefCell::new(Vec::new())),
+            ..CachedTrieStore::new(store, cache)
+        }
+    }
+
+    /// Returns the number of reads served by the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.get()
+    }
+
+    /// Returns the number of reads which fell back to the underlying store.
+    pub fn misses(&self) -> u64 {
+        self.misses.get()
+    }
+
+    /// Caches the tries read so far by a store made with
+    /// [`deferred`](CachedTrieStore::deferred).  This must only be called once the transaction
+    /// they were read in has been committed.
+    pub fn cache_deferred(&self)
+    where
+        K: Clone,
+        V: Clone,
+    {
+        if let Some(deferred) = &self.deferred {
+            for (trie_key, trie, serialized_length) in deferred.borrow_mut().drain(..) {
+                self.cache.insert(trie_key, trie, serialized_length);
+            }
+        }
+    }
+
+    /// Logs the number of cache hits and misses.
+    pub fn log_metrics(&self, correlation_id: CorrelationId) {
+        log_metric(
+            correlation_id,
+            TRIE_CACHE_HITS,
+            GET,
+            GAUGE,
+            self.hits.get() as f64,
+        );
+        log_metric(
+            correlation_id,
+            TRIE_CACHE_MISSES,
+            GET,
+            GAUGE,
+            self.misses.get() as f64,
+        );
+    }
+}
+
+impl<'a, S, K, V> Store<Blake2bHash, Trie<K, V>> for CachedTrieStore<'a, S, K, V>
+where
+    S: TrieStore<K, V>,
+    K: Clone,
+    V: Clone,
+{
+    type Error = S::Error;
+
+    type Handle = S::Handle;
+
+    fn handle(&self) -> Self::Handle {
+        self.store.handle()
+    }
+
+    fn get<T>(&self, txn: &T, key: &Blake2bHash) -> Result<Option<Trie<K, V>>, Self::Error>
+    where
+        T: Readable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: FromBytes,
+        Self::Error: From<T::Error>,
+    {
+        if let Some(trie) = self.cache.get(key) {
+            self.hits.set(self.hits.get() + 1);
+            return Ok(Some(trie));
+        }
+        self.misses.set(self.misses.get() + 1);
+        match txn.read(self.store.handle(), &key.to_bytes()?)? {
+            None => Ok(None),
+            Some(trie_bytes) => {
+                let serialized_length = trie_bytes.len();
+                let trie: Trie<K, V> = bytesrepr::deserialize(trie_bytes)?;
+                match &self.deferred {
+                    Some(deferred) => {
+                        deferred
+                            .borrow_mut()
+                            .push((*key, trie.clone(), serialized_length))
+                    }
+                    None => self.cache.insert(*key, trie.clone(), serialized_length),
+                }
+                Ok(Some(trie))
+            }
+        }
+    }
+
+    fn put<T>(&self, txn: &mut T, key: &Blake2bHash, value: &Trie<K, V>) -> Result<(), Self::Error>
+    where
+        T: Writable<Handle = Self::Handle>,
+        Blake2bHash: ToBytes,
+        Trie<K, V>: ToBytes,
+        Self::Error: From<T::Error>,
+    {
+        self.store.put(txn, key, value)
+    }
+}
+
+impl<'a, S, K, V> TrieStore<K, V> for CachedTrieStore<'a, S, K, V>
+where
+    S: TrieStore<K, V>,
+    K: Clone,
+    V: Clone,
+{
+}
@@ -2,9 +2,11 @@
 //!
 //! See the [in_memory](in_memory/index.html#usage) and
 //! [lmdb](lmdb/index.html#usage) modules for usage examples.
+pub mod cache;
 pub mod in_memory;
 pub mod lmdb;
 pub(crate) mod operations;
+pub mod pruning;
 #[cfg(test)]
 mod tests;
 
@@ -14,5 +16,8 @@ use crate::{store::Store, trie::Trie};
 
 const NAME: &str = "TRIE_STORE";
 
+/// The name of the store holding synced tries whose subtries are not yet complete.
+pub const STAGED_TRIE_STORE_NAME: &str = "staged";
+
 /// An entity which persists [`Trie`] values at their hashes.
 pub trait TrieStore<K, V>: Store<Blake2bHash, Trie<K, V>> {}
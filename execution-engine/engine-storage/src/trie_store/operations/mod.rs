@@ -1,13 +1,20 @@
 #[cfg(test)]
 mod tests;
 
-use std::time::Instant;
+use std::{
+    collections::{BTreeMap, HashSet},
+    marker::PhantomData,
+    time::Instant,
+};
 
 use engine_shared::{
     logging::{log_duration, log_metric, GAUGE},
     newtypes::{Blake2bHash, CorrelationId},
 };
-use types::bytesrepr::{self, FromBytes, ToBytes};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    TrieMerkleProof,
+};
 
 use crate::{
     transaction_source::{Readable, Writable},
@@ -17,15 +24,24 @@ use crate::{
 
 const TRIE_STORE_READ_DURATION: &str = "trie_store_read_duration";
 const TRIE_STORE_READ_GETS: &str = "trie_store_read_gets";
+const TRIE_STORE_READ_WITH_PROOF_DURATION: &str = "trie_store_read_with_proof_duration";
 const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+const TRIE_STORE_DELETE_DURATION: &str = "trie_store_delete_duration";
+const TRIE_STORE_DIFF_DURATION: &str = "trie_store_diff_duration";
+const TRIE_STORE_DIFF_GETS: &str = "trie_store_diff_gets";
+const TRIE_STORE_MISSING_TRIE_KEYS_DURATION: &str = "trie_store_missing_trie_keys_duration";
+const TRIE_STORE_MISSING_TRIE_KEYS_GETS: &str = "trie_store_missing_trie_keys_gets";
 const READ: &str = "read";
 const GET: &str = "get";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
 const PUT: &str = "put";
+const DELETE: &str = "delete";
+const DIFF: &str = "diff";
+const MISSING_TRIE_KEYS: &str = "missing_trie_keys";
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ReadResult<V> {
@@ -198,6 +214,109 @@ where
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum ReadWithProofResult<V> {
+    Found(V, TrieMerkleProof),
+    NotFound(TrieMerkleProof),
+    RootNotFound,
+}
+
+/// Returns a value from the corresponding key at a given root in a given store, along with a
+/// [`TrieMerkleProof`] of its presence under that root.  If the key is absent, the returned proof
+/// shows its absence instead.
+pub fn read_with_proof<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ReadWithProofResult<V>, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let path: Vec<u8> = key.to_bytes()?;
+
+    let mut depth: usize = 0;
+    let mut current: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ReadWithProofResult::RootNotFound),
+    };
+
+    let start = Instant::now();
+    let mut proof_nodes: Vec<Vec<u8>> = Vec::new();
+
+    loop {
+        proof_nodes.push(current.to_bytes()?);
+        let maybe_pointer: Option<Pointer> = match current {
+            Trie::Leaf {
+                key: leaf_key,
+                value: leaf_value,
+            } => {
+                let proof = TrieMerkleProof::new(proof_nodes);
+                let result = if *key == leaf_key {
+                    ReadWithProofResult::Found(leaf_value, proof)
+                } else {
+                    // Keys may not match in the case of a compressed path from
+                    // a Node directly to a Leaf
+                    ReadWithProofResult::NotFound(proof)
+                };
+                log_duration(
+                    correlation_id,
+                    TRIE_STORE_READ_WITH_PROOF_DURATION,
+                    READ,
+                    start.elapsed(),
+                );
+                return Ok(result);
+            }
+            Trie::Node { pointer_block } => {
+                let index: usize = {
+                    assert!(depth < path.len(), "depth must be < {}", path.len());
+                    path[depth].into()
+                };
+                depth += 1;
+                assert!(index < trie::RADIX, "key length must be < {}", trie::RADIX);
+                pointer_block[index]
+            }
+            Trie::Extension { affix, pointer } => {
+                let sub_path = &path[depth..depth + affix.len()];
+                if sub_path == affix.as_slice() {
+                    depth += affix.len();
+                    Some(pointer)
+                } else {
+                    None
+                }
+            }
+        };
+
+        match maybe_pointer {
+            Some(pointer) => match store.get(txn, pointer.hash())? {
+                Some(next) => current = next,
+                None => panic!(
+                    "No trie value at key: {:?} (reading from key: {:?})",
+                    pointer.hash(),
+                    key
+                ),
+            },
+            None => {
+                log_duration(
+                    correlation_id,
+                    TRIE_STORE_READ_WITH_PROOF_DURATION,
+                    READ,
+                    start.elapsed(),
+                );
+                return Ok(ReadWithProofResult::NotFound(TrieMerkleProof::new(
+                    proof_nodes,
+                )));
+            }
+        }
+    }
+}
+
 struct TrieScan<K, V> {
     tip: Trie<K, V>,
     parents: Parents<K, V>,
@@ -730,78 +849,704 @@ where
     }
 }
 
-/// Returns the keys at a given root hash.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteResult {
+    Deleted(Blake2bHash),
+    DoesNotExist,
+    RootNotFound,
+}
+
+/// Removes the leaf at the given key from the trie at a given root in a given store, returning the
+/// hash of the new root.
+///
+/// The parent node of the removed leaf is collapsed if it is left with a single child, so that the
+/// resulting trie is the same as if the key had never been written.
+pub fn delete<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+
+    let current_root = match store.get(txn, root)? {
+        None => return Ok(DeleteResult::RootNotFound),
+        Some(current_root) => current_root,
+    };
+    let path: Vec<u8> = key.to_bytes()?;
+    let TrieScan { tip, mut parents } =
+        scan::<K, V, T, S, E>(correlation_id, txn, store, &path, &current_root)?;
+
+    match tip {
+        Trie::Leaf {
+            key: ref leaf_key, ..
+        } if key == leaf_key => (),
+        _ => {
+            log_duration(
+                correlation_id,
+                TRIE_STORE_DELETE_DURATION,
+                DELETE,
+                start.elapsed(),
+            );
+            return Ok(DeleteResult::DoesNotExist);
+        }
+    }
+
+    let new_elements: Vec<(Blake2bHash, Trie<K, V>)> = match parents.pop() {
+        // The leaf is the root itself, so the trie becomes empty.
+        None => {
+            let (empty_root_hash, empty_root) = trie::operations::create_hashed_empty_trie()?;
+            vec![(empty_root_hash, empty_root)]
+        }
+        Some((leaf_index, Trie::Node { mut pointer_block })) => {
+            pointer_block[leaf_index.into()] = None;
+            let remaining: Vec<(usize, Pointer)> = pointer_block[..]
+                .iter()
+                .enumerate()
+                .filter_map(|(index, maybe_pointer)| maybe_pointer.map(|pointer| (index, pointer)))
+                .collect();
+            match remaining.as_slice() {
+                // The root node is never collapsed, and neither is a node which still has siblings
+                // to distinguish between.
+                _ if parents.is_empty() || remaining.len() > 1 => {
+                    rehash(Trie::Node { pointer_block }, parents)?
+                }
+                [(_, Pointer::LeafPointer(leaf_hash))] => {
+                    // The remaining leaf takes the place of its parent node, along with any
+                    // extension above that node.
+                    if let Some((_, Trie::Extension { .. })) = parents.last() {
+                        parents.pop();
+                    }
+                    let leaf = match store.get(txn, leaf_hash)? {
+                        Some(leaf) => leaf,
+                        None => panic!(
+                            "No trie value at key: {:?} (deleting key: {:?})",
+                            leaf_hash, key
+                        ),
+                    };
+                    rehash(leaf, parents)?
+                }
+                [(index, Pointer::NodePointer(child_hash))] => {
+                    // The node is replaced by an extension, which absorbs the affixes of any
+                    // extensions directly above and below it.
+                    let mut affix = match parents.last() {
+                        Some((_, Trie::Extension { .. })) => match parents.pop() {
+                            Some((_, Trie::Extension { affix, .. })) => affix,
+                            _ => unreachable!(),
+                        },
+                        _ => Vec::new(),
+                    };
+                    affix.push(*index as u8);
+                    let pointer = match store.get(txn, child_hash)? {
+                        Some(Trie::Extension {
+                            affix: child_affix,
+                            pointer,
+                        }) => {
+                            affix.extend(child_affix);
+                            pointer
+                        }
+                        Some(_) => Pointer::NodePointer(*child_hash),
+                        None => panic!(
+                            "No trie value at key: {:?} (deleting key: {:?})",
+                            child_hash, key
+                        ),
+                    };
+                    rehash(Trie::extension(affix, pointer), parents)?
+                }
+                [] => panic!("a non-root node should have at least two children"),
+                _ => unreachable!(),
+            }
+        }
+        Some(_) => panic!("A leaf should have a node for its parent"),
+    };
+
+    let mut root_hash = root.to_owned();
+    for (hash, element) in new_elements.iter() {
+        store.put(txn, hash, element)?;
+        root_hash = *hash;
+    }
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DELETE_DURATION,
+        DELETE,
+        start.elapsed(),
+    );
+    Ok(DeleteResult::Deleted(root_hash))
+}
+
+/// A lazy iterator over the leaves of a trie whose serialized keys start with a given prefix.
+///
+/// Leaves are yielded in ascending order of their serialized keys.  Only the branches of the trie
+/// which can hold such keys are read from the store, and only as the iterator advances.
+pub struct PrefixIterator<'a, K, V, T, S, E> {
+    txn: T,
+    store: &'a S,
+    prefix: Vec<u8>,
+    /// Hashes of the tries still to visit, paired with the path leading to them.
+    pending: Vec<(Blake2bHash, Vec<u8>)>,
+    _phantom: PhantomData<(K, V, E)>,
+}
+
+impl<'a, K, V, T, S, E> Iterator for PrefixIterator<'a, K, V, T, S, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    type Item = Result<(K, V), E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((hash, mut path)) = self.pending.pop() {
+            let trie: Trie<K, V> = match self.store.get(&self.txn, &hash) {
+                Ok(Some(trie)) => trie,
+                Ok(None) => panic!(
+                    "No trie value at key: {:?} (iterating over prefix: {:?})",
+                    hash, self.prefix
+                ),
+                Err(error) => return Some(Err(error.into())),
+            };
+            match trie {
+                Trie::Leaf { key, value } => {
+                    // The path to a leaf may be shorter than its key, so the key itself must be
+                    // checked against the prefix.
+                    match key.to_bytes() {
+                        Ok(key_bytes) if key_bytes.starts_with(&self.prefix) => {
+                            return Some(Ok((key, value)))
+                        }
+                        Ok(_) => (),
+                        Err(error) => return Some(Err(error.into())),
+                    }
+                }
+                Trie::Node { pointer_block } => {
+                    // Children are pushed in reverse, so that they are visited in ascending order.
+                    for index in (0..RADIX).rev() {
+                        if let Some(pointer) = pointer_block[index] {
+                            let mut child_path = path.clone();
+                            child_path.push(index as u8);
+                            if is_compatible_with_prefix(&child_path, &self.prefix) {
+                                self.pending.push((*pointer.hash(), child_path));
+                            }
+                        }
+                    }
+                }
+                Trie::Extension { affix, pointer } => {
+                    path.extend(affix);
+                    if is_compatible_with_prefix(&path, &self.prefix) {
+                        self.pending.push((*pointer.hash(), path));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Returns `true` if a trie reached by `path` may contain keys starting with `prefix`.
+fn is_compatible_with_prefix(path: &[u8], prefix: &[u8]) -> bool {
+    let length = path.len().min(prefix.len());
+    path[..length] == prefix[..length]
+}
+
+/// Returns a lazy iterator over the keys and values at a given root hash whose serialized keys
+/// start with `prefix`.
+///
+/// The iterator takes ownership of `txn`, and reads from it as it advances.
 ///
 /// Notes:
-/// * This should be rewritten as an Iterator in the future.
 /// * The root doesn't necessarily need to be the apex of the trie. It can be the "root" of a
 ///   sub-trie.
-#[allow(dead_code)]
-pub fn keys<K, V, T, S, E>(
+pub fn iter_prefix<'a, K, V, T, S, E>(
     _correlation_id: CorrelationId,
-    txn: &T,
-    store: &S,
+    txn: T,
+    store: &'a S,
     root: &Blake2bHash,
-) -> Result<Vec<K>, E>
+    prefix: &[u8],
+) -> PrefixIterator<'a, K, V, T, S, E>
 where
-    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
-    V: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
     T: Readable<Handle = S::Handle>,
     S: TrieStore<K, V>,
     S::Error: From<T::Error>,
     E: From<S::Error> + From<types::bytesrepr::Error>,
 {
-    let mut ret = Vec::new();
+    PrefixIterator {
+        txn,
+        store,
+        prefix: prefix.to_vec(),
+        pending: vec![(*root, Vec::new())],
+        _phantom: PhantomData,
+    }
+}
+
+/// Returns a lazy iterator over the keys at a given root hash.
+///
+/// See [`iter_prefix`].
+pub fn keys<'a, K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: T,
+    store: &'a S,
+    root: &Blake2bHash,
+) -> impl Iterator<Item = Result<K, E>> + 'a
+where
+    K: ToBytes + FromBytes + 'a,
+    V: ToBytes + FromBytes + 'a,
+    T: Readable<Handle = S::Handle> + 'a,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error> + 'a,
+{
+    iter_prefix::<K, V, T, S, E>(correlation_id, txn, store, root, &[])
+        .map(|result| result.map(|(key, _)| key))
+}
+
+/// A difference between the leaves of two tries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrieChange<K, V> {
+    /// The key is only present in the new trie.
+    Added { key: K, value: V },
+    /// The key is present in both tries, with different values.
+    Changed { key: K, old_value: V, new_value: V },
+    /// The key is only present in the old trie.
+    Removed { key: K, value: V },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DiffResult<K, V> {
+    Found(Vec<TrieChange<K, V>>),
+    RootNotFound,
+}
+
+/// A subtrie of one of the tries being compared by [`diff`].
+///
+/// Extensions are compared one byte of their affix at a time, so a subtrie is not necessarily
+/// stored as such.
+enum Subtrie<K, V> {
+    Empty,
+    Stored(Pointer),
+    Loaded(Trie<K, V>),
+}
+
+impl<K, V> Subtrie<K, V> {
+    fn from_pointer(maybe_pointer: Option<Pointer>) -> Self {
+        match maybe_pointer {
+            Some(pointer) => Subtrie::Stored(pointer),
+            None => Subtrie::Empty,
+        }
+    }
 
-    #[allow(clippy::type_complexity)]
-    let mut visited: Vec<(Trie<K, V>, Option<usize>, Vec<u8>)> = {
-        let root = match store.get(txn, root)? {
-            None => return Ok(ret),
-            Some(current_root) => current_root,
+    /// Splits an extension into the first byte of its affix and the subtrie following that byte.
+    fn split_extension(mut affix: Vec<u8>, pointer: Pointer) -> (u8, Self) {
+        let first = affix.remove(0);
+        let rest = if affix.is_empty() {
+            Subtrie::Stored(pointer)
+        } else {
+            Subtrie::Loaded(Trie::Extension { affix, pointer })
         };
-        vec![(root, None, vec![])]
-    };
+        (first, rest)
+    }
+}
+
+struct Differ<'a, K, V, T, S, E> {
+    txn: &'a T,
+    store: &'a S,
+    gets: u32,
+    _phantom: PhantomData<(K, V, E)>,
+}
+
+impl<'a, K, V, T, S, E> Differ<'a, K, V, T, S, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes + PartialEq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    fn load(&mut self, subtrie: Subtrie<K, V>) -> Result<Subtrie<K, V>, E> {
+        match subtrie {
+            Subtrie::Stored(pointer) => {
+                self.gets += 1;
+                match self.store.get(self.txn, pointer.hash())? {
+                    Some(trie) => Ok(Subtrie::Loaded(trie)),
+                    None => panic!("No trie value at key: {:?}", pointer.hash()),
+                }
+            }
+            subtrie => Ok(subtrie),
+        }
+    }
 
-    while let Some((trie, maybe_index, mut path)) = visited.pop() {
-        let mut maybe_next_trie: Option<Trie<K, V>> = None;
+    fn diff(&mut self, old: Subtrie<K, V>, new: Subtrie<K, V>) -> Result<Vec<TrieChange<K, V>>, E> {
+        let mut changes = Vec::new();
+        let mut pending = vec![(old, new)];
 
-        match trie {
-            Trie::Leaf { key, .. } => {
-                debug_assert!({
-                    let key_bytes = key.to_bytes()?;
-                    key_bytes.starts_with(&path)
-                });
-                ret.push(key);
+        while let Some((old, new)) = pending.pop() {
+            match (&old, &new) {
+                (Subtrie::Empty, Subtrie::Empty) => continue,
+                // Identical subtries hold identical leaves.
+                (Subtrie::Stored(old_pointer), Subtrie::Stored(new_pointer))
+                    if old_pointer.hash() == new_pointer.hash() =>
+                {
+                    continue
+                }
+                _ => (),
             }
-            Trie::Node { ref pointer_block } => {
-                let mut index: usize = maybe_index.unwrap_or_default();
-                while index < RADIX {
-                    if let Some(ref pointer) = pointer_block[index] {
-                        maybe_next_trie = store.get(txn, pointer.hash())?;
-                        debug_assert!(maybe_next_trie.is_some());
-                        visited.push((trie, Some(index + 1), path.clone()));
-                        path.push(index as u8);
-                        break;
+            match (self.load(old)?, self.load(new)?) {
+                (
+                    Subtrie::Loaded(Trie::Node {
+                        pointer_block: old_block,
+                    }),
+                    Subtrie::Loaded(Trie::Node {
+                        pointer_block: new_block,
+                    }),
+                ) => {
+                    for index in (0..RADIX).rev() {
+                        pending.push((
+                            Subtrie::from_pointer(old_block[index]),
+                            Subtrie::from_pointer(new_block[index]),
+                        ));
                     }
-                    index += 1;
                 }
-            }
-            Trie::Extension { affix, pointer } => {
-                maybe_next_trie = store.get(txn, pointer.hash())?;
-                debug_assert!({
-                    match &maybe_next_trie {
-                        Some(Trie::Node { .. }) => true,
-                        _ => false,
+                (
+                    Subtrie::Loaded(Trie::Extension { affix, pointer }),
+                    Subtrie::Loaded(Trie::Node { pointer_block }),
+                ) => {
+                    let (first, mut rest) = Subtrie::split_extension(affix, pointer);
+                    for index in (0..RADIX).rev() {
+                        let old = if index == first as usize {
+                            std::mem::replace(&mut rest, Subtrie::Empty)
+                        } else {
+                            Subtrie::Empty
+                        };
+                        pending.push((old, Subtrie::from_pointer(pointer_block[index])));
                     }
-                });
-                path.extend(affix);
+                }
+                (
+                    Subtrie::Loaded(Trie::Node { pointer_block }),
+                    Subtrie::Loaded(Trie::Extension { affix, pointer }),
+                ) => {
+                    let (first, mut rest) = Subtrie::split_extension(affix, pointer);
+                    for index in (0..RADIX).rev() {
+                        let new = if index == first as usize {
+                            std::mem::replace(&mut rest, Subtrie::Empty)
+                        } else {
+                            Subtrie::Empty
+                        };
+                        pending.push((Subtrie::from_pointer(pointer_block[index]), new));
+                    }
+                }
+                (
+                    Subtrie::Loaded(Trie::Extension {
+                        affix: old_affix,
+                        pointer: old_pointer,
+                    }),
+                    Subtrie::Loaded(Trie::Extension {
+                        affix: new_affix,
+                        pointer: new_pointer,
+                    }),
+                ) if old_affix[0] == new_affix[0] => {
+                    let (_, old_rest) = Subtrie::split_extension(old_affix, old_pointer);
+                    let (_, new_rest) = Subtrie::split_extension(new_affix, new_pointer);
+                    pending.push((old_rest, new_rest));
+                }
+                // Any other pair of subtries, which includes a leaf or an empty subtrie on either
+                // side, is compared leaf by leaf.
+                (old, new) => {
+                    let old_leaves = self.leaves(old)?;
+                    let new_leaves = self.leaves(new)?;
+                    Self::diff_leaves(old_leaves, new_leaves, &mut changes)?;
+                }
             }
         }
 
-        if let Some(next_trie) = maybe_next_trie {
-            visited.push((next_trie, None, path));
+        Ok(changes)
+    }
+
+    fn leaves(&mut self, subtrie: Subtrie<K, V>) -> Result<Vec<(K, V)>, E> {
+        let mut ret = Vec::new();
+        let mut pending = vec![subtrie];
+        while let Some(subtrie) = pending.pop() {
+            match self.load(subtrie)? {
+                Subtrie::Loaded(Trie::Leaf { key, value }) => ret.push((key, value)),
+                Subtrie::Loaded(Trie::Node { pointer_block }) => pending.extend(
+                    pointer_block[..]
+                        .iter()
+                        .flatten()
+                        .map(|pointer| Subtrie::Stored(*pointer)),
+                ),
+                Subtrie::Loaded(Trie::Extension { pointer, .. }) => {
+                    pending.push(Subtrie::Stored(pointer))
+                }
+                Subtrie::Empty | Subtrie::Stored(_) => (),
+            }
         }
+        Ok(ret)
     }
 
-    Ok(ret)
+    fn diff_leaves(
+        old_leaves: Vec<(K, V)>,
+        new_leaves: Vec<(K, V)>,
+        changes: &mut Vec<TrieChange<K, V>>,
+    ) -> Result<(), E> {
+        let mut old_leaves = old_leaves
+            .into_iter()
+            .map(|(key, value)| Ok((key.to_bytes()?, (key, value))))
+            .collect::<Result<BTreeMap<Vec<u8>, (K, V)>, bytesrepr::Error>>()?;
+        for (key, new_value) in new_leaves {
+            match old_leaves.remove(&key.to_bytes()?) {
+                Some((_, old_value)) if old_value == new_value => (),
+                Some((_, old_value)) => changes.push(TrieChange::Changed {
+                    key,
+                    old_value,
+                    new_value,
+                }),
+                None => changes.push(TrieChange::Added {
+                    key,
+                    value: new_value,
+                }),
+            }
+        }
+        for (_, (key, value)) in old_leaves {
+            changes.push(TrieChange::Removed { key, value });
+        }
+        Ok(())
+    }
+}
+
+/// Returns the differences between the leaves of the tries at `old_root` and `new_root`.
+///
+/// Subtries which are identical in both tries are skipped without being read.
+pub fn diff<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    old_root: &Blake2bHash,
+    new_root: &Blake2bHash,
+) -> Result<DiffResult<K, V>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes + PartialEq,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut differ: Differ<K, V, T, S, E> = Differ {
+        txn,
+        store,
+        gets: 0,
+        _phantom: PhantomData,
+    };
+
+    let old: Trie<K, V> = match store.get(txn, old_root)? {
+        Some(root) => root,
+        None => return Ok(DiffResult::RootNotFound),
+    };
+    let new: Trie<K, V> = match store.get(txn, new_root)? {
+        Some(root) => root,
+        None => return Ok(DiffResult::RootNotFound),
+    };
+
+    let changes = if old_root == new_root {
+        Vec::new()
+    } else {
+        differ.diff(Subtrie::Loaded(old), Subtrie::Loaded(new))?
+    };
+
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DIFF_DURATION,
+        DIFF,
+        start.elapsed(),
+    );
+    log_metric(
+        correlation_id,
+        TRIE_STORE_DIFF_GETS,
+        GET,
+        GAUGE,
+        f64::from(differ.gets),
+    );
+
+    Ok(DiffResult::Found(changes))
+}
+
+/// Returns the keys of the children of `trie`.
+fn child_keys<K, V>(trie: &Trie<K, V>) -> Vec<Blake2bHash> {
+    match trie {
+        Trie::Leaf { .. } => Vec::new(),
+        Trie::Node { pointer_block } => pointer_block[..]
+            .iter()
+            .flatten()
+            .map(|pointer| *pointer.hash())
+            .collect(),
+        Trie::Extension { pointer, .. } => vec![*pointer.hash()],
+    }
+}
+
+/// Returns `true` if every child of `trie` is in `store`.
+///
+/// The tries in `store` have complete subtries, so this means that the subtrie of `trie` is
+/// complete too.
+fn children_stored<K, V, T, S, E>(txn: &T, store: &S, trie: &Trie<K, V>) -> Result<bool, E>
+where
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    for child_key in child_keys(trie) {
+        let maybe_bytes = txn
+            .read(store.handle(), &child_key.to_bytes()?)
+            .map_err(S::Error::from)?;
+        if maybe_bytes.is_none() {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Returns the keys of the tries which are missing among the tries at `trie_keys` and their
+/// descendants.
+///
+/// Tries received with [`put_trie`] are held in `staging_store` until their subtries are
+/// complete, so that every trie in `store` can be read all the way down to its leaves.  The
+/// walk stops at tries already in `store`, and only descends through staged tries, so it visits
+/// the incomplete part of the trie rather than the whole of it.  Along the way, staged tries
+/// whose children are all in `store` are moved there, starting from the deepest ones.
+///
+/// The result is the frontier of a partially downloaded trie; it is empty once the tries at
+/// `trie_keys` and all their descendants are in `store`.
+pub fn missing_trie_keys<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    staging_store: &S,
+    trie_keys: Vec<Blake2bHash>,
+) -> Result<Vec<Blake2bHash>, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+    let mut get_counter: i32 = 0;
+    let mut missing = Vec::new();
+    let mut visited: HashSet<Blake2bHash> = HashSet::new();
+    // A staged trie is pushed a second time with its value once its children have been pushed, so
+    // that it is popped again only after all of them have been visited.
+    let mut pending: Vec<(Blake2bHash, Option<Trie<K, V>>)> = trie_keys
+        .into_iter()
+        .map(|trie_key| (trie_key, None))
+        .collect();
+
+    while let Some((trie_key, maybe_staged)) = pending.pop() {
+        if let Some(trie) = maybe_staged {
+            if children_stored::<K, V, T, S, E>(txn, store, &trie)? {
+                store.put(txn, &trie_key, &trie)?;
+                staging_store.delete(txn, &trie_key)?;
+            }
+            continue;
+        }
+        if !visited.insert(trie_key) {
+            continue;
+        }
+        get_counter += 1;
+        if txn
+            .read(store.handle(), &trie_key.to_bytes()?)
+            .map_err(S::Error::from)?
+            .is_some()
+        {
+            continue;
+        }
+        let maybe_trie: Option<Trie<K, V>> = staging_store.get(txn, &trie_key)?;
+        get_counter += 1;
+        match maybe_trie {
+            None => missing.push(trie_key),
+            Some(trie) => {
+                let children = child_keys(&trie);
+                pending.push((trie_key, Some(trie)));
+                pending.extend(children.into_iter().map(|child_key| (child_key, None)));
+            }
+        }
+    }
+
+    log_duration(
+        correlation_id,
+        TRIE_STORE_MISSING_TRIE_KEYS_DURATION,
+        MISSING_TRIE_KEYS,
+        start.elapsed(),
+    );
+    log_metric(
+        correlation_id,
+        TRIE_STORE_MISSING_TRIE_KEYS_GETS,
+        GET,
+        GAUGE,
+        f64::from(get_counter),
+    );
+
+    Ok(missing)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PutTrieResult {
+    Stored(Blake2bHash),
+    /// The given bytes do not hash to the given trie key.
+    HashMismatch {
+        expected: Blake2bHash,
+        actual: Blake2bHash,
+    },
+}
+
+/// Stores a serialized trie received from elsewhere under `trie_key`, after checking that it
+/// hashes to `trie_key` and that it is a valid trie.
+///
+/// The trie goes straight into `store` if all of its children are already there.  Otherwise it
+/// is held in `staging_store` until [`missing_trie_keys`] finds its subtrie complete, so a root
+/// can only be read from `store` once all of its descendants have been received.
+pub fn put_trie<K, V, T, S, E>(
+    _correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    staging_store: &S,
+    trie_key: &Blake2bHash,
+    trie_bytes: &[u8],
+) -> Result<PutTrieResult, E>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let actual = Blake2bHash::new(trie_bytes);
+    if actual != *trie_key {
+        return Ok(PutTrieResult::HashMismatch {
+            expected: *trie_key,
+            actual,
+        });
+    }
+    let trie: Trie<K, V> = bytesrepr::deserialize(trie_bytes.to_vec())?;
+    if children_stored::<K, V, T, S, E>(txn, store, &trie)? {
+        store.put(txn, trie_key, &trie)?;
+    } else {
+        staging_store.put(txn, trie_key, &trie)?;
+    }
+    Ok(PutTrieResult::Stored(actual))
 }
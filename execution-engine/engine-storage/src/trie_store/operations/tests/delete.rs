@@ -0,0 +1,154 @@
+//! This module contains tests for [`delete`].
+//!
+//! A trie has a single canonical form for a given set of leaves, so deleting a leaf must result in
+//! the same root as writing every other leaf to an empty trie.
+
+use super::*;
+use crate::{
+    error::{self, in_memory},
+    trie_store::operations::{delete, DeleteResult},
+};
+
+fn delete_leaf<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root_hash: &Blake2bHash,
+    key: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let result = delete::<K, V, _, _, E>(correlation_id, &mut txn, store, root_hash, key)?;
+    txn.commit()?;
+    Ok(result)
+}
+
+fn deletes_had_expected_results<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+    leaves: &[Trie<K, V>],
+) -> Result<(), E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let full_root_hash =
+        root_of::<_, _, _, _, E>(correlation_id, environment, store, empty_root_hash, leaves)?;
+
+    for (index, leaf) in leaves.iter().enumerate() {
+        let key = leaf.key().expect("leaves should contain only leaves");
+        let remaining: Vec<Trie<K, V>> = leaves
+            .iter()
+            .enumerate()
+            .filter(|(other_index, _)| *other_index != index)
+            .map(|(_, leaf)| leaf.clone())
+            .collect();
+        let expected = root_of::<_, _, _, _, E>(
+            correlation_id,
+            environment,
+            store,
+            empty_root_hash,
+            &remaining,
+        )?;
+
+        let result =
+            delete_leaf::<K, V, _, _, E>(correlation_id, environment, store, &full_root_hash, key)?;
+        assert_eq!(result, DeleteResult::Deleted(expected));
+
+        // Deleting the same key again has no effect.
+        let result =
+            delete_leaf::<K, V, _, _, E>(correlation_id, environment, store, &expected, key)?;
+        assert_eq!(result, DeleteResult::DoesNotExist);
+    }
+
+    // Deleting every leaf in turn results in an empty trie.
+    let mut root_hash = full_root_hash;
+    for leaf in leaves.iter().rev() {
+        let key = leaf.key().expect("leaves should contain only leaves");
+        match delete_leaf::<K, V, _, _, E>(correlation_id, environment, store, &root_hash, key)? {
+            DeleteResult::Deleted(new_root_hash) => root_hash = new_root_hash,
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+    assert_eq!(root_hash, *empty_root_hash);
+
+    Ok(())
+}
+
+const LEAF_SETS: [[TestTrie; TEST_LEAVES_LENGTH]; 3] = [
+    TEST_LEAVES,
+    TEST_LEAVES_NON_COLLIDING,
+    TEST_LEAVES_ADJACENTS,
+];
+
+#[test]
+fn lmdb_deletes_from_n_leaf_trie_had_expected_results() {
+    for leaves in LEAF_SETS.iter() {
+        for num_leaves in 1..=TEST_LEAVES_LENGTH {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+
+            deletes_had_expected_results::<_, _, _, _, error::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+                &leaves[..num_leaves],
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[test]
+fn in_memory_deletes_from_n_leaf_trie_had_expected_results() {
+    for leaves in LEAF_SETS.iter() {
+        for num_leaves in 1..=TEST_LEAVES_LENGTH {
+            let correlation_id = CorrelationId::new();
+            let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+            let context = InMemoryTestContext::new(&tries).unwrap();
+
+            deletes_had_expected_results::<_, _, _, _, in_memory::Error>(
+                correlation_id,
+                &context.environment,
+                &context.store,
+                &root_hash,
+                &leaves[..num_leaves],
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[test]
+fn in_memory_delete_from_missing_root_returns_root_not_found() {
+    let correlation_id = CorrelationId::new();
+    let (_, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let missing_root_hash = Blake2bHash::new(&[1u8; 32]);
+    let key = TEST_LEAVES[0].key().unwrap();
+
+    let result = delete_leaf::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &missing_root_hash,
+        key,
+    )
+    .unwrap();
+    assert_eq!(result, DeleteResult::RootNotFound);
+}
@@ -0,0 +1,215 @@
+//! This module contains tests for [`diff`].
+
+use std::collections::BTreeMap;
+
+use super::*;
+use crate::{
+    error::{self, in_memory},
+    trie_store::operations::{diff, DiffResult, TrieChange},
+};
+
+const LEAF_SETS: [[TestTrie; TEST_LEAVES_LENGTH]; 3] = [
+    TEST_LEAVES,
+    TEST_LEAVES_NON_COLLIDING,
+    TEST_LEAVES_ADJACENTS,
+];
+
+fn sorted(mut changes: Vec<TrieChange<TestKey, TestValue>>) -> Vec<TrieChange<TestKey, TestValue>> {
+    changes.sort_by_key(|change| match change {
+        TrieChange::Added { key, .. }
+        | TrieChange::Changed { key, .. }
+        | TrieChange::Removed { key, .. } => *key,
+    });
+    changes
+}
+
+fn expected_changes(old: &[TestTrie], new: &[TestTrie]) -> Vec<TrieChange<TestKey, TestValue>> {
+    let old: BTreeMap<TestKey, TestValue> = old
+        .iter()
+        .filter_map(|leaf| match leaf {
+            Trie::Leaf { key, value } => Some((*key, *value)),
+            _ => None,
+        })
+        .collect();
+    let new: BTreeMap<TestKey, TestValue> = new
+        .iter()
+        .filter_map(|leaf| match leaf {
+            Trie::Leaf { key, value } => Some((*key, *value)),
+            _ => None,
+        })
+        .collect();
+
+    let mut ret = Vec::new();
+    for (key, new_value) in new.iter() {
+        match old.get(key) {
+            Some(old_value) if old_value == new_value => (),
+            Some(old_value) => ret.push(TrieChange::Changed {
+                key: *key,
+                old_value: *old_value,
+                new_value: *new_value,
+            }),
+            None => ret.push(TrieChange::Added {
+                key: *key,
+                value: *new_value,
+            }),
+        }
+    }
+    for (key, value) in old.iter() {
+        if !new.contains_key(key) {
+            ret.push(TrieChange::Removed {
+                key: *key,
+                value: *value,
+            });
+        }
+    }
+    sorted(ret)
+}
+
+fn diff_had_expected_results<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+    old_leaves: &[TestTrie],
+    new_leaves: &[TestTrie],
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let old_root_hash = root_of::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        old_leaves,
+    )?;
+    let new_root_hash = root_of::<_, _, _, _, E>(
+        correlation_id,
+        environment,
+        store,
+        empty_root_hash,
+        new_leaves,
+    )?;
+
+    let txn = environment.create_read_txn()?;
+    let result = diff::<TestKey, TestValue, _, _, E>(
+        correlation_id,
+        &txn,
+        store,
+        &old_root_hash,
+        &new_root_hash,
+    )?;
+    txn.commit()?;
+
+    match result {
+        DiffResult::Found(changes) => {
+            assert_eq!(sorted(changes), expected_changes(old_leaves, new_leaves))
+        }
+        DiffResult::RootNotFound => panic!("diff given an invalid root"),
+    }
+    Ok(())
+}
+
+fn diffs_had_expected_results<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    for leaves in LEAF_SETS.iter() {
+        for old_length in 0..=TEST_LEAVES_LENGTH {
+            for new_length in 0..=TEST_LEAVES_LENGTH {
+                // Growing and shrinking tries.
+                diff_had_expected_results::<_, _, E>(
+                    correlation_id,
+                    environment,
+                    store,
+                    empty_root_hash,
+                    &leaves[..old_length],
+                    &leaves[..new_length],
+                )?;
+                // Tries with few or no leaves in common.
+                diff_had_expected_results::<_, _, E>(
+                    correlation_id,
+                    environment,
+                    store,
+                    empty_root_hash,
+                    &leaves[..old_length],
+                    &leaves[new_length..],
+                )?;
+            }
+        }
+    }
+
+    // Tries with the same keys and different values.
+    for length in 0..=TEST_LEAVES_LENGTH {
+        diff_had_expected_results::<_, _, E>(
+            correlation_id,
+            environment,
+            store,
+            empty_root_hash,
+            &TEST_LEAVES,
+            &TEST_LEAVES_UPDATED[..length],
+        )?;
+    }
+    Ok(())
+}
+
+#[test]
+fn lmdb_diffs_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = LmdbTestContext::new(&tries).unwrap();
+
+    diffs_had_expected_results::<_, _, error::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_diffs_had_expected_results() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    diffs_had_expected_results::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &root_hash,
+    )
+    .unwrap();
+}
+
+#[test]
+fn in_memory_diff_with_missing_root_returns_root_not_found() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let missing_root_hash = Blake2bHash::new(&[1u8; 32]);
+
+    let txn = context.environment.create_read_txn().unwrap();
+    let result = diff::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &root_hash,
+        &missing_root_hash,
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    assert_eq!(result, DiffResult::RootNotFound);
+}
@@ -3,7 +3,7 @@ mod partial_tries {
 
     use crate::{
         error::{self, in_memory},
-        transaction_source::{Transaction, TransactionSource},
+        transaction_source::TransactionSource,
         trie::Trie,
         trie_store::operations::{
             self,
@@ -36,12 +36,12 @@ mod partial_tries {
                 let txn = context.environment.create_read_txn().unwrap();
                 let mut tmp = operations::keys::<TestKey, TestValue, _, _, error::Error>(
                     correlation_id,
-                    &txn,
+                    txn,
                     &context.store,
                     &root_hash,
                 )
+                .collect::<Result<Vec<TestKey>, _>>()
                 .unwrap();
-                txn.commit().unwrap();
                 tmp.sort();
                 tmp
             };
@@ -71,12 +71,12 @@ mod partial_tries {
                 let txn = context.environment.create_read_txn().unwrap();
                 let mut tmp = operations::keys::<TestKey, TestValue, _, _, in_memory::Error>(
                     correlation_id,
-                    &txn,
+                    txn,
                     &context.store,
                     &root_hash,
                 )
+                .collect::<Result<Vec<TestKey>, _>>()
                 .unwrap();
-                txn.commit().unwrap();
                 tmp.sort();
                 tmp
             };
@@ -90,7 +90,7 @@ mod full_tries {
 
     use crate::{
         error::in_memory,
-        transaction_source::{Transaction, TransactionSource},
+        transaction_source::TransactionSource,
         trie::Trie,
         trie_store::operations::{
             self,
@@ -129,12 +129,12 @@ mod full_tries {
                     let txn = context.environment.create_read_txn().unwrap();
                     let mut tmp = operations::keys::<TestKey, TestValue, _, _, in_memory::Error>(
                         correlation_id,
-                        &txn,
+                        txn,
                         &context.store,
                         &state,
                     )
+                    .collect::<Result<Vec<TestKey>, _>>()
                     .unwrap();
-                    txn.commit().unwrap();
                     tmp.sort();
                     tmp
                 };
@@ -143,3 +143,86 @@ mod full_tries {
         }
     }
 }
+
+mod prefixes {
+    use engine_shared::newtypes::CorrelationId;
+    use types::bytesrepr::ToBytes;
+
+    use crate::{
+        error::{self, in_memory},
+        transaction_source::TransactionSource,
+        trie::Trie,
+        trie_store::operations::{
+            self,
+            tests::{
+                InMemoryTestContext, LmdbTestContext, TestKey, TestValue, TEST_LEAVES,
+                TEST_TRIE_GENERATORS,
+            },
+        },
+    };
+
+    const PREFIXES: [&[u8]; 7] = [
+        &[],
+        &[0],
+        &[0, 0, 0],
+        &[0, 0, 0, 0, 0],
+        &[0, 1],
+        &[1],
+        &[0, 0, 0, 2, 0, 0, 0],
+    ];
+
+    fn expected_pairs(prefix: &[u8]) -> Vec<(TestKey, TestValue)> {
+        let mut ret: Vec<(TestKey, TestValue)> = TEST_LEAVES
+            .iter()
+            .filter_map(|leaf| match leaf {
+                Trie::Leaf { key, value } => Some((*key, *value)),
+                _ => None,
+            })
+            .filter(|(key, _)| key.to_bytes().unwrap().starts_with(prefix))
+            .collect();
+        ret.sort_by_key(|(key, _)| key.to_bytes().unwrap());
+        ret
+    }
+
+    #[test]
+    fn lmdb_iter_prefix_yields_matching_pairs_in_order() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS.len() - 1]().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+
+        for prefix in PREFIXES.iter() {
+            let txn = context.environment.create_read_txn().unwrap();
+            let actual = operations::iter_prefix::<TestKey, TestValue, _, _, error::Error>(
+                correlation_id,
+                txn,
+                &context.store,
+                &root_hash,
+                prefix,
+            )
+            .collect::<Result<Vec<(TestKey, TestValue)>, _>>()
+            .unwrap();
+            assert_eq!(actual, expected_pairs(prefix));
+        }
+    }
+
+    #[test]
+    fn in_memory_iter_prefix_yields_matching_pairs_in_order() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS.len() - 1]().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+
+        for prefix in PREFIXES.iter() {
+            let txn = context.environment.create_read_txn().unwrap();
+            let actual = operations::iter_prefix::<TestKey, TestValue, _, _, in_memory::Error>(
+                correlation_id,
+                txn,
+                &context.store,
+                &root_hash,
+                prefix,
+            )
+            .collect::<Result<Vec<(TestKey, TestValue)>, _>>()
+            .unwrap();
+            assert_eq!(actual, expected_pairs(prefix));
+        }
+    }
+}
@@ -1,8 +1,12 @@
+mod delete;
+mod diff;
 mod ee_699;
 mod keys;
+mod proofs;
 mod proptests;
 mod read;
 mod scan;
+mod sync;
 mod write;
 
 use std::{collections::HashMap, convert};
@@ -589,7 +593,7 @@ where
 
 fn check_keys<K, V, T, S, E>(
     correlation_id: CorrelationId,
-    txn: &T,
+    txn: T,
     store: &S,
     root: &Blake2bHash,
     leaves: &[Trie<K, V>],
@@ -612,7 +616,8 @@ where
         tmp
     };
     let actual = {
-        let mut tmp = operations::keys::<_, _, _, _, E>(correlation_id, txn, store, root)?;
+        let mut tmp = operations::keys::<_, _, _, _, E>(correlation_id, txn, store, root)
+            .collect::<Result<Vec<K>, E>>()?;
         tmp.sort();
         tmp
     };
@@ -649,15 +654,15 @@ where
             .all(|b| !b)
     );
 
+    // The keys iterator takes ownership of the transaction.
     assert!(check_keys::<_, _, _, _, E>(
         correlation_id,
-        &txn,
+        txn,
         store,
         root,
         present
     )?);
 
-    txn.commit()?;
     Ok(())
 }
 
@@ -703,6 +708,35 @@ where
     Ok(results)
 }
 
+/// Writes `leaves` to the trie at `empty_root_hash` and returns the resulting root hash.
+fn root_of<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    empty_root_hash: &Blake2bHash,
+    leaves: &[Trie<K, V>],
+) -> Result<Blake2bHash, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let results =
+        write_leaves::<_, _, _, _, E>(correlation_id, environment, store, empty_root_hash, leaves)?;
+    let root_hash = results
+        .into_iter()
+        .filter_map(|result| match result {
+            WriteResult::Written(root_hash) => Some(root_hash),
+            _ => None,
+        })
+        .last()
+        .unwrap_or(*empty_root_hash);
+    Ok(root_hash)
+}
+
 fn check_pairs<'a, K, V, R, S, E>(
     correlation_id: CorrelationId,
     environment: &'a R,
@@ -718,8 +752,8 @@ where
     S::Error: From<R::Error>,
     E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
 {
-    let txn = environment.create_read_txn()?;
     for (index, root_hash) in root_hashes.iter().enumerate() {
+        let txn = environment.create_read_txn()?;
         for (key, value) in &pairs[..=index] {
             let result = read::<_, _, _, _, E>(correlation_id, &txn, store, root_hash, key)?;
             if ReadResult::Found(*value) != result {
@@ -736,8 +770,8 @@ where
             tmp
         };
         let actual = {
-            let mut tmp =
-                operations::keys::<_, _, _, _, E>(correlation_id, &txn, store, root_hash)?;
+            let mut tmp = operations::keys::<_, _, _, _, E>(correlation_id, txn, store, root_hash)
+                .collect::<Result<Vec<K>, E>>()?;
             tmp.sort();
             tmp
         };
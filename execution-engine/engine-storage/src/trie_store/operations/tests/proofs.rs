@@ -0,0 +1,132 @@
+//! This module contains tests for [`read_with_proof`].
+//!
+//! Each proof produced from one of the test tries is checked with
+//! [`TrieMerkleProof::verify_path`](types::TrieMerkleProof::verify_path), which must agree with
+//! the result of the read.
+
+use types::TrieMerkleProofError;
+
+use super::*;
+use crate::{
+    error::{self, in_memory},
+    trie_store::operations::{read_with_proof, ReadWithProofResult},
+};
+
+fn check_proofs<'a, K, V, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    root: &Blake2bHash,
+    present: &[Trie<K, V>],
+    absent: &[Trie<K, V>],
+) -> Result<(), E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let txn: R::ReadTransaction = environment.create_read_txn()?;
+    let root_bytes: [u8; 32] = (*root).into();
+
+    for leaf in present.iter().chain(absent) {
+        let (key, value) = match leaf {
+            Trie::Leaf { key, value } => (key, value),
+            _ => panic!("leaves should only contain leaves"),
+        };
+        let path = key.to_bytes()?;
+        match read_with_proof::<_, _, _, _, E>(correlation_id, &txn, store, root, key)? {
+            ReadWithProofResult::Found(found, proof) => {
+                assert!(present.contains(leaf));
+                assert_eq!(&found, value);
+                assert_eq!(
+                    proof.verify_path(&root_bytes, &path),
+                    Ok(Some(value.to_bytes()?.as_slice()))
+                );
+            }
+            ReadWithProofResult::NotFound(proof) => {
+                assert!(absent.contains(leaf));
+                assert_eq!(proof.verify_path(&root_bytes, &path), Ok(None));
+            }
+            ReadWithProofResult::RootNotFound => panic!("root should be present"),
+        }
+    }
+
+    txn.commit()?;
+    Ok(())
+}
+
+#[test]
+fn lmdb_proofs_from_n_leaf_partial_trie_are_valid() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new(&tries).unwrap();
+        let test_leaves = TEST_LEAVES;
+        let (used, unused) = test_leaves.split_at(num_leaves);
+
+        check_proofs::<_, _, _, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+            unused,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn in_memory_proofs_from_n_leaf_partial_trie_are_valid() {
+    for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new(&tries).unwrap();
+        let test_leaves = TEST_LEAVES;
+        let (used, unused) = test_leaves.split_at(num_leaves);
+
+        check_proofs::<_, _, _, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &root_hash,
+            used,
+            unused,
+        )
+        .unwrap();
+    }
+}
+
+#[test]
+fn proofs_should_not_verify_against_other_roots() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = create_6_leaf_trie().unwrap();
+    let (other_root_hash, _) = create_5_leaf_trie().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let other_root_bytes: [u8; 32] = other_root_hash.into();
+
+    let txn = context.environment.create_read_txn().unwrap();
+    for leaf in TEST_LEAVES.iter() {
+        let key = leaf.key().unwrap();
+        let proof = match read_with_proof::<_, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &txn,
+            &context.store,
+            &root_hash,
+            key,
+        )
+        .unwrap()
+        {
+            ReadWithProofResult::Found(_, proof) => proof,
+            result => panic!("unexpected result: {:?}", result),
+        };
+        assert_eq!(
+            proof.verify_path(&other_root_bytes, &key.to_bytes().unwrap()),
+            Err(TrieMerkleProofError::RootHashMismatch)
+        );
+    }
+    txn.commit().unwrap();
+}
@@ -0,0 +1,334 @@
+//! This module contains tests for [`missing_trie_keys`] and [`put_trie`].
+
+use super::*;
+use crate::{
+    error::{self, in_memory},
+    store::Store,
+    trie_store::{
+        operations::{missing_trie_keys, put_trie, PutTrieResult},
+        STAGED_TRIE_STORE_NAME,
+    },
+};
+
+fn missing<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    staging_store: &S,
+    trie_keys: Vec<Blake2bHash>,
+) -> Result<Vec<Blake2bHash>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let ret = missing_trie_keys::<TestKey, TestValue, _, _, E>(
+        correlation_id,
+        &mut txn,
+        store,
+        staging_store,
+        trie_keys,
+    )?;
+    txn.commit()?;
+    Ok(ret)
+}
+
+fn put<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    staging_store: &S,
+    trie_keys: &[Blake2bHash],
+    source: &HashMap<Blake2bHash, Vec<u8>>,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    for trie_key in trie_keys.iter() {
+        let result = put_trie::<TestKey, TestValue, _, _, E>(
+            correlation_id,
+            &mut txn,
+            store,
+            staging_store,
+            trie_key,
+            &source[trie_key],
+        )?;
+        assert_eq!(result, PutTrieResult::Stored(*trie_key));
+    }
+    txn.commit()?;
+    Ok(())
+}
+
+fn serialized(tries: &[HashedTestTrie]) -> HashMap<Blake2bHash, Vec<u8>> {
+    tries
+        .iter()
+        .map(|HashedTrie { hash, trie }| (*hash, trie.to_bytes().unwrap()))
+        .collect()
+}
+
+/// Copies the trie at `root_hash` from `source` into the store of `environment`, one level at a
+/// time, and returns the number of rounds it took.
+fn sync<'a, R, S, E>(
+    correlation_id: CorrelationId,
+    environment: &'a R,
+    store: &S,
+    staging_store: &S,
+    root_hash: &Blake2bHash,
+    source: &[HashedTestTrie],
+) -> Result<usize, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<TestKey, TestValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let source = serialized(source);
+
+    let mut rounds = 0;
+    let mut missing_keys = missing::<_, _, E>(
+        correlation_id,
+        environment,
+        store,
+        staging_store,
+        vec![*root_hash],
+    )?;
+    while !missing_keys.is_empty() {
+        rounds += 1;
+        put::<_, _, E>(
+            correlation_id,
+            environment,
+            store,
+            staging_store,
+            &missing_keys,
+            &source,
+        )?;
+        missing_keys = missing::<_, _, E>(
+            correlation_id,
+            environment,
+            store,
+            staging_store,
+            vec![*root_hash],
+        )?;
+    }
+    Ok(rounds)
+}
+
+#[test]
+fn lmdb_sync_copies_every_trie() {
+    for generator in TEST_TRIE_GENERATORS.iter() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = LmdbTestContext::new::<TestKey, TestValue>(&[]).unwrap();
+        let staging_store = LmdbTrieStore::new(
+            &context.environment,
+            Some(STAGED_TRIE_STORE_NAME),
+            DatabaseFlags::empty(),
+        )
+        .unwrap();
+
+        let rounds = sync::<_, _, error::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &staging_store,
+            &root_hash,
+            &tries,
+        )
+        .unwrap();
+        assert!(rounds > 0);
+
+        for HashedTrie { hash, trie } in tries.iter() {
+            let txn = context.environment.create_read_txn().unwrap();
+            let stored: Option<TestTrie> = context.store.get(&txn, hash).unwrap();
+            let staged: Option<TestTrie> = staging_store.get(&txn, hash).unwrap();
+            txn.commit().unwrap();
+            assert_eq!(stored.as_ref(), Some(trie));
+            assert_eq!(staged, None);
+        }
+    }
+}
+
+#[test]
+fn in_memory_sync_copies_every_trie() {
+    for generator in TEST_TRIE_GENERATORS.iter() {
+        let correlation_id = CorrelationId::new();
+        let (root_hash, tries) = generator().unwrap();
+        let context = InMemoryTestContext::new::<TestKey, TestValue>(&[]).unwrap();
+        let staging_store =
+            InMemoryTrieStore::new(&context.environment, Some(STAGED_TRIE_STORE_NAME));
+
+        let rounds = sync::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &staging_store,
+            &root_hash,
+            &tries,
+        )
+        .unwrap();
+        assert!(rounds > 0);
+
+        for HashedTrie { hash, trie } in tries.iter() {
+            let txn = context.environment.create_read_txn().unwrap();
+            let stored: Option<TestTrie> = context.store.get(&txn, hash).unwrap();
+            let staged: Option<TestTrie> = staging_store.get(&txn, hash).unwrap();
+            txn.commit().unwrap();
+            assert_eq!(stored.as_ref(), Some(trie));
+            assert_eq!(staged, None);
+        }
+    }
+}
+
+#[test]
+fn in_memory_partially_synced_root_is_staged() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS.len() - 1]().unwrap();
+    let source = serialized(&tries);
+    let context = InMemoryTestContext::new::<TestKey, TestValue>(&[]).unwrap();
+    let staging_store = InMemoryTrieStore::new(&context.environment, Some(STAGED_TRIE_STORE_NAME));
+
+    put::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &staging_store,
+        &[root_hash],
+        &source,
+    )
+    .unwrap();
+    let missing_keys = missing::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &staging_store,
+        vec![root_hash],
+    )
+    .unwrap();
+    assert!(!missing_keys.is_empty());
+
+    // The root cannot be read from the store while its children are missing.
+    let txn = context.environment.create_read_txn().unwrap();
+    let stored: Option<TestTrie> = context.store.get(&txn, &root_hash).unwrap();
+    let staged: Option<TestTrie> = staging_store.get(&txn, &root_hash).unwrap();
+    txn.commit().unwrap();
+    assert_eq!(stored, None);
+    assert!(staged.is_some());
+    assert_eq!(
+        read::<TestKey, TestValue, _, _, in_memory::Error>(
+            correlation_id,
+            &context.environment.create_read_txn().unwrap(),
+            &context.store,
+            &root_hash,
+            &TestKey([0u8; TEST_KEY_LENGTH]),
+        )
+        .unwrap(),
+        ReadResult::RootNotFound
+    );
+
+    sync::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &staging_store,
+        &root_hash,
+        &tries,
+    )
+    .unwrap();
+
+    let txn = context.environment.create_read_txn().unwrap();
+    let stored: Option<TestTrie> = context.store.get(&txn, &root_hash).unwrap();
+    let staged: Option<TestTrie> = staging_store.get(&txn, &root_hash).unwrap();
+    txn.commit().unwrap();
+    assert!(stored.is_some());
+    assert_eq!(staged, None);
+}
+
+#[test]
+fn in_memory_missing_trie_keys_of_partial_trie() {
+    let correlation_id = CorrelationId::new();
+    let (root_hash, tries) = TEST_TRIE_GENERATORS[TEST_TRIE_GENERATORS.len() - 1]().unwrap();
+    let root = tries
+        .iter()
+        .find(|HashedTrie { hash, .. }| *hash == root_hash)
+        .unwrap();
+    let source = serialized(&tries);
+    let context = InMemoryTestContext::new::<TestKey, TestValue>(&[]).unwrap();
+    let staging_store = InMemoryTrieStore::new(&context.environment, Some(STAGED_TRIE_STORE_NAME));
+    put::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &staging_store,
+        &[root_hash],
+        &source,
+    )
+    .unwrap();
+
+    let expected: Vec<Blake2bHash> = match &root.trie {
+        Trie::Node { pointer_block } => pointer_block[..]
+            .iter()
+            .flatten()
+            .map(|pointer| *pointer.hash())
+            .collect(),
+        _ => panic!("root should be a node"),
+    };
+    let mut actual = missing::<_, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &staging_store,
+        vec![root_hash],
+    )
+    .unwrap();
+    actual.sort();
+    let mut expected = expected;
+    expected.sort();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn in_memory_put_trie_rejects_mismatched_hash() {
+    let correlation_id = CorrelationId::new();
+    let (_, tries) = TEST_TRIE_GENERATORS[1]().unwrap();
+    let context = InMemoryTestContext::new::<TestKey, TestValue>(&[]).unwrap();
+    let staging_store = InMemoryTrieStore::new(&context.environment, Some(STAGED_TRIE_STORE_NAME));
+    let HashedTrie { hash, trie } = &tries[0];
+    let other_hash = Blake2bHash::new(&[1u8; 32]);
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    let result = put_trie::<TestKey, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &mut txn,
+        &context.store,
+        &staging_store,
+        &other_hash,
+        &trie.to_bytes().unwrap(),
+    )
+    .unwrap();
+    txn.commit().unwrap();
+    assert_eq!(
+        result,
+        PutTrieResult::HashMismatch {
+            expected: other_hash,
+            actual: *hash,
+        }
+    );
+    assert_eq!(
+        missing::<_, _, in_memory::Error>(
+            correlation_id,
+            &context.environment,
+            &context.store,
+            &staging_store,
+            vec![other_hash, *hash],
+        )
+        .unwrap()
+        .len(),
+        2
+    );
+}
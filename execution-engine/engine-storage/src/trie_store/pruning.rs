@@ -0,0 +1,246 @@
+//! Garbage collection of unreachable [`Trie`] nodes in an [`LmdbTrieStore`].
+//!
+//! Every commit to global state writes new trie nodes and leaves the nodes of the previous state
+//! in place, so the store only ever grows.  A [`TriePruner`] removes every node which is not
+//! reachable from a given set of state roots to keep, using a mark-and-sweep over the store.
+//!
+//! Pruning is incremental: [`TriePruner::step`] does a bounded amount of work in its own
+//! transaction, so that it can be interleaved with executing and committing deploys.  Any state
+//! root committed while pruning is in progress and which should survive the pruning must be
+//! registered with [`TriePruner::keep_root`] before the next call to [`TriePruner::step`], as new
+//! roots may share nodes which are otherwise unreachable.
+//!
+//! If a root to keep is not in the store, pruning stops with [`error::Error::RootNotFound`] before
+//! anything is deleted, since the root was most likely given by mistake.
+
+use std::{collections::HashSet, marker::PhantomData};
+
+use lmdb::{self, Cursor};
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+use crate::{
+    error,
+    store::Store,
+    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
+    trie::{Pointer, Trie},
+    trie_store::lmdb::LmdbTrieStore,
+};
+
+/// Statistics about the progress of a [`TriePruner`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PruningStats {
+    /// The number of nodes found to be reachable from the roots being kept.
+    pub nodes_kept: u64,
+    /// The number of unreachable nodes deleted from the store.
+    pub nodes_freed: u64,
+    /// The total size in bytes of the keys and values of the deleted nodes.
+    pub bytes_freed: u64,
+}
+
+/// The outcome of a single call to [`TriePruner::step`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PruningProgress {
+    /// There is more work left to do.
+    InProgress(PruningStats),
+    /// Every unreachable node has been deleted.
+    Done(PruningStats),
+}
+
+/// Incrementally deletes the nodes of an [`LmdbTrieStore`] which are not reachable from a set of
+/// state roots.
+///
+/// `K` and `V` are the key and value types of the tries held in the store.
+pub struct TriePruner<K, V> {
+    roots: HashSet<Blake2bHash>,
+    marked: HashSet<Blake2bHash>,
+    pending: Vec<Blake2bHash>,
+    last_swept: Option<Blake2bHash>,
+    sweep_done: bool,
+    stats: PruningStats,
+    _phantom: PhantomData<(K, V)>,
+}
+
+impl<K, V> TriePruner<K, V>
+where
+    K: ToBytes + FromBytes,
+    V: ToBytes + FromBytes,
+{
+    /// Creates a pruner which will keep every node reachable from `roots`.
+    pub fn new<I: IntoIterator<Item = Blake2bHash>>(roots: I) -> Self {
+        let mut ret = TriePruner {
+            roots: HashSet::new(),
+            marked: HashSet::new(),
+            pending: Vec::new(),
+            last_swept: None,
+            sweep_done: false,
+            stats: PruningStats::default(),
+            _phantom: PhantomData,
+        };
+        for root in roots {
+            ret.keep_root(root);
+        }
+        ret
+    }
+
+    /// Adds `root` to the set of state roots whose nodes are kept.
+    ///
+    /// This must be called for every root committed while pruning is in progress which should
+    /// outlive the pruning.
+    pub fn keep_root(&mut self, root: Blake2bHash) {
+        self.roots.insert(root);
+        if self.marked.insert(root) {
+            self.stats.nodes_kept += 1;
+            self.pending.push(root);
+        }
+    }
+
+    /// Returns the statistics accumulated so far.
+    pub fn stats(&self) -> PruningStats {
+        self.stats
+    }
+
+    /// Returns `true` if every unreachable node has been deleted.
+    pub fn is_done(&self) -> bool {
+        self.sweep_done && self.pending.is_empty()
+    }
+
+    /// Visits or deletes at most `max_nodes` nodes of `store`.
+    ///
+    /// Nodes reachable from the kept roots are always all marked before any node is deleted, and
+    /// marking is resumed first if a root was added with [`TriePruner::keep_root`] since the last
+    /// step.
+    ///
+    /// Returns [`error::Error::RootNotFound`] if a kept root is missing from `store`, in which case
+    /// every later step fails the same way and nothing is deleted.
+    pub fn step(
+        &mut self,
+        env: &LmdbEnvironment,
+        store: &LmdbTrieStore,
+        max_nodes: usize,
+    ) -> Result<PruningProgress, error::Error> {
+        if !self.pending.is_empty() {
+            self.mark(env, store, max_nodes)?;
+        } else if !self.sweep_done {
+            self.sweep(env, store, max_nodes)?;
+        }
+
+        if self.is_done() {
+            Ok(PruningProgress::Done(self.stats))
+        } else {
+            Ok(PruningProgress::InProgress(self.stats))
+        }
+    }
+
+    /// Runs the pruner until every unreachable node has been deleted, doing at most
+    /// `nodes_per_step` nodes in each transaction.
+    ///
+    /// `nodes_per_step` must be non-zero.
+    pub fn run(
+        &mut self,
+        env: &LmdbEnvironment,
+        store: &LmdbTrieStore,
+        nodes_per_step: usize,
+    ) -> Result<PruningStats, error::Error> {
+        loop {
+            if let PruningProgress::Done(stats) = self.step(env, store, nodes_per_step)? {
+                return Ok(stats);
+            }
+        }
+    }
+
+    fn mark(
+        &mut self,
+        env: &LmdbEnvironment,
+        store: &LmdbTrieStore,
+        max_nodes: usize,
+    ) -> Result<(), error::Error> {
+        let txn = env.create_read_txn()?;
+        for _ in 0..max_nodes {
+            let hash = match self.pending.pop() {
+                Some(hash) => hash,
+                None => break,
+            };
+            let trie: Trie<K, V> = match store.get(&txn, &hash)? {
+                Some(trie) => trie,
+                None if self.roots.contains(&hash) => {
+                    self.pending.push(hash);
+                    return Err(error::Error::RootNotFound(hash));
+                }
+                // A missing node below a root has no children in the store which could be
+                // reached through it.
+                None => continue,
+            };
+            match trie {
+                Trie::Leaf { .. } => {}
+                Trie::Node { pointer_block } => {
+                    for pointer in pointer_block[..].iter().flatten() {
+                        self.mark_pointer(pointer);
+                    }
+                }
+                Trie::Extension { pointer, .. } => self.mark_pointer(&pointer),
+            }
+        }
+        txn.commit()?;
+        Ok(())
+    }
+
+    fn mark_pointer(&mut self, pointer: &Pointer) {
+        let hash = *pointer.hash();
+        if !self.marked.insert(hash) {
+            return;
+        }
+        self.stats.nodes_kept += 1;
+        // Leaves have no children, so there is no need to read them.
+        if let Pointer::NodePointer(_) = pointer {
+            self.pending.push(hash);
+        }
+    }
+
+    fn sweep(
+        &mut self,
+        env: &LmdbEnvironment,
+        store: &LmdbTrieStore,
+        max_nodes: usize,
+    ) -> Result<(), error::Error> {
+        let handle = Store::<Blake2bHash, Trie<K, V>>::handle(store);
+        let mut txn = env.create_read_write_txn()?;
+
+        let mut unreachable: Vec<(Vec<u8>, usize)> = Vec::new();
+        let mut visited = 0;
+        {
+            let mut cursor = lmdb::Transaction::open_ro_cursor(&txn, handle)?;
+            let iter = match self.last_swept {
+                Some(last_swept) => cursor.iter_from(last_swept.to_bytes()?),
+                None => cursor.iter_start(),
+            };
+            for entry in iter {
+                let (key_bytes, value_bytes) = entry?;
+                let hash: Blake2bHash = bytesrepr::deserialize(key_bytes.to_vec())?;
+                if Some(hash) == self.last_swept {
+                    continue;
+                }
+                if visited == max_nodes {
+                    break;
+                }
+                visited += 1;
+                self.last_swept = Some(hash);
+                if !self.marked.contains(&hash) {
+                    unreachable.push((key_bytes.to_vec(), key_bytes.len() + value_bytes.len()));
+                }
+            }
+        }
+        if visited < max_nodes {
+            self.sweep_done = true;
+        }
+
+        for (key_bytes, size) in unreachable {
+            txn.del(handle, &key_bytes, None)?;
+            self.stats.nodes_freed += 1;
+            self.stats.bytes_freed += size as u64;
+        }
+        txn.commit()?;
+        Ok(())
+    }
+}
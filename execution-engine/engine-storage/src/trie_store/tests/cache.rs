@@ -0,0 +1,200 @@
+use std::{sync::Arc, thread};
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::ToBytes;
+
+use super::TestData;
+use crate::{
+    store::{Store, StoreExt},
+    transaction_source::{in_memory::InMemoryEnvironment, Transaction, TransactionSource},
+    trie::Trie,
+    trie_store::{
+        cache::{trie_size, CachedTrieStore, TrieCache, DEFAULT_TRIE_CACHE_SIZE},
+        in_memory::InMemoryTrieStore,
+    },
+};
+
+fn size_of(trie: &Trie<Vec<u8>, Vec<u8>>) -> usize {
+    trie_size(trie, trie.to_bytes().unwrap().len())
+}
+
+fn insert(cache: &TrieCache<Vec<u8>, Vec<u8>>, hash: Blake2bHash, trie: Trie<Vec<u8>, Vec<u8>>) {
+    let serialized_length = trie.to_bytes().unwrap().len();
+    cache.insert(hash, trie, serialized_length)
+}
+
+#[test]
+fn cache_evicts_least_recently_used_trie() {
+    let data = super::create_data();
+    let TestData(hash_1, trie_1) = data[0].clone();
+    let TestData(hash_2, trie_2) = data[1].clone();
+    let TestData(hash_3, trie_3) = data[2].clone();
+    let cache = TrieCache::new(size_of(&trie_1) + size_of(&trie_2));
+
+    insert(&cache, hash_1, trie_1.clone());
+    insert(&cache, hash_2, trie_2);
+    assert_eq!(cache.get(&hash_1), Some(trie_1.clone()));
+
+    insert(&cache, hash_3, trie_3.clone());
+    assert_eq!(cache.len(), 2);
+    assert!(cache.size() <= cache.capacity());
+    assert_eq!(cache.get(&hash_1), Some(trie_1));
+    assert_eq!(cache.get(&hash_2), None);
+    assert_eq!(cache.get(&hash_3), Some(trie_3));
+}
+
+#[test]
+fn cache_with_zero_capacity_holds_nothing() {
+    let data = super::create_data();
+    let cache = TrieCache::new(0);
+    let TestData(hash, trie) = data[0].clone();
+    insert(&cache, hash, trie);
+    assert!(cache.is_empty());
+    assert_eq!(cache.get(&hash), None);
+}
+
+#[test]
+fn cache_evicts_small_tries_to_make_room_for_a_large_one() {
+    let data = super::create_data();
+    let leaves: Vec<_> = data
+        .iter()
+        .filter(|TestData(_, trie)| match trie {
+            Trie::Leaf { .. } => true,
+            _ => false,
+        })
+        .cloned()
+        .collect();
+    let TestData(node_hash, node) = data
+        .iter()
+        .find(|TestData(_, trie)| match trie {
+            Trie::Node { .. } => true,
+            _ => false,
+        })
+        .cloned()
+        .unwrap();
+    let cache = TrieCache::new(size_of(&node));
+
+    for TestData(hash, trie) in leaves.iter().cloned() {
+        insert(&cache, hash, trie);
+    }
+    assert_eq!(cache.len(), leaves.len());
+
+    insert(&cache, node_hash, node.clone());
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.size(), size_of(&node));
+    assert_eq!(cache.get(&node_hash), Some(node));
+}
+
+#[test]
+fn cache_does_not_hold_tries_larger_than_capacity() {
+    let data = super::create_data();
+    let TestData(node_hash, node) = data
+        .iter()
+        .find(|TestData(_, trie)| match trie {
+            Trie::Node { .. } => true,
+            _ => false,
+        })
+        .cloned()
+        .unwrap();
+    let TestData(leaf_hash, leaf) = data[0].clone();
+    let cache = TrieCache::new(size_of(&node) - 1);
+
+    insert(&cache, leaf_hash, leaf.clone());
+    insert(&cache, node_hash, node);
+    assert_eq!(cache.get(&node_hash), None);
+    assert_eq!(cache.get(&leaf_hash), Some(leaf));
+}
+
+#[test]
+fn cached_store_reads_through_cache() {
+    let data = super::create_data();
+    let env = InMemoryEnvironment::new();
+    let store = InMemoryTrieStore::new(&env, None);
+    let cache = TrieCache::new(DEFAULT_TRIE_CACHE_SIZE);
+    let cached_store = CachedTrieStore::new(&store, &cache);
+    let keys: Vec<_> = data.iter().map(|TestData(hash, _)| *hash).collect();
+    let expected: Vec<_> = data
+        .iter()
+        .map(|TestData(_, trie)| Some(trie.clone()))
+        .collect();
+
+    {
+        let mut txn = env.create_read_write_txn().unwrap();
+        cached_store
+            .put_many(&mut txn, data.iter().map(Into::into))
+            .unwrap();
+        txn.commit().unwrap();
+    }
+    assert!(cache.is_empty());
+
+    for _ in 0..2 {
+        let txn = env.create_read_txn().unwrap();
+        let tries = cached_store.get_many(&txn, keys.iter()).unwrap();
+        txn.commit().unwrap();
+        assert_eq!(tries, expected);
+    }
+    assert_eq!(cached_store.misses(), data.len() as u64);
+    assert_eq!(cached_store.hits(), data.len() as u64);
+
+    let txn = env.create_read_txn().unwrap();
+    let missing = cached_store
+        .get(&txn, &Blake2bHash::from([0u8; 32]))
+        .unwrap();
+    txn.commit().unwrap();
+    assert_eq!(missing, None);
+    assert_eq!(cache.len(), data.len());
+}
+
+#[test]
+fn deferred_store_caches_only_when_asked() {
+    let data = super::create_data();
+    let env = InMemoryEnvironment::new();
+    let store = InMemoryTrieStore::new(&env, None);
+    let cache = TrieCache::new(DEFAULT_TRIE_CACHE_SIZE);
+    let keys: Vec<_> = data.iter().map(|TestData(hash, _)| *hash).collect();
+
+    // tries read in an aborted transaction which wrote them should never be cached
+    {
+        let cached_store = CachedTrieStore::deferred(&store, &cache);
+        let mut txn = env.create_read_write_txn().unwrap();
+        cached_store
+            .put_many(&mut txn, data.iter().map(Into::into))
+            .unwrap();
+        cached_store.get_many(&txn, keys.iter()).unwrap();
+    }
+    assert!(cache.is_empty());
+
+    // and tries read in a committed one only once the store is told the transaction committed
+    let cached_store = CachedTrieStore::deferred(&store, &cache);
+    let mut txn = env.create_read_write_txn().unwrap();
+    cached_store
+        .put_many(&mut txn, data.iter().map(Into::into))
+        .unwrap();
+    cached_store.get_many(&txn, keys.iter()).unwrap();
+    txn.commit().unwrap();
+    assert!(cache.is_empty());
+    cached_store.cache_deferred();
+    assert_eq!(cache.len(), data.len());
+}
+
+#[test]
+fn cache_is_shared_between_threads() {
+    let data = super::create_data();
+    let cache = Arc::new(TrieCache::new(DEFAULT_TRIE_CACHE_SIZE));
+
+    let handles: Vec<_> = data
+        .iter()
+        .cloned()
+        .map(|TestData(hash, trie)| {
+            let cache = Arc::clone(&cache);
+            thread::spawn(move || insert(&cache, hash, trie))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    for TestData(hash, trie) in data {
+        assert_eq!(cache.get(&hash), Some(trie));
+    }
+}
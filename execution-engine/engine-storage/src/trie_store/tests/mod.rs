@@ -1,5 +1,7 @@
+mod cache;
 mod concurrent;
 mod proptests;
+mod pruning;
 mod simple;
 
 use engine_shared::newtypes::Blake2bHash;
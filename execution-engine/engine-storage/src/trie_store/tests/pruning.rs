@@ -0,0 +1,208 @@
+use lmdb::DatabaseFlags;
+use tempfile::tempdir;
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::ToBytes;
+
+use super::TestData;
+use crate::{
+    error,
+    store::{Store, StoreExt},
+    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
+    trie::{Pointer, Trie},
+    trie_store::{
+        lmdb::LmdbTrieStore,
+        pruning::{PruningProgress, PruningStats, TriePruner},
+    },
+    TEST_MAP_SIZE,
+};
+
+type TestPruner = TriePruner<Vec<u8>, Vec<u8>>;
+
+fn put_data(env: &LmdbEnvironment, store: &LmdbTrieStore, data: &[TestData<Vec<u8>, Vec<u8>>]) {
+    let mut txn = env.create_read_write_txn().unwrap();
+    store
+        .put_many(&mut txn, data.iter().map(Into::into))
+        .unwrap();
+    txn.commit().unwrap();
+}
+
+fn present(
+    env: &LmdbEnvironment,
+    store: &LmdbTrieStore,
+    data: &[TestData<Vec<u8>, Vec<u8>>],
+) -> Vec<bool> {
+    let txn = env.create_read_txn().unwrap();
+    let ret = data
+        .iter()
+        .map(|TestData(hash, _)| {
+            let maybe_trie: Option<Trie<Vec<u8>, Vec<u8>>> = store.get(&txn, hash).unwrap();
+            maybe_trie.is_some()
+        })
+        .collect();
+    txn.commit().unwrap();
+    ret
+}
+
+fn size_of(data: &[TestData<Vec<u8>, Vec<u8>>]) -> u64 {
+    data.iter()
+        .map(|TestData(hash, trie)| {
+            (hash.to_bytes().unwrap().len() + trie.to_bytes().unwrap().len()) as u64
+        })
+        .sum()
+}
+
+#[test]
+fn should_remove_nodes_unreachable_from_kept_roots() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let data = super::create_data();
+    put_data(&env, &store, &data);
+
+    // Keep only the subtrie rooted at node_2, i.e. node_2 and leaves 2 and 3.
+    let TestData(node_2_hash, _) = data[4];
+    let mut pruner = TestPruner::new(vec![node_2_hash]);
+    let stats = pruner.run(&env, &store, 100).unwrap();
+
+    assert_eq!(
+        present(&env, &store, &data),
+        vec![false, true, true, false, true, false]
+    );
+    let freed = [data[0].clone(), data[3].clone(), data[5].clone()];
+    assert_eq!(
+        stats,
+        PruningStats {
+            nodes_kept: 3,
+            nodes_freed: 3,
+            bytes_freed: size_of(&freed),
+        }
+    );
+    assert!(pruner.is_done());
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn should_keep_everything_reachable_from_root() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let data = super::create_data();
+    put_data(&env, &store, &data);
+
+    let TestData(node_1_hash, _) = data[3];
+    let stats = TestPruner::new(vec![node_1_hash])
+        .run(&env, &store, 1)
+        .unwrap();
+
+    assert!(present(&env, &store, &data).into_iter().all(|b| b));
+    assert_eq!(stats.nodes_kept, data.len() as u64);
+    assert_eq!(stats.nodes_freed, 0);
+    assert_eq!(stats.bytes_freed, 0);
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn should_prune_incrementally() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let data = super::create_data();
+    put_data(&env, &store, &data);
+
+    let TestData(node_2_hash, _) = data[4];
+    let mut pruner = TestPruner::new(vec![node_2_hash]);
+
+    let mut steps = 0;
+    let stats = loop {
+        steps += 1;
+        match pruner.step(&env, &store, 1).unwrap() {
+            PruningProgress::InProgress(stats) => {
+                assert!(stats.nodes_freed < 3);
+            }
+            PruningProgress::Done(stats) => break stats,
+        }
+    };
+
+    // One node to mark (leaves are not read), six nodes to sweep and a final empty sweep.
+    assert_eq!(steps, 1 + data.len() + 1);
+    assert_eq!(stats.nodes_freed, 3);
+    assert_eq!(
+        present(&env, &store, &data),
+        vec![false, true, true, false, true, false]
+    );
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn should_keep_roots_added_during_pruning() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let data = super::create_data();
+    put_data(&env, &store, &data);
+
+    // Mark the subtrie rooted at node_2, then sweep a single node.
+    let TestData(node_2_hash, _) = data[4];
+    let mut pruner = TestPruner::new(vec![node_2_hash]);
+    pruner.step(&env, &store, 1).unwrap();
+    assert_eq!(pruner.stats().nodes_kept, 3);
+    pruner.step(&env, &store, 1).unwrap();
+
+    // A root committed in the meantime, which shares node_2, is registered.
+    let new_leaf = Trie::Leaf {
+        key: vec![0u8, 0, 0],
+        value: b"val_4".to_vec(),
+    };
+    let new_leaf_hash = Blake2bHash::new(&new_leaf.to_bytes().unwrap());
+    let new_root: Trie<Vec<u8>, Vec<u8>> = Trie::node(&[
+        (0, Pointer::LeafPointer(new_leaf_hash)),
+        (1, Pointer::NodePointer(node_2_hash)),
+    ]);
+    let new_root_hash = Blake2bHash::new(&new_root.to_bytes().unwrap());
+    let new_data = vec![
+        TestData(new_leaf_hash, new_leaf),
+        TestData(new_root_hash, new_root),
+    ];
+    put_data(&env, &store, &new_data);
+    pruner.keep_root(new_root_hash);
+
+    let stats = pruner.run(&env, &store, 1).unwrap();
+
+    assert_eq!(
+        present(&env, &store, &data),
+        vec![false, true, true, false, true, false]
+    );
+    assert_eq!(present(&env, &store, &new_data), vec![true, true]);
+    assert_eq!(stats.nodes_kept, 5);
+    assert_eq!(stats.nodes_freed, 3);
+
+    tmp_dir.close().unwrap();
+}
+
+#[test]
+fn should_not_prune_when_kept_root_is_missing() {
+    let tmp_dir = tempdir().unwrap();
+    let env = LmdbEnvironment::new(&tmp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+    let store = LmdbTrieStore::new(&env, None, DatabaseFlags::empty()).unwrap();
+    let data = super::create_data();
+    put_data(&env, &store, &data);
+
+    let TestData(node_2_hash, _) = data[4];
+    let missing_root = Blake2bHash::new(b"missing root");
+    let mut pruner = TestPruner::new(vec![node_2_hash, missing_root]);
+
+    for _ in 0..3 {
+        assert_eq!(
+            pruner.step(&env, &store, 100),
+            Err(error::Error::RootNotFound(missing_root))
+        );
+    }
+    assert!(present(&env, &store, &data).into_iter().all(|b| b));
+    assert!(!pruner.is_done());
+
+    tmp_dir.close().unwrap();
+}
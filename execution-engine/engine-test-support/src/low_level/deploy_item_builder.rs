@@ -5,7 +5,7 @@ use engine_core::{
     engine_state::{deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem},
     DeployHash,
 };
-use types::{account::PublicKey, bytesrepr::ToBytes, URef};
+use types::{account::PublicKey, bytesrepr::ToBytes, URef, DEFAULT_ENTRY_POINT};
 
 use crate::low_level::utils;
 
@@ -19,6 +19,7 @@ struct DeployItemData {
     pub deploy_hash: DeployHash,
 }
 
+#[derive(Default)]
 pub struct DeployItemBuilder {
     deploy_item: DeployItemData,
 }
@@ -45,8 +46,11 @@ impl DeployItemBuilder {
 
     pub fn with_stored_payment_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
-        self.deploy_item.payment_code =
-            Some(ExecutableDeployItem::StoredContractByHash { hash, args });
+        self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByHash {
+            hash,
+            entry_point: DEFAULT_ENTRY_POINT.to_string(),
+            args,
+        });
         self
     }
 
@@ -54,6 +58,7 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref.addr().to_vec(),
+            entry_point: DEFAULT_ENTRY_POINT.to_string(),
             args,
         });
         self
@@ -63,6 +68,7 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.payment_code = Some(ExecutableDeployItem::StoredContractByName {
             name: uref_name.to_owned(),
+            entry_point: DEFAULT_ENTRY_POINT.to_string(),
             args,
         });
         self
@@ -80,8 +86,11 @@ impl DeployItemBuilder {
 
     pub fn with_stored_session_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
-        self.deploy_item.session_code =
-            Some(ExecutableDeployItem::StoredContractByHash { hash, args });
+        self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByHash {
+            hash,
+            entry_point: DEFAULT_ENTRY_POINT.to_string(),
+            args,
+        });
         self
     }
 
@@ -89,6 +98,7 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByURef {
             uref: uref.addr().to_vec(),
+            entry_point: DEFAULT_ENTRY_POINT.to_string(),
             args,
         });
         self
@@ -98,11 +108,36 @@ impl DeployItemBuilder {
         let args = Self::serialize_args(args);
         self.deploy_item.session_code = Some(ExecutableDeployItem::StoredContractByName {
             name: uref_name.to_owned(),
+            entry_point: DEFAULT_ENTRY_POINT.to_string(),
             args,
         });
         self
     }
 
+    /// Sets the entry point of the stored payment contract; if not set, the default entry point
+    /// is run.
+    ///
+    /// Panics if the payment code was not set to a stored contract before.
+    pub fn with_payment_entry_point(mut self, entry_point: &str) -> Self {
+        Self::set_entry_point(self.deploy_item.payment_code.as_mut(), entry_point);
+        self
+    }
+
+    /// Sets the entry point of the stored session contract; if not set, the default entry point
+    /// is run.
+    ///
+    /// Panics if the session code was not set to a stored contract before.
+    pub fn with_session_entry_point(mut self, entry_point: &str) -> Self {
+        Self::set_entry_point(self.deploy_item.session_code.as_mut(), entry_point);
+        self
+    }
+
+    /// Sets the gas price; if not set, the deploy pays at the chain's default conversion rate.
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.deploy_item.gas_price = gas_price;
+        self
+    }
+
     pub fn with_authorization_keys(mut self, authorization_keys: &[PublicKey]) -> Self {
         self.deploy_item.authorization_keys = authorization_keys.iter().cloned().collect();
         self
@@ -124,6 +159,17 @@ impl DeployItemBuilder {
         }
     }
 
+    fn set_entry_point(deploy_item: Option<&mut ExecutableDeployItem>, new_entry_point: &str) {
+        match deploy_item {
+            Some(ExecutableDeployItem::StoredContractByHash { entry_point, .. })
+            | Some(ExecutableDeployItem::StoredContractByName { entry_point, .. })
+            | Some(ExecutableDeployItem::StoredContractByURef { entry_point, .. }) => {
+                *entry_point = new_entry_point.to_string()
+            }
+            _ => panic!("entry point can only be set for a stored contract"),
+        }
+    }
+
     fn serialize_args(args: impl ArgsParser) -> Vec<u8> {
         args.parse_to_vec_u8()
             .expect("should convert to `Vec<CLValue>`")
@@ -131,11 +177,3 @@ impl DeployItemBuilder {
             .expect("should serialize args")
     }
 }
-
-impl Default for DeployItemBuilder {
-    fn default() -> Self {
-        let mut deploy_item: DeployItemData = Default::default();
-        deploy_item.gas_price = 1;
-        DeployItemBuilder { deploy_item }
-    }
-}
@@ -43,6 +43,16 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    pub fn with_chain_deploys(mut self, chain_deploys: bool) -> Self {
+        self.execute_request.chain_deploys = chain_deploys;
+        self
+    }
+
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.execute_request.trace = trace;
+        self
+    }
+
     pub fn build(self) -> ExecuteRequest {
         self.execute_request
     }
@@ -10,8 +10,10 @@ use lazy_static::lazy_static;
 use num_traits::identities::Zero;
 
 use engine_core::engine_state::genesis::{GenesisAccount, GenesisConfig};
-use engine_shared::{motes::Motes, test_utils};
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_shared::{conv_rates::ConvRates, motes::Motes, test_utils};
+use engine_wasm_prep::{
+    host_function_costs::HostFunctionCosts, storage_costs::StorageCosts, wasm_costs::WasmCosts,
+};
 use types::{account::PublicKey, ProtocolVersion, U512};
 
 pub use additive_map_diff::AdditiveMapDiff;
@@ -48,6 +50,10 @@ lazy_static! {
     pub static ref DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
     pub static ref DEFAULT_PAYMENT: U512 = 100_000_000.into();
     pub static ref DEFAULT_WASM_COSTS: WasmCosts = test_utils::wasm_costs_mock();
+    pub static ref DEFAULT_HOST_FUNCTION_COSTS: HostFunctionCosts =
+        test_utils::host_function_costs_mock();
+    pub static ref DEFAULT_STORAGE_COSTS: StorageCosts = test_utils::storage_costs_mock();
+    pub static ref DEFAULT_CONV_RATES: ConvRates = ConvRates::default();
     pub static ref DEFAULT_GENESIS_CONFIG: GenesisConfig = {
         let mint_installer_bytes = utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT);
         let pos_installer_bytes = utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT);
@@ -59,6 +65,9 @@ lazy_static! {
             pos_installer_bytes,
             DEFAULT_ACCOUNTS.clone(),
             *DEFAULT_WASM_COSTS,
+            *DEFAULT_HOST_FUNCTION_COSTS,
+            *DEFAULT_STORAGE_COSTS,
+            *DEFAULT_CONV_RATES,
         )
     };
 }
@@ -16,8 +16,9 @@ use engine_shared::{
 use types::Key;
 
 use crate::low_level::{
-    DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
-    MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
+    DEFAULT_CHAIN_NAME, DEFAULT_CONV_RATES, DEFAULT_GENESIS_TIMESTAMP, DEFAULT_HOST_FUNCTION_COSTS,
+    DEFAULT_PROTOCOL_VERSION, DEFAULT_STORAGE_COSTS, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
+    POS_INSTALL_CONTRACT,
 };
 
 const COMPILED_WASM_DEFAULT_PATH: &str = "../target/wasm32-unknown-unknown/release";
@@ -81,6 +82,9 @@ pub fn create_genesis_config(accounts: Vec<GenesisAccount>) -> GenesisConfig {
     let proof_of_stake_installer_bytes = read_wasm_file_bytes(POS_INSTALL_CONTRACT);
     let protocol_version = *DEFAULT_PROTOCOL_VERSION;
     let wasm_costs = *DEFAULT_WASM_COSTS;
+    let host_function_costs = *DEFAULT_HOST_FUNCTION_COSTS;
+    let storage_costs = *DEFAULT_STORAGE_COSTS;
+    let conv_rates = *DEFAULT_CONV_RATES;
     GenesisConfig::new(
         name,
         timestamp,
@@ -89,6 +93,9 @@ pub fn create_genesis_config(accounts: Vec<GenesisAccount>) -> GenesisConfig {
         proof_of_stake_installer_bytes,
         accounts,
         wasm_costs,
+        host_function_costs,
+        storage_costs,
+        conv_rates,
     )
 }
 
@@ -13,8 +13,9 @@ use lmdb::DatabaseFlags;
 
 use engine_core::{
     engine_state::{
-        execute_request::ExecuteRequest, execution_result::ExecutionResult, genesis::GenesisConfig,
-        EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
+        execute_and_commit_request::ExecuteAndCommitRequest, execute_request::ExecuteRequest,
+        execution_result::ExecutionResult, genesis::GenesisConfig, EngineConfig, EngineState,
+        Error as EngineError, SYSTEM_ACCOUNT_ADDR,
     },
     execution,
 };
@@ -38,7 +39,9 @@ use engine_shared::{
     transform::Transform,
 };
 use engine_storage::{
-    global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider},
+    global_state::{
+        in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, CommitResult, StateProvider,
+    },
     protocol_data_store::lmdb::LmdbProtocolDataStore,
     transaction_source::lmdb::LmdbEnvironment,
     trie_store::lmdb::LmdbTrieStore,
@@ -440,6 +443,49 @@ where
         self
     }
 
+    /// Executes the deploys of `request` on the latest post-state hash and commits the effects of
+    /// the selected ones in one go, expecting the commit to succeed.
+    pub fn exec_and_commit(&mut self, mut request: ExecuteAndCommitRequest) -> &mut Self
+    where
+        EngineError: From<S::Error>,
+    {
+        let prestate_hash = self
+            .post_state_hash
+            .clone()
+            .expect("expected post_state_hash");
+        request.execute_request.parent_state_hash = prestate_hash
+            .as_slice()
+            .try_into()
+            .expect("expected a valid hash");
+
+        let (execution_results, commit_result) = self
+            .engine_state
+            .run_execute_and_commit(CorrelationId::new(), request)
+            .expect("should execute and commit");
+        match commit_result {
+            CommitResult::Success {
+                state_root,
+                bonded_validators,
+            } => {
+                self.post_state_hash = Some(state_root.to_vec());
+                self.bonded_validators.push(bonded_validators);
+            }
+            commit_result => panic!(
+                "Expected commit success but received a failure instead: {:?}",
+                commit_result
+            ),
+        }
+
+        self.transforms.extend(
+            execution_results
+                .iter()
+                .map(|res| res.effect().transforms.clone()),
+        );
+        self.exec_responses
+            .push(execution_results.into_iter().map(Rc::new).collect());
+        self
+    }
+
     pub fn upgrade_with_upgrade_request(
         &mut self,
         upgrade_request: &mut UpgradeRequest,
@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+
+use lazy_static::lazy_static;
+
+use engine_core::engine_state::{
+    deploy_item::DeployItem, execute_and_commit_request::ExecuteAndCommitRequest,
+    execute_request::ExecuteRequest,
+};
+use engine_shared::newtypes::CorrelationId;
+use engine_test_support::low_level::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
+};
+use types::{account::PublicKey, U512};
+
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account.wasm";
+
+lazy_static! {
+    static ref ACCOUNT_1_INITIAL_FUNDS: U512 = *DEFAULT_PAYMENT * 10;
+}
+
+fn transfer_deploy(from: [u8; 32], to: [u8; 32], amount: u64, deploy_hash: [u8; 32]) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(from)
+        .with_session_code(CONTRACT_TRANSFER_TO_ACCOUNT, (PublicKey::new(to), amount))
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(from)])
+        .with_deploy_hash(deploy_hash)
+        .build()
+}
+
+/// Creates account 1, then has account 1 create account 2.
+fn make_exec_request(pre_state_hash: &[u8], chain_deploys: bool) -> ExecuteRequest {
+    ExecuteRequestBuilder::new()
+        .push_deploy(transfer_deploy(
+            DEFAULT_ACCOUNT_ADDR,
+            ACCOUNT_1_ADDR,
+            ACCOUNT_1_INITIAL_FUNDS.as_u64(),
+            [1; 32],
+        ))
+        .push_deploy(transfer_deploy(ACCOUNT_1_ADDR, ACCOUNT_2_ADDR, 1, [2; 32]))
+        .with_pre_state_hash(pre_state_hash)
+        .with_chain_deploys(chain_deploys)
+        .build()
+}
+
+#[ignore]
+#[test]
+fn should_not_see_prior_deploys_without_chaining() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    let results = builder
+        .get_engine_state()
+        .run_execute(
+            CorrelationId::new(),
+            make_exec_request(&pre_state_hash, false),
+        )
+        .expect("should execute");
+
+    assert!(results[0].is_success());
+    // account 1 does not exist in the parent state
+    assert!(results[1].is_failure());
+}
+
+#[ignore]
+#[test]
+fn should_see_prior_deploys_when_chained() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    let (results, chained_effect) = builder
+        .get_engine_state()
+        .run_execute_chained(
+            CorrelationId::new(),
+            make_exec_request(&pre_state_hash, true),
+        )
+        .expect("should execute");
+
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_success());
+    assert!(results[1].is_success());
+
+    // every deploy's transforms are part of the chained effect
+    for result in &results {
+        for key in result.effect().transforms.keys() {
+            assert!(chained_effect.transforms.get(key).is_some());
+        }
+    }
+
+    builder.commit_effects(pre_state_hash, chained_effect.transforms);
+
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_some());
+}
+
+#[ignore]
+#[test]
+fn should_execute_and_commit_in_one_call() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    builder.exec_and_commit(ExecuteAndCommitRequest::new(make_exec_request(
+        &pre_state_hash,
+        true,
+    )));
+
+    let results = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| result.is_success()));
+
+    assert_ne!(builder.get_post_state_hash(), pre_state_hash);
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_some());
+}
+
+#[ignore]
+#[test]
+fn should_execute_and_commit_without_chaining() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    builder.exec_and_commit(ExecuteAndCommitRequest::new(make_exec_request(
+        &pre_state_hash,
+        false,
+    )));
+
+    let results = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert!(results[0].is_success());
+    // account 1 does not exist in the parent state
+    assert!(results[1].is_failure());
+
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_none());
+}
+
+#[ignore]
+#[test]
+fn should_commit_only_selected_deploys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    let committed_deploys: BTreeSet<usize> = vec![0].into_iter().collect();
+    builder.exec_and_commit(ExecuteAndCommitRequest {
+        execute_request: make_exec_request(&pre_state_hash, true),
+        committed_deploys,
+    });
+
+    let results = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert!(results.iter().all(|result| result.is_success()));
+
+    // the second deploy saw the first one's effects, but its own were not committed
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_none());
+}
+
+#[ignore]
+#[test]
+fn should_not_chain_on_deploys_which_are_not_committed() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    let committed_deploys: BTreeSet<usize> = vec![1].into_iter().collect();
+    builder.exec_and_commit(ExecuteAndCommitRequest {
+        execute_request: make_exec_request(&pre_state_hash, true),
+        committed_deploys,
+    });
+
+    let results = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert!(results[0].is_success());
+    // account 1 was never created, as the first deploy's effects were not applied
+    assert!(results[1].is_failure());
+
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+    assert!(builder.get_account(ACCOUNT_2_ADDR).is_none());
+}
@@ -0,0 +1,118 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::low_level::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_KEY, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+};
+use types::{CLType, Key};
+
+const CONTRACT_CONTRACT_ABI: &str = "contract_abi.wasm";
+const CONTRACT_NAME: &str = "contract_abi";
+const ENTRY_POINT_DOUBLE: &str = "double";
+const STANDARD_PAYMENT_CONTRACT: &str = "standard_payment.wasm";
+
+const CASE_MATCHING: u8 = 0;
+const CASE_WRONG_ARGS: u8 = 1;
+const CASE_WRONG_RET: u8 = 2;
+const CASE_DUPLICATE_ENTRY_POINT: u8 = 3;
+
+fn store_contract(case: u8) -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CONTRACT_ABI, (case,))
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_call_contract_with_args_matching_abi() {
+    let builder = store_contract(CASE_MATCHING);
+    assert!(!builder.is_error(), "{:?}", builder.exec_error_message(0));
+}
+
+#[ignore]
+#[test]
+fn should_reject_call_contract_args_not_matching_abi() {
+    let builder = store_contract(CASE_WRONG_ARGS);
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(error_message.contains("InvalidArgs"), "{}", error_message);
+}
+
+#[ignore]
+#[test]
+fn should_reject_return_value_not_matching_abi() {
+    let builder = store_contract(CASE_WRONG_RET);
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("InvalidReturnValue"),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_abi_with_duplicate_entry_points() {
+    let builder = store_contract(CASE_DUPLICATE_ENTRY_POINT);
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("DuplicateEntryPoint"),
+        "{}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_args_not_matching_abi() {
+    let mut builder = store_contract(CASE_MATCHING);
+
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_session_named_key(CONTRACT_NAME, ("twenty one",))
+        .with_session_entry_point(ENTRY_POINT_DOUBLE)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_KEY])
+        .with_deploy_hash([2; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have error message");
+    assert!(error_message.contains("InvalidArgs"), "{}", error_message);
+}
+
+#[ignore]
+#[test]
+fn should_return_abi_with_queried_contract() {
+    let builder = store_contract(CASE_MATCHING);
+
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[CONTRACT_NAME])
+        .expect("should query contract");
+    let contract = match stored_value {
+        StoredValue::Contract(contract) => contract,
+        _ => panic!("expected a contract, got {:?}", stored_value),
+    };
+    let entry_point = contract
+        .abi()
+        .and_then(|abi| abi.entry_point(ENTRY_POINT_DOUBLE))
+        .expect("should have ABI of entry point");
+    assert_eq!(entry_point.parameters().len(), 1);
+    assert_eq!(entry_point.parameters()[0].name(), "value");
+    assert_eq!(entry_point.parameters()[0].cl_type(), &CLType::U64);
+    assert_eq!(entry_point.ret(), &CLType::U64);
+}
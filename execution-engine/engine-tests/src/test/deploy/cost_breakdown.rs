@@ -0,0 +1,43 @@
+use engine_core::engine_state::CONV_RATE;
+use engine_shared::motes::Motes;
+use engine_test_support::low_level::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+};
+use types::Phase;
+
+const DO_NOTHING_CONTRACT_NAME: &str = "do_nothing.wasm";
+
+#[ignore]
+#[test]
+fn should_report_gas_and_motes_per_phase() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, DO_NOTHING_CONTRACT_NAME, ()).build();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let result = utils::get_success_result(response);
+    let cost_breakdown = result.cost_breakdown();
+
+    assert_eq!(
+        cost_breakdown.gas(Phase::Payment) + cost_breakdown.gas(Phase::Session),
+        result.cost()
+    );
+    assert_eq!(cost_breakdown.gas(Phase::System), Default::default());
+
+    let motes_charged = Motes::from_gas(result.cost(), CONV_RATE).expect("should have motes");
+    assert_eq!(cost_breakdown.motes_charged(), motes_charged);
+    assert_eq!(
+        cost_breakdown.motes_charged() + cost_breakdown.motes_refunded(),
+        Motes::new(*DEFAULT_PAYMENT)
+    );
+}
@@ -0,0 +1,123 @@
+use engine_test_support::low_level::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_GENESIS_CONFIG,
+};
+use types::CLValue;
+
+const CONTRACT_EMIT_EVENT: &str = "emit_event.wasm";
+const CONTRACT_EMIT_EVENT_STORED: &str = "emit_event_stored.wasm";
+const CONTRACT_EMIT_EVENT_CALLER: &str = "emit_event_caller.wasm";
+const EVENT_VALUE: &str = "value";
+
+#[ignore]
+#[test]
+fn should_return_events_in_order() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_EVENT,
+        (EVENT_VALUE.to_string(), false),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let events = utils::get_success_result(response).events();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].name(), "first");
+    assert_eq!(events[0].value(), &CLValue::from_t(1u64).unwrap());
+    assert_eq!(events[1].name(), "second");
+    assert_eq!(
+        events[1].value(),
+        &CLValue::from_t(EVENT_VALUE.to_string()).unwrap()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_drop_events_of_failed_session() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_EVENT,
+        (EVENT_VALUE.to_string(), true),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let result = utils::get_success_result(response);
+    assert!(result.is_failure());
+    assert!(result.events().is_empty());
+}
+
+/// Stores the contract emitting the "callee" event, then has the caller emit "first", call it and
+/// emit "second".  The caller's deploy is the second one executed by the returned builder.
+fn exec_caller(callee_reverts: bool) -> InMemoryWasmTestBuilder {
+    let store_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_EMIT_EVENT_STORED, ())
+            .build();
+    let call_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_EMIT_EVENT_CALLER,
+        (EVENT_VALUE.to_string(), callee_reverts),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(store_request)
+        .expect_success()
+        .commit()
+        .exec(call_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_return_events_of_called_contract_in_order() {
+    let builder = exec_caller(false);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let events = utils::get_success_result(response).events();
+
+    let names: Vec<&str> = events.iter().map(|event| event.name()).collect();
+    assert_eq!(names, vec!["first", "callee", "second"]);
+    assert_eq!(
+        events[1].value(),
+        &CLValue::from_t(EVENT_VALUE.to_string()).unwrap()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_drop_events_of_reverted_called_contract() {
+    let builder = exec_caller(true);
+
+    let response = builder
+        .get_exec_response(1)
+        .expect("there should be a response");
+    let events = utils::get_success_result(response).events();
+
+    let names: Vec<&str> = events.iter().map(|event| event.name()).collect();
+    assert_eq!(names, vec!["first", "second"]);
+}
@@ -0,0 +1,163 @@
+use engine_core::engine_state::{deploy_item::DeployItem, Error, CONV_RATE};
+use engine_shared::{motes::Motes, newtypes::CorrelationId};
+use engine_test_support::low_level::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_KEY, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+};
+use types::{account::PublicKey, ProtocolVersion, U512};
+
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const STANDARD_PAYMENT_CONTRACT_NAME: &str = "standard_payment";
+const STORE_AT_HASH: &str = "hash";
+const TRANSFER_PURSE_TO_ACCOUNT_CONTRACT_NAME: &str = "transfer_purse_to_account";
+const TRANSFERRED_AMOUNT: u64 = 1;
+
+fn session_builder(deploy_hash: [u8; 32]) -> DeployItemBuilder {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            &format!("{}.wasm", TRANSFER_PURSE_TO_ACCOUNT_CONTRACT_NAME),
+            (
+                PublicKey::new(ACCOUNT_1_ADDR),
+                U512::from(TRANSFERRED_AMOUNT),
+            ),
+        )
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_KEY])
+        .with_deploy_hash(deploy_hash)
+}
+
+/// Estimates `deploy`, then executes and commits it as the builder's `exec_index`th request,
+/// checking that the estimate matches the cost charged and did not itself change global state.
+fn assert_estimate_matches_execution(
+    builder: &mut InMemoryWasmTestBuilder,
+    deploy: DeployItem,
+    exec_index: usize,
+) {
+    let pre_state_hash = builder.get_post_state_hash();
+    let make_exec_request = |deploy| {
+        ExecuteRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_pre_state_hash(&pre_state_hash)
+            .build()
+    };
+
+    let estimates = builder
+        .get_engine_state()
+        .estimate_gas(CorrelationId::new(), make_exec_request(deploy.clone()))
+        .expect("should estimate gas");
+    assert_eq!(estimates.len(), 1);
+    let estimate = &estimates[0];
+    assert!(estimate.is_success(), "{:?}", estimate.error());
+    assert!(estimate.payment_cost().value() > U512::zero());
+    assert!(estimate.session_cost().value() > U512::zero());
+    assert_eq!(builder.get_post_state_hash(), pre_state_hash);
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+
+    builder
+        .exec(make_exec_request(deploy))
+        .expect_success()
+        .commit();
+
+    let exec_cost = *builder
+        .exec_costs(exec_index)
+        .first()
+        .expect("should have cost");
+    assert_eq!(estimate.cost(), exec_cost);
+    assert_eq!(
+        estimate.suggested_payment(),
+        Motes::from_gas(exec_cost, CONV_RATE)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_estimate_gas_of_module_bytes() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let deploy = session_builder([1; 32])
+        .with_payment_code(
+            &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
+            (*DEFAULT_PAYMENT,),
+        )
+        .build();
+
+    assert_estimate_matches_execution(&mut builder, deploy, 0);
+}
+
+#[ignore]
+#[test]
+fn should_estimate_gas_of_stored_contract() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        &format!("{}_stored.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
+        (STORE_AT_HASH.to_string(),),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let deploy = session_builder([2; 32])
+        .with_stored_payment_named_key(STANDARD_PAYMENT_CONTRACT_NAME, (*DEFAULT_PAYMENT,))
+        .build();
+
+    assert_estimate_matches_execution(&mut builder, deploy, 1);
+}
+
+#[ignore]
+#[test]
+fn should_estimate_gas_beyond_payment() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    // The payment covers nothing, yet both phases are run to completion.
+    let deploy = session_builder([3; 32])
+        .with_payment_code(
+            &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
+            (U512::from(1),),
+        )
+        .build();
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_pre_state_hash(&builder.get_post_state_hash())
+        .build();
+
+    let estimates = builder
+        .get_engine_state()
+        .estimate_gas(CorrelationId::new(), exec_request)
+        .expect("should estimate gas");
+
+    assert!(estimates[0].is_success());
+    assert!(estimates[0].session_cost().value() > U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_fail_estimate_of_unknown_protocol_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let deploy = session_builder([4; 32])
+        .with_payment_code(
+            &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
+            (*DEFAULT_PAYMENT,),
+        )
+        .build();
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_pre_state_hash(&builder.get_post_state_hash())
+        .with_protocol_version(ProtocolVersion::from_parts(99, 0, 0))
+        .build();
+
+    let estimates = builder
+        .get_engine_state()
+        .estimate_gas(CorrelationId::new(), exec_request)
+        .expect("should estimate gas");
+
+    match estimates[0].error() {
+        Some(Error::InvalidProtocolVersion(_)) => (),
+        other => panic!("expected an invalid protocol version, got {:?}", other),
+    }
+}
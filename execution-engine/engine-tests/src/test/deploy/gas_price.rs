@@ -0,0 +1,157 @@
+use engine_core::engine_state::{upgrade::ActivationPoint, CONV_RATE};
+use engine_shared::{conv_rates::ConvRates, motes::Motes};
+use engine_test_support::low_level::{
+    utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+    UpgradeRequestBuilder, DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+    DEFAULT_PROTOCOL_VERSION, STANDARD_PAYMENT_CONTRACT,
+};
+use types::{account::PublicKey, ProtocolVersion, U512};
+
+const DO_NOTHING_CONTRACT_NAME: &str = "do_nothing.wasm";
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const UPGRADED_MIN_CONV_RATE: u64 = 2 * CONV_RATE;
+const UPGRADED_DEFAULT_CONV_RATE: u64 = 3 * CONV_RATE;
+
+fn do_nothing_at_gas_price(
+    gas_price: u64,
+    protocol_version: ProtocolVersion,
+) -> ExecuteRequestBuilder {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(DO_NOTHING_CONTRACT_NAME, ())
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[PublicKey::new(DEFAULT_ACCOUNT_ADDR)])
+        .with_gas_price(gas_price)
+        .with_deploy_hash([1; 32])
+        .build();
+    ExecuteRequestBuilder::new()
+        .push_deploy(deploy)
+        .with_protocol_version(protocol_version)
+}
+
+/// Runs genesis, then upgrades to a protocol version with higher conversion rates, which it
+/// returns.
+fn upgrade_conv_rates(builder: &mut InMemoryWasmTestBuilder) -> ProtocolVersion {
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_conv_rates(ConvRates::new(
+            UPGRADED_MIN_CONV_RATE,
+            UPGRADED_DEFAULT_CONV_RATE,
+        ))
+        .build();
+
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    new_protocol_version
+}
+
+/// Executes and commits the built request, returning the motes paid by the default account.
+fn exec_and_get_motes_paid(
+    builder: &mut InMemoryWasmTestBuilder,
+    exec_request_builder: ExecuteRequestBuilder,
+) -> U512 {
+    let purse_id = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .purse_id();
+    let balance_before = builder.get_purse_balance(purse_id);
+
+    builder
+        .exec(exec_request_builder.build())
+        .expect_success()
+        .commit();
+
+    balance_before - builder.get_purse_balance(purse_id)
+}
+
+#[ignore]
+#[test]
+fn should_pay_at_default_conv_rate_without_gas_price() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let motes_paid = exec_and_get_motes_paid(
+        &mut builder,
+        do_nothing_at_gas_price(0, *DEFAULT_PROTOCOL_VERSION),
+    );
+
+    let cost = Motes::from_gas(builder.exec_costs(0)[0], CONV_RATE).expect("should have motes");
+    assert_eq!(motes_paid, cost.value());
+}
+
+#[ignore]
+#[test]
+fn should_pay_at_deploy_gas_price() {
+    let gas_price = 5 * CONV_RATE;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let motes_paid = exec_and_get_motes_paid(
+        &mut builder,
+        do_nothing_at_gas_price(gas_price, *DEFAULT_PROTOCOL_VERSION),
+    );
+
+    let cost = Motes::from_gas(builder.exec_costs(0)[0], gas_price).expect("should have motes");
+    assert_eq!(motes_paid, cost.value());
+}
+
+#[ignore]
+#[test]
+fn should_pay_at_upgraded_default_conv_rate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let new_protocol_version = upgrade_conv_rates(&mut builder);
+
+    let conv_rates = builder
+        .get_engine_state()
+        .conv_rates(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded conversion rates");
+    assert_eq!(
+        conv_rates,
+        ConvRates::new(UPGRADED_MIN_CONV_RATE, UPGRADED_DEFAULT_CONV_RATE)
+    );
+
+    let motes_paid = exec_and_get_motes_paid(
+        &mut builder,
+        do_nothing_at_gas_price(0, new_protocol_version),
+    );
+
+    let cost = Motes::from_gas(builder.exec_costs(0)[0], UPGRADED_DEFAULT_CONV_RATE)
+        .expect("should have motes");
+    assert_eq!(motes_paid, cost.value());
+}
+
+#[ignore]
+#[test]
+fn should_reject_gas_price_below_upgraded_minimum() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let new_protocol_version = upgrade_conv_rates(&mut builder);
+
+    let exec_request = do_nothing_at_gas_price(CONV_RATE, new_protocol_version).build();
+    builder.exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let precondition_failure = utils::get_precondition_failure(response);
+    assert_eq!(
+        precondition_failure,
+        format!(
+            "Gas price {} is below the minimum of {}",
+            CONV_RATE, UPGRADED_MIN_CONV_RATE
+        )
+    );
+}
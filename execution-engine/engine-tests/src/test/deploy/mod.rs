@@ -1,3 +1,14 @@
+mod call_contract_gas_limit;
+mod chained_execution;
+mod contract_abi;
+mod cost_breakdown;
+mod events;
+mod gas_estimation;
+mod gas_price;
+mod named_entry_points;
+mod parallel_execution;
 mod payment_code;
 mod preconditions;
 mod stored_contracts;
+mod trace;
+mod try_call_contract;
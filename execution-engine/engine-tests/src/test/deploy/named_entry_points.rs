@@ -0,0 +1,87 @@
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_test_support::low_level::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_KEY, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+};
+
+const CONTRACT_CALL_CONTRACT_LEGACY: &str = "call_contract_legacy.wasm";
+const CONTRACT_NAMED_ENTRY_POINTS: &str = "named_entry_points.wasm";
+const CONTRACT_NAME: &str = "named_entry_points";
+const ENTRY_POINT_FIRST: &str = "first";
+const ENTRY_POINT_SECOND: &str = "second";
+const STANDARD_PAYMENT_CONTRACT: &str = "standard_payment.wasm";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_NAMED_ENTRY_POINTS, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_entry_point(entry_point: &str) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_stored_session_named_key(CONTRACT_NAME, ())
+        .with_session_entry_point(entry_point)
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_KEY])
+        .with_deploy_hash([2; 32])
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+#[ignore]
+#[test]
+fn should_run_selected_entry_point() {
+    let mut builder = setup();
+    builder
+        .exec(call_entry_point(ENTRY_POINT_SECOND))
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account.named_keys().contains_key(ENTRY_POINT_FIRST));
+    assert!(account.named_keys().contains_key(ENTRY_POINT_SECOND));
+}
+
+#[ignore]
+#[test]
+fn should_fail_on_unknown_entry_point() {
+    let mut builder = setup();
+    builder.exec(call_entry_point("third")).commit();
+
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_fail_on_default_entry_point_not_stored() {
+    let mut builder = setup();
+    builder.exec(call_entry_point("call")).commit();
+
+    assert!(builder.is_error());
+}
+
+#[ignore]
+#[test]
+fn should_call_default_entry_point_through_legacy_call_contract() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_CALL_CONTRACT_LEGACY, ())
+            .build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+}
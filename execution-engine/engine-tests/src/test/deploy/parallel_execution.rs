@@ -0,0 +1,123 @@
+use engine_core::engine_state::{
+    conflicts::DeployConflicts, deploy_item::DeployItem, execute_request::ExecuteRequest,
+    execution_result::ExecutionResult, EngineConfig, RenderedError,
+};
+use engine_shared::newtypes::CorrelationId;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::low_level::{
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_KEY, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
+};
+use types::account::PublicKey;
+
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const ACCOUNT_2_ADDR: [u8; 32] = [2u8; 32];
+const UNKNOWN_ADDR: [u8; 32] = [99u8; 32];
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account.wasm";
+const EXECUTION_THREADS: usize = 3;
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+fn transfer_deploy(from: [u8; 32], to: [u8; 32], deploy_hash: [u8; 32]) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(from)
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            (PublicKey::new(to), TRANSFER_AMOUNT),
+        )
+        .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_KEY])
+        .with_deploy_hash(deploy_hash)
+        .build()
+}
+
+fn make_exec_request(pre_state_hash: &[u8]) -> ExecuteRequest {
+    ExecuteRequestBuilder::new()
+        .push_deploy(transfer_deploy(
+            DEFAULT_ACCOUNT_ADDR,
+            ACCOUNT_1_ADDR,
+            [1; 32],
+        ))
+        .push_deploy(transfer_deploy(
+            DEFAULT_ACCOUNT_ADDR,
+            ACCOUNT_2_ADDR,
+            [2; 32],
+        ))
+        .push_deploy(transfer_deploy(UNKNOWN_ADDR, ACCOUNT_1_ADDR, [3; 32]))
+        .push_deploy(transfer_deploy(
+            DEFAULT_ACCOUNT_ADDR,
+            ACCOUNT_1_ADDR,
+            [4; 32],
+        ))
+        .with_pre_state_hash(pre_state_hash)
+        .build()
+}
+
+fn assert_same_result(sequential: &ExecutionResult, parallel: &ExecutionResult) {
+    assert_eq!(sequential.effect(), parallel.effect());
+    assert_eq!(sequential.cost(), parallel.cost());
+    match (sequential, parallel) {
+        (ExecutionResult::Success { .. }, ExecutionResult::Success { .. }) => (),
+        (
+            ExecutionResult::Failure {
+                error: sequential_error,
+                ..
+            },
+            ExecutionResult::Failure {
+                error: parallel_error,
+                ..
+            },
+        ) => assert_eq!(
+            RenderedError::from(sequential_error),
+            RenderedError::from(parallel_error)
+        ),
+        _ => panic!(
+            "results differ: {:?} and {:?}",
+            sequential.is_success(),
+            parallel.is_success()
+        ),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_execute_in_parallel_with_same_results_as_sequentially() {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root_hash = global_state.empty_root_hash;
+    let engine_config = EngineConfig::new().with_execution_threads(EXECUTION_THREADS);
+
+    let mut builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root_hash.to_vec());
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let pre_state_hash = builder.get_post_state_hash();
+    let engine_state = builder.get_engine_state();
+
+    let sequential_results = engine_state
+        .run_execute(CorrelationId::new(), make_exec_request(&pre_state_hash))
+        .expect("should execute sequentially");
+    let parallel_results = engine_state
+        .run_execute_parallel(CorrelationId::new(), make_exec_request(&pre_state_hash))
+        .expect("should execute in parallel");
+
+    assert_eq!(sequential_results.len(), parallel_results.len());
+    for (sequential, parallel) in sequential_results.iter().zip(parallel_results.iter()) {
+        assert_same_result(sequential, parallel);
+    }
+
+    assert!(sequential_results[0].is_success());
+    assert!(sequential_results[1].is_success());
+    assert!(sequential_results[2].is_failure());
+    assert!(sequential_results[3].is_success());
+
+    let conflicts = DeployConflicts::from_results(&parallel_results);
+    assert_eq!(
+        conflicts,
+        DeployConflicts::from_results(&sequential_results)
+    );
+    assert_eq!(conflicts.deploy_count(), 4);
+    // Transfers from the same account all write its main purse balance.
+    assert!(!conflicts.commute(0, 1));
+    assert!(!conflicts.commute(0, 3));
+    // A precondition failure has no effects.
+    assert_eq!(conflicts.conflicting_with(2).count(), 0);
+}
@@ -0,0 +1,115 @@
+use engine_core::{execution::HostCallArg, resolvers::v1_function_index::FunctionIndex};
+use engine_test_support::low_level::{
+    utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_GENESIS_CONFIG,
+};
+use types::{CLValue, Key, U512};
+
+const CONTRACT_REVERT: &str = "revert.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT_01: &str = "transfer_to_account_01.wasm";
+const ACCOUNT_1_ADDR: [u8; 32] = [1u8; 32];
+const TRANSFER_AMOUNT: u64 = 250_000_000 + 1000;
+
+#[ignore]
+#[test]
+fn should_not_trace_by_default() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_REVERT, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    assert!(utils::get_success_result(response).trace().is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_trace_each_phase_up_to_revert() {
+    let exec_request = ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_REVERT, ())
+        .with_trace(true)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let result = utils::get_success_result(response);
+    assert!(result.is_failure());
+
+    // Payment, session and finalization were each run.
+    let trace = result.trace();
+    assert_eq!(trace.len(), 3);
+
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(trace[0].base_key(), account_key);
+    assert_eq!(trace[1].base_key(), account_key);
+
+    let session_calls = trace[1].host_calls();
+    let last_call = session_calls.last().expect("session should call the host");
+    assert_eq!(last_call.function(), FunctionIndex::RevertFuncIndex);
+    assert!(last_call.gas_used() <= result.cost());
+
+    // The gas used only ever grows.
+    assert!(session_calls
+        .windows(2)
+        .all(|calls| calls[0].gas_used() <= calls[1].gas_used()));
+}
+
+#[ignore]
+#[test]
+fn should_trace_nested_calls_and_touched_keys() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT_01,
+        (ACCOUNT_1_ADDR,),
+    )
+    .with_trace(true)
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    let trace = utils::get_success_result(response).trace();
+
+    let transfer_call = trace[1]
+        .host_calls()
+        .iter()
+        .find(|host_call| host_call.function() == FunctionIndex::TransferToAccountIndex)
+        .expect("session should transfer");
+    assert!(transfer_call.keys().contains(&Key::Account(ACCOUNT_1_ADDR)));
+    assert_eq!(
+        transfer_call.args(),
+        &[
+            HostCallArg::Key(Key::Account(ACCOUNT_1_ADDR)),
+            HostCallArg::Value(CLValue::from_t(U512::from(TRANSFER_AMOUNT)).unwrap()),
+        ]
+    );
+
+    // The purse of the new account is created and funded by the mint.
+    let mint_key = Key::from(builder.get_mint_contract_uref()).normalize();
+    assert!(!transfer_call.calls().is_empty());
+    for mint_frame in transfer_call.calls() {
+        assert_eq!(mint_frame.base_key().normalize(), mint_key);
+        assert!(!mint_frame.host_calls().is_empty());
+    }
+
+    // Finalization is run by the proof of stake contract.
+    let pos_key = Key::from(builder.get_pos_contract_uref()).normalize();
+    assert_eq!(trace[2].base_key().normalize(), pos_key);
+}
@@ -5,3 +5,4 @@ mod metrics;
 mod regression;
 mod system_contracts;
 mod upgrade;
+mod view;
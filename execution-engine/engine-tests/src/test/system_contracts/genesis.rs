@@ -3,7 +3,10 @@ use engine_core::engine_state::{
     SYSTEM_ACCOUNT_ADDR,
 };
 use engine_shared::{motes::Motes, stored_value::StoredValue};
-use engine_test_support::low_level::{utils, InMemoryWasmTestBuilder, DEFAULT_WASM_COSTS};
+use engine_test_support::low_level::{
+    utils, InMemoryWasmTestBuilder, DEFAULT_CONV_RATES, DEFAULT_HOST_FUNCTION_COSTS,
+    DEFAULT_STORAGE_COSTS, DEFAULT_WASM_COSTS,
+};
 use types::{account::PublicKey, Key, ProtocolVersion, U512};
 
 const MINT_INSTALL: &str = "mint_install.wasm";
@@ -50,6 +53,9 @@ fn should_run_genesis() {
     let accounts = vec![account_1, account_2];
     let protocol_version = ProtocolVersion::V1_0_0;
     let wasm_costs = *DEFAULT_WASM_COSTS;
+    let host_function_costs = *DEFAULT_HOST_FUNCTION_COSTS;
+    let storage_costs = *DEFAULT_STORAGE_COSTS;
+    let conv_rates = *DEFAULT_CONV_RATES;
 
     let genesis_config = GenesisConfig::new(
         name,
@@ -59,6 +65,9 @@ fn should_run_genesis() {
         pos_installer_bytes,
         accounts,
         wasm_costs,
+        host_function_costs,
+        storage_costs,
+        conv_rates,
     );
 
     let mut builder = InMemoryWasmTestBuilder::default();
@@ -133,6 +142,9 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
         let accounts = vec![account_1, account_2];
         let protocol_version = ProtocolVersion::V1_0_0;
         let wasm_costs = *DEFAULT_WASM_COSTS;
+        let host_function_costs = *DEFAULT_HOST_FUNCTION_COSTS;
+        let storage_costs = *DEFAULT_STORAGE_COSTS;
+        let conv_rates = *DEFAULT_CONV_RATES;
 
         GenesisConfig::new(
             name,
@@ -142,6 +154,9 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
             pos_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            conv_rates,
         )
     };
 
@@ -181,6 +196,9 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
         let accounts = vec![account_1, account_2];
         let protocol_version = ProtocolVersion::V1_0_0;
         let wasm_costs = *DEFAULT_WASM_COSTS;
+        let host_function_costs = *DEFAULT_HOST_FUNCTION_COSTS;
+        let storage_costs = *DEFAULT_STORAGE_COSTS;
+        let conv_rates = *DEFAULT_CONV_RATES;
 
         GenesisConfig::new(
             name,
@@ -190,6 +208,9 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
             pos_installer_bytes,
             accounts,
             wasm_costs,
+            host_function_costs,
+            storage_costs,
+            conv_rates,
         )
     };
 
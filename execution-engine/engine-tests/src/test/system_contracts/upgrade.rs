@@ -3,9 +3,9 @@ use engine_grpc_server::engine_server::ipc::DeployCode;
 use engine_shared::{stored_value::StoredValue, transform::Transform};
 use engine_test_support::low_level::{
     utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
-    DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_WASM_COSTS,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_GENESIS_CONFIG, DEFAULT_HOST_FUNCTION_COSTS, DEFAULT_WASM_COSTS,
 };
-use engine_wasm_prep::wasm_costs::WasmCosts;
+use engine_wasm_prep::{host_function_costs::HostFunctionCosts, wasm_costs::WasmCosts};
 use types::{CLValue, Key, ProtocolVersion, U512};
 
 const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
@@ -13,6 +13,7 @@ const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
 const MODIFIED_SYSTEM_UPGRADER_CONTRACT_NAME: &str = "modified_system_upgrader.wasm";
 const MODIFIED_MINT_CALLER_CONTRACT_NAME: &str = "modified_mint_caller.wasm";
 const PAYMENT_AMOUNT: u64 = 200_000_000;
+const DO_NOTHING_CONTRACT_NAME: &str = "do_nothing.wasm";
 
 fn get_upgraded_wasm_costs() -> WasmCosts {
     WasmCosts {
@@ -473,6 +474,77 @@ fn should_upgrade_system_contract_and_wasm_costs_major() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_upgrade_host_function_costs() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&*DEFAULT_GENESIS_CONFIG);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    // host functions are free after the upgrade
+    let new_host_function_costs = HostFunctionCosts::default();
+    assert_ne!(new_host_function_costs, *DEFAULT_HOST_FUNCTION_COSTS);
+
+    let mut upgrade_request = {
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_new_host_function_costs(new_host_function_costs)
+            .build()
+    };
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let engine_state = builder.get_engine_state();
+    let upgraded_host_function_costs = engine_state
+        .host_function_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have upgraded costs");
+    let upgraded_wasm_costs = engine_state
+        .wasm_costs(new_protocol_version)
+        .expect("should have result")
+        .expect("should have costs");
+
+    assert_eq!(
+        new_host_function_costs, upgraded_host_function_costs,
+        "upgraded host function costs should equal new costs"
+    );
+    assert_eq!(
+        *DEFAULT_WASM_COSTS, upgraded_wasm_costs,
+        "upgraded wasm costs should equal original costs"
+    );
+
+    let exec_request_1 =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, DO_NOTHING_CONTRACT_NAME, ()).build();
+    let exec_request_2 =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, DO_NOTHING_CONTRACT_NAME, ())
+            .with_protocol_version(new_protocol_version)
+            .build();
+
+    builder.exec(exec_request_1).expect_success().commit();
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let cost_before = builder.get_exec_response(0).expect("should have response")[0].cost();
+    let cost_after = builder.get_exec_response(1).expect("should have response")[0].cost();
+
+    // standard payment calls host functions, which are only charged before the upgrade
+    assert!(
+        cost_after < cost_before,
+        "host function calls should no longer be charged"
+    );
+}
+
 #[ignore]
 #[test]
 fn should_not_downgrade() {
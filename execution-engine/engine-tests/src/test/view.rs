@@ -0,0 +1,162 @@
+use std::convert::TryInto;
+
+use contract::args_parser::ArgsParser;
+use engine_core::{
+    engine_state::{
+        view::{ViewRequest, ViewResult},
+        Error,
+    },
+    execution,
+};
+use engine_shared::{gas::Gas, newtypes::CorrelationId};
+use engine_test_support::low_level::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_ACCOUNT_KEY, DEFAULT_BLOCK_TIME,
+    DEFAULT_GENESIS_CONFIG, DEFAULT_PROTOCOL_VERSION,
+};
+use types::{bytesrepr::ToBytes, BlockTime, Key, DEFAULT_ENTRY_POINT, U512};
+
+const CONTRACT_EMIT_EVENT_STORED: &str = "emit_event_stored.wasm";
+const EMIT_EVENT_CONTRACT_NAME: &str = "emit_event";
+const EMIT_EVENT_ENTRY_POINT: &str = "emit";
+const GAS_LIMIT: u64 = 10_000_000;
+
+fn view(
+    builder: &InMemoryWasmTestBuilder,
+    contract_key: Key,
+    entry_point: &str,
+    args: impl ArgsParser,
+    gas_limit: u64,
+) -> ViewResult {
+    let state_hash = builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should be a valid hash");
+    let args = args
+        .parse_to_vec_u8()
+        .expect("should convert to `Vec<CLValue>`")
+        .into_bytes()
+        .expect("should serialize args");
+    let view_request = ViewRequest::new(
+        state_hash,
+        contract_key,
+        args,
+        *DEFAULT_ACCOUNT_KEY,
+        BlockTime::new(DEFAULT_BLOCK_TIME),
+        Gas::new(gas_limit.into()),
+        *DEFAULT_PROTOCOL_VERSION,
+    )
+    .with_entry_point(entry_point.to_string());
+    builder
+        .get_engine_state()
+        .run_view(CorrelationId::new(), view_request)
+        .expect("should run view")
+}
+
+fn view_mint(
+    builder: &InMemoryWasmTestBuilder,
+    args: impl ArgsParser,
+    gas_limit: u64,
+) -> ViewResult {
+    let mint_key = Key::URef(builder.get_mint_contract_uref());
+    view(builder, mint_key, DEFAULT_ENTRY_POINT, args, gas_limit)
+}
+
+#[ignore]
+#[test]
+fn should_return_value_from_view() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .purse_id()
+        .value();
+
+    match view_mint(&builder, ("balance", purse), GAS_LIMIT) {
+        ViewResult::Success { value, cost } => {
+            let balance: Option<U512> = value.into_t().expect("should be a balance");
+            assert_eq!(balance, Some(U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)));
+            assert!(cost.value() > U512::zero());
+        }
+        other => panic!("unexpected view result: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_reject_writes_in_view() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+    let post_state_hash = builder.get_post_state_hash();
+
+    // Creating a purse stores its balance.
+    match view_mint(&builder, ("create",), GAS_LIMIT) {
+        ViewResult::Failure {
+            error: Error::ExecError(execution::Error::WriteInReadOnlyContext),
+            ..
+        } => (),
+        other => panic!("unexpected view result: {:?}", other),
+    }
+
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+}
+
+#[ignore]
+#[test]
+fn should_reject_events_in_view() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_EMIT_EVENT_STORED, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_GENESIS_CONFIG)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let contract_key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(EMIT_EVENT_CONTRACT_NAME)
+        .expect("should have stored contract");
+
+    match view(
+        &builder,
+        contract_key,
+        EMIT_EVENT_ENTRY_POINT,
+        ("value".to_string(), false),
+        GAS_LIMIT,
+    ) {
+        ViewResult::Failure {
+            error: Error::ExecError(execution::Error::WriteInReadOnlyContext),
+            ..
+        } => (),
+        other => panic!("unexpected view result: {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_stop_view_at_gas_limit() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_GENESIS_CONFIG);
+
+    let purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .purse_id()
+        .value();
+
+    match view_mint(&builder, ("balance", purse), 1) {
+        ViewResult::Failure {
+            error: Error::ExecError(execution::Error::GasLimit),
+            ..
+        } => (),
+        other => panic!("unexpected view result: {:?}", other),
+    }
+}
@@ -0,0 +1,256 @@
+use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+
+const NUM_FIELDS: usize = 42;
+pub const HOST_FUNCTION_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+
+/// Gas charged for calls of host functions, on top of the metered Wasm instructions.
+///
+/// The `gas` host function, which does the metering itself, is free.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct HostFunctionCosts {
+    /// Cost of `read_value`, in addition to the per-byte cost of the value read
+    pub read_value: u32,
+    /// Cost of `read_value_local`, in addition to the per-byte cost of the value read
+    pub read_value_local: u32,
+    /// Cost of `write`, in addition to the storage cost of the value written
+    pub write: u32,
+    /// Cost of `write_local`, in addition to the storage cost of the value written
+    pub write_local: u32,
+    /// Cost of `add`
+    pub add: u32,
+    /// Cost of `add_local`
+    pub add_local: u32,
+    /// Cost of `new_uref`, in addition to the storage cost of the initial value
+    pub new_uref: u32,
+    /// Cost of `load_named_keys`
+    pub load_named_keys: u32,
+    /// Cost of `ret`
+    pub ret: u32,
+    /// Cost of `get_key`
+    pub get_key: u32,
+    /// Cost of `has_key`
+    pub has_key: u32,
+    /// Cost of `put_key`
+    pub put_key: u32,
+    /// Cost of `remove_key`
+    pub remove_key: u32,
+    /// Cost of `call_contract` or `call_contract_entry_point`, not including the cost of the
+    /// called contract
+    pub call_contract: u32,
+    /// Cost of `get_arg_size`
+    pub get_arg_size: u32,
+    /// Cost of `get_arg`
+    pub get_arg: u32,
+    /// Cost of `get_caller`
+    pub get_caller: u32,
+    /// Cost of `get_blocktime`
+    pub get_blocktime: u32,
+    /// Cost of `store_function`
+    pub store_function: u32,
+    /// Cost of `store_function_at_hash`
+    pub store_function_at_hash: u32,
+    /// Cost of `is_valid_uref`
+    pub is_valid_uref: u32,
+    /// Cost of `revert`
+    pub revert: u32,
+    /// Cost of `add_associated_key`
+    pub add_associated_key: u32,
+    /// Cost of `remove_associated_key`
+    pub remove_associated_key: u32,
+    /// Cost of `update_associated_key`
+    pub update_associated_key: u32,
+    /// Cost of `set_action_threshold`
+    pub set_action_threshold: u32,
+    /// Cost of `create_purse`
+    pub create_purse: u32,
+    /// Cost of `transfer_to_account`
+    pub transfer_to_account: u32,
+    /// Cost of `transfer_from_purse_to_account`
+    pub transfer_from_purse_to_account: u32,
+    /// Cost of `transfer_from_purse_to_purse`
+    pub transfer_from_purse_to_purse: u32,
+    /// Cost of `get_balance`
+    pub get_balance: u32,
+    /// Cost of `get_phase`
+    pub get_phase: u32,
+    /// Cost of `upgrade_contract_at_uref`
+    pub upgrade_contract_at_uref: u32,
+    /// Cost of `get_system_contract`
+    pub get_system_contract: u32,
+    /// Cost of `get_main_purse`
+    pub get_main_purse: u32,
+    /// Cost of `read_host_buffer`
+    pub read_host_buffer: u32,
+    /// Cost of `emit_event`, in addition to the per-byte cost of the event
+    pub emit_event: u32,
+    /// Cost of `store_contract`
+    pub store_contract: u32,
+    /// Cost of `store_contract_at_hash`
+    pub store_contract_at_hash: u32,
+    /// Cost of `try_call_contract`
+    pub try_call_contract: u32,
+    /// Cost per byte of a value read by `read_value` or `read_value_local`
+    pub read_value_byte: u32,
+    /// Cost per byte of the name and value of an event emitted by `emit_event`
+    pub event_byte: u32,
+}
+
+impl HostFunctionCosts {
+    fn to_array(&self) -> [u32; NUM_FIELDS] {
+        [
+            self.read_value,
+            self.read_value_local,
+            self.write,
+            self.write_local,
+            self.add,
+            self.add_local,
+            self.new_uref,
+            self.load_named_keys,
+            self.ret,
+            self.get_key,
+            self.has_key,
+            self.put_key,
+            self.remove_key,
+            self.call_contract,
+            self.get_arg_size,
+            self.get_arg,
+            self.get_caller,
+            self.get_blocktime,
+            self.store_function,
+            self.store_function_at_hash,
+            self.is_valid_uref,
+            self.revert,
+            self.add_associated_key,
+            self.remove_associated_key,
+            self.update_associated_key,
+            self.set_action_threshold,
+            self.create_purse,
+            self.transfer_to_account,
+            self.transfer_from_purse_to_account,
+            self.transfer_from_purse_to_purse,
+            self.get_balance,
+            self.get_phase,
+            self.upgrade_contract_at_uref,
+            self.get_system_contract,
+            self.get_main_purse,
+            self.read_host_buffer,
+            self.emit_event,
+            self.store_contract,
+            self.store_contract_at_hash,
+            self.try_call_contract,
+            self.read_value_byte,
+            self.event_byte,
+        ]
+    }
+
+    fn from_array(costs: [u32; NUM_FIELDS]) -> Self {
+        HostFunctionCosts {
+            read_value: costs[0],
+            read_value_local: costs[1],
+            write: costs[2],
+            write_local: costs[3],
+            add: costs[4],
+            add_local: costs[5],
+            new_uref: costs[6],
+            load_named_keys: costs[7],
+            ret: costs[8],
+            get_key: costs[9],
+            has_key: costs[10],
+            put_key: costs[11],
+            remove_key: costs[12],
+            call_contract: costs[13],
+            get_arg_size: costs[14],
+            get_arg: costs[15],
+            get_caller: costs[16],
+            get_blocktime: costs[17],
+            store_function: costs[18],
+            store_function_at_hash: costs[19],
+            is_valid_uref: costs[20],
+            revert: costs[21],
+            add_associated_key: costs[22],
+            remove_associated_key: costs[23],
+            update_associated_key: costs[24],
+            set_action_threshold: costs[25],
+            create_purse: costs[26],
+            transfer_to_account: costs[27],
+            transfer_from_purse_to_account: costs[28],
+            transfer_from_purse_to_purse: costs[29],
+            get_balance: costs[30],
+            get_phase: costs[31],
+            upgrade_contract_at_uref: costs[32],
+            get_system_contract: costs[33],
+            get_main_purse: costs[34],
+            read_host_buffer: costs[35],
+            emit_event: costs[36],
+            store_contract: costs[37],
+            store_contract_at_hash: costs[38],
+            try_call_contract: costs[39],
+            read_value_byte: costs[40],
+            event_byte: costs[41],
+        }
+    }
+}
+
+impl ToBytes for HostFunctionCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = Vec::with_capacity(HOST_FUNCTION_COSTS_SERIALIZED_LENGTH);
+        for cost in self.to_array().iter() {
+            ret.append(&mut cost.to_bytes()?);
+        }
+        Ok(ret)
+    }
+}
+
+impl FromBytes for HostFunctionCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let mut costs = [0u32; NUM_FIELDS];
+        let mut rem = bytes;
+        for cost in costs.iter_mut() {
+            let (value, remainder): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+            *cost = value;
+            rem = remainder;
+        }
+        Ok((HostFunctionCosts::from_array(costs), rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{collection, num, prelude::*};
+
+    use super::{HostFunctionCosts, NUM_FIELDS};
+
+    pub fn host_function_costs_arb() -> impl Strategy<Value = HostFunctionCosts> {
+        collection::vec(num::u32::ANY, NUM_FIELDS).prop_map(|values| {
+            let mut costs = [0u32; NUM_FIELDS];
+            costs.copy_from_slice(&values);
+            HostFunctionCosts::from_array(costs)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::test_utils;
+    use types::bytesrepr;
+
+    use super::{gens, HostFunctionCosts};
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let mock = test_utils::host_function_costs_mock();
+        let free = HostFunctionCosts::default();
+        bytesrepr::test_serialization_roundtrip(&mock);
+        bytesrepr::test_serialization_roundtrip(&free);
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            host_function_costs in gens::host_function_costs_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&host_function_costs);
+        }
+    }
+}
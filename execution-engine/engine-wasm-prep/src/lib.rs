@@ -1,3 +1,5 @@
+pub mod host_function_costs;
+pub mod storage_costs;
 pub mod wasm_costs;
 
 use std::fmt::{self, Display, Formatter};
@@ -0,0 +1,88 @@
+use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+
+const NUM_FIELDS: usize = 1;
+pub const STORAGE_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+
+/// Gas charged for the data a deploy stores in global state.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct StorageCosts {
+    /// Cost per byte of a value written to global state, or of a named key added to an account or
+    /// contract
+    pub gas_per_byte: u32,
+}
+
+impl StorageCosts {
+    pub fn new(gas_per_byte: u32) -> Self {
+        StorageCosts { gas_per_byte }
+    }
+
+    /// Returns the cost of storing `byte_size` bytes.
+    pub fn cost(&self, byte_size: usize) -> u64 {
+        u64::from(self.gas_per_byte).saturating_mul(byte_size as u64)
+    }
+}
+
+impl ToBytes for StorageCosts {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret: Vec<u8> = Vec::with_capacity(STORAGE_COSTS_SERIALIZED_LENGTH);
+        ret.append(&mut self.gas_per_byte.to_bytes()?);
+        Ok(ret)
+    }
+}
+
+impl FromBytes for StorageCosts {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (gas_per_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((StorageCosts { gas_per_byte }, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::{num, prop_compose};
+
+    use crate::storage_costs::StorageCosts;
+
+    prop_compose! {
+        pub fn storage_costs_arb()(gas_per_byte in num::u32::ANY) -> StorageCosts {
+            StorageCosts { gas_per_byte }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::test_utils;
+    use types::bytesrepr;
+
+    use super::{gens, StorageCosts};
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let mock = test_utils::storage_costs_mock();
+        let free = StorageCosts::default();
+        bytesrepr::test_serialization_roundtrip(&mock);
+        bytesrepr::test_serialization_roundtrip(&free);
+    }
+
+    #[test]
+    fn should_compute_cost() {
+        let storage_costs = StorageCosts::new(10);
+        assert_eq!(storage_costs.cost(0), 0);
+        assert_eq!(storage_costs.cost(32), 320);
+        assert_eq!(
+            StorageCosts::new(std::u32::MAX).cost(std::usize::MAX),
+            std::u64::MAX
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn should_serialize_and_deserialize_with_arbitrary_values(
+            storage_costs in gens::storage_costs_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&storage_costs);
+        }
+    }
+}
@@ -128,6 +128,8 @@ pub enum ApiError {
     HostBufferEmpty,
     /// Data in the host buffer is full and should be consumed first by read operation
     HostBufferFull,
+    /// A called contract used more gas than the limit it was called with.
+    GasLimit,
     /// Error specific to Mint contract.
     Mint(u8),
     /// Error specific to Proof of Stake contract.
@@ -264,6 +266,7 @@ impl From<ApiError> for u32 {
             ApiError::BufferTooSmall => 35,
             ApiError::HostBufferEmpty => 36,
             ApiError::HostBufferFull => 37,
+            ApiError::GasLimit => 38,
             ApiError::Mint(value) => MINT_ERROR_OFFSET + u32::from(value),
             ApiError::ProofOfStake(value) => POS_ERROR_OFFSET + u32::from(value),
             ApiError::User(value) => RESERVED_ERROR_MAX + 1 + u32::from(value),
@@ -315,6 +318,7 @@ impl Debug for ApiError {
             ApiError::BufferTooSmall => write!(f, "ApiError::BufferTooSmall")?,
             ApiError::HostBufferEmpty => write!(f, "ApiError::HostBufferEmpty")?,
             ApiError::HostBufferFull => write!(f, "ApiError::HostBufferFull")?,
+            ApiError::GasLimit => write!(f, "ApiError::GasLimit")?,
             ApiError::Mint(value) => write!(f, "ApiError::Mint({})", value)?,
             ApiError::ProofOfStake(value) => write!(f, "ApiError::ProofOfStake({})", value)?,
             ApiError::User(value) => write!(f, "ApiError::User({})", value)?,
@@ -370,6 +374,7 @@ pub fn result_from(value: i32) -> Result<(), ApiError> {
         35 => Err(ApiError::BufferTooSmall),
         36 => Err(ApiError::HostBufferEmpty),
         37 => Err(ApiError::HostBufferFull),
+        38 => Err(ApiError::GasLimit),
         _ => {
             if value > RESERVED_ERROR_MAX as i32 && value <= (2 * RESERVED_ERROR_MAX + 1) as i32 {
                 Err(ApiError::User(value as u16))
@@ -476,6 +481,7 @@ mod tests {
         round_trip(Err(ApiError::BufferTooSmall));
         round_trip(Err(ApiError::HostBufferEmpty));
         round_trip(Err(ApiError::HostBufferFull));
+        round_trip(Err(ApiError::GasLimit));
         round_trip(Err(ApiError::Mint(0)));
         round_trip(Err(ApiError::Mint(u8::MAX)));
         round_trip(Err(ApiError::ProofOfStake(0)));
@@ -0,0 +1,308 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    bytesrepr::{Error, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    CLType, CLValue,
+};
+
+/// A named argument of an entry point, along with its type.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Parameter {
+    name: String,
+    cl_type: CLType,
+}
+
+impl Parameter {
+    pub fn new<T: Into<String>>(name: T, cl_type: CLType) -> Self {
+        Parameter {
+            name: name.into(),
+            cl_type,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cl_type(&self) -> &CLType {
+        &self.cl_type
+    }
+
+    /// Returns `true` if `value` can be passed as this parameter.  A parameter of type
+    /// [`CLType::Any`] accepts a value of any type.
+    pub fn accepts(&self, value: &CLValue) -> bool {
+        self.cl_type == CLType::Any || self.cl_type == *value.cl_type()
+    }
+}
+
+impl ToBytes for Parameter {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = self.name.to_bytes()?;
+        self.cl_type.append_bytes(&mut result);
+        Ok(result)
+    }
+}
+
+impl FromBytes for Parameter {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (name, rem): (String, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (cl_type, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((Parameter { name, cl_type }, rem))
+    }
+}
+
+impl ToBytes for Vec<Parameter> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let size = self.len() as u32;
+        let mut result: Vec<u8> = Vec::with_capacity(U32_SERIALIZED_LENGTH);
+        result.append(&mut size.to_bytes()?);
+        for parameter in self {
+            result.append(&mut parameter.to_bytes()?);
+        }
+        Ok(result)
+    }
+}
+
+impl FromBytes for Vec<Parameter> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let mut result = Vec::new();
+        result.try_reserve_exact(size as usize)?;
+        for _ in 0..size {
+            let (parameter, rem): (Parameter, &[u8]) = FromBytes::from_bytes(stream)?;
+            result.push(parameter);
+            stream = rem;
+        }
+        Ok((result, stream))
+    }
+}
+
+/// Describes the arguments taken and the value returned by an entry point of a contract.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct EntryPointAbi {
+    name: String,
+    parameters: Vec<Parameter>,
+    ret: CLType,
+}
+
+impl EntryPointAbi {
+    pub fn new<T: Into<String>>(name: T, parameters: Vec<Parameter>, ret: CLType) -> Self {
+        EntryPointAbi {
+            name: name.into(),
+            parameters,
+            ret,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn parameters(&self) -> &[Parameter] {
+        &self.parameters
+    }
+
+    pub fn ret(&self) -> &CLType {
+        &self.ret
+    }
+
+    /// Returns `true` if `args` match the parameters of the entry point in number, order and type.
+    pub fn accepts(&self, args: &[CLValue]) -> bool {
+        self.parameters.len() == args.len()
+            && self
+                .parameters
+                .iter()
+                .zip(args)
+                .all(|(parameter, arg)| parameter.accepts(arg))
+    }
+
+    /// Returns `true` if `value` can be returned by the entry point.  A return type of
+    /// [`CLType::Any`] allows a value of any type.
+    pub fn returns(&self, value: &CLValue) -> bool {
+        self.ret == CLType::Any || self.ret == *value.cl_type()
+    }
+}
+
+impl ToBytes for EntryPointAbi {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = self.name.to_bytes()?;
+        result.append(&mut self.parameters.to_bytes()?);
+        self.ret.append_bytes(&mut result);
+        Ok(result)
+    }
+}
+
+impl FromBytes for EntryPointAbi {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (name, rem): (String, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let (parameters, rem): (Vec<Parameter>, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (ret, rem): (CLType, &[u8]) = FromBytes::from_bytes(rem)?;
+        Ok((
+            EntryPointAbi {
+                name,
+                parameters,
+                ret,
+            },
+            rem,
+        ))
+    }
+}
+
+impl ToBytes for Vec<EntryPointAbi> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let size = self.len() as u32;
+        let mut result: Vec<u8> = Vec::with_capacity(U32_SERIALIZED_LENGTH);
+        result.append(&mut size.to_bytes()?);
+        for entry_point in self {
+            result.append(&mut entry_point.to_bytes()?);
+        }
+        Ok(result)
+    }
+}
+
+impl FromBytes for Vec<EntryPointAbi> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (size, mut stream): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+        let mut result = Vec::new();
+        result.try_reserve_exact(size as usize)?;
+        for _ in 0..size {
+            let (entry_point, rem): (EntryPointAbi, &[u8]) = FromBytes::from_bytes(stream)?;
+            result.push(entry_point);
+            stream = rem;
+        }
+        Ok((result, stream))
+    }
+}
+
+/// The typed interface of a stored contract: a description of each of its entry points.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct ContractAbi {
+    // Sorted by name
+    entry_points: Vec<EntryPointAbi>,
+}
+
+impl ContractAbi {
+    /// Creates an ABI from the given entry point descriptions.
+    pub fn new(mut entry_points: Vec<EntryPointAbi>) -> Self {
+        entry_points.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        ContractAbi { entry_points }
+    }
+
+    pub fn entry_points(&self) -> &[EntryPointAbi] {
+        &self.entry_points
+    }
+
+    /// Returns the description of the entry point called `name`, if any.
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPointAbi> {
+        self.entry_points
+            .binary_search_by(|entry_point| entry_point.name.as_str().cmp(name))
+            .ok()
+            .map(|index| &self.entry_points[index])
+    }
+
+    /// Returns the name of an entry point described more than once, if any.  Contracts with such
+    /// an ABI can't be stored.
+    pub fn duplicate_entry_point(&self) -> Option<&str> {
+        self.entry_points
+            .windows(2)
+            .find(|pair| pair[0].name == pair[1].name)
+            .map(|pair| pair[0].name.as_str())
+    }
+}
+
+impl ToBytes for ContractAbi {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.entry_points.to_bytes()
+    }
+}
+
+impl FromBytes for ContractAbi {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (entry_points, rem): (Vec<EntryPointAbi>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((ContractAbi::new(entry_points), rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+
+    use super::*;
+    use crate::{bytesrepr, Key, U512};
+
+    fn transfer_abi() -> EntryPointAbi {
+        EntryPointAbi::new(
+            "transfer",
+            vec![
+                Parameter::new("target", CLType::Key),
+                Parameter::new("amount", CLType::U512),
+            ],
+            CLType::Unit,
+        )
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize_contract_abi() {
+        let abi = ContractAbi::new(vec![
+            transfer_abi(),
+            EntryPointAbi::new("balance", vec![], CLType::Option(Box::new(CLType::U512))),
+        ]);
+        let bytes = abi.to_bytes().expect("should serialize");
+        let parsed: ContractAbi = bytesrepr::deserialize(bytes).expect("should deserialize");
+        assert_eq!(abi, parsed);
+    }
+
+    #[test]
+    fn should_find_entry_point_by_name() {
+        let abi = ContractAbi::new(vec![
+            transfer_abi(),
+            EntryPointAbi::new("balance", vec![], CLType::U512),
+        ]);
+        assert_eq!(abi.entry_point("transfer"), Some(&transfer_abi()));
+        assert!(abi.entry_point("call").is_none());
+    }
+
+    #[test]
+    fn should_check_args_against_parameters() {
+        let entry_point = transfer_abi();
+        let target = CLValue::from_t(Key::Hash([1; 32])).unwrap();
+        let amount = CLValue::from_t(U512::one()).unwrap();
+
+        assert!(entry_point.accepts(&[target.clone(), amount.clone()]));
+        assert!(!entry_point.accepts(&[amount.clone(), target.clone()]));
+        assert!(!entry_point.accepts(&[target]));
+
+        let any_entry_point = EntryPointAbi::new(
+            "any",
+            vec![Parameter::new("value", CLType::Any)],
+            CLType::Unit,
+        );
+        assert!(any_entry_point.accepts(&[amount]));
+    }
+
+    #[test]
+    fn should_check_return_value_against_ret() {
+        let entry_point = EntryPointAbi::new("balance", vec![], CLType::U512);
+        assert!(entry_point.returns(&CLValue::from_t(U512::one()).unwrap()));
+        assert!(!entry_point.returns(&CLValue::from_t(()).unwrap()));
+
+        let any_entry_point = EntryPointAbi::new("any", vec![], CLType::Any);
+        assert!(any_entry_point.returns(&CLValue::from_t(()).unwrap()));
+    }
+
+    #[test]
+    fn should_find_duplicate_entry_point() {
+        let abi = ContractAbi::new(vec![
+            transfer_abi(),
+            EntryPointAbi::new("balance", vec![], CLType::U512),
+        ]);
+        assert!(abi.duplicate_entry_point().is_none());
+
+        let abi = ContractAbi::new(vec![
+            transfer_abi(),
+            EntryPointAbi::new("balance", vec![], CLType::U512),
+            EntryPointAbi::new("transfer", vec![], CLType::Unit),
+        ]);
+        assert_eq!(abi.duplicate_entry_point(), Some("transfer"));
+    }
+}
@@ -1,5 +1,9 @@
 use crate::{Key, URef};
 
+/// The name of the entry point run when a stored contract is called without naming one.  It is the
+/// only entry point of contracts stored by `store_function` or `store_function_at_hash`.
+pub const DEFAULT_ENTRY_POINT: &str = "call";
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ContractRef {
     Hash([u8; 32]),
@@ -1,6 +1,6 @@
 //! Contains functions for generating arbitrary values for use by [`Proptest`](https://crates.io/crates/proptest).
 
-use alloc::{collections::BTreeMap, string::String, vec};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec};
 
 use proptest::{
     array, bits,
@@ -12,7 +12,8 @@ use proptest::{
 
 use crate::{
     account::{PublicKey, Weight},
-    AccessRights, CLType, CLValue, Key, Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
+    AccessRights, CLType, CLValue, ContractAbi, EntryPointAbi, Key, Parameter, Phase,
+    ProtocolVersion, SemVer, URef, U128, U256, U512,
 };
 
 pub fn u8_slice_32() -> impl Strategy<Value = [u8; 32]> {
@@ -153,6 +154,36 @@ pub fn cl_value_arb() -> impl Strategy<Value = CLValue> {
     ]
 }
 
+fn abi_cl_type_arb() -> impl Strategy<Value = CLType> {
+    prop_oneof![
+        Just(CLType::Bool),
+        Just(CLType::U64),
+        Just(CLType::U512),
+        Just(CLType::Unit),
+        Just(CLType::String),
+        Just(CLType::Key),
+        Just(CLType::Option(Box::new(CLType::URef))),
+        Just(CLType::List(Box::new(CLType::U8))),
+        Just(CLType::Any),
+    ]
+}
+
+pub fn entry_point_abi_arb() -> impl Strategy<Value = EntryPointAbi> {
+    let parameter_arb = ("[a-z_]{1,16}", abi_cl_type_arb())
+        .prop_map(|(name, cl_type)| Parameter::new(name, cl_type));
+    ("[a-z_]{1,16}", vec(parameter_arb, 0..5), abi_cl_type_arb())
+        .prop_map(|(name, parameters, ret)| EntryPointAbi::new(name, parameters, ret))
+}
+
+pub fn contract_abi_arb() -> impl Strategy<Value = ContractAbi> {
+    vec(entry_point_abi_arb(), 0..5).prop_map(|mut entry_points| {
+        // Entry points of an ABI must have distinct names
+        entry_points.sort_by(|lhs, rhs| lhs.name().cmp(rhs.name()));
+        entry_points.dedup_by(|lhs, rhs| lhs.name() == rhs.name());
+        ContractAbi::new(entry_points)
+    })
+}
+
 pub fn result_arb() -> impl Strategy<Value = Result<u32, u32>> {
     result::maybe_ok(any::<u32>(), any::<u32>())
 }
@@ -13,6 +13,7 @@ mod block_time;
 pub mod bytesrepr;
 mod cl_type;
 mod cl_value;
+mod contract_abi;
 mod contract_ref;
 #[cfg(any(feature = "gens", test))]
 pub mod gens;
@@ -23,6 +24,7 @@ mod semver;
 pub mod system_contract_errors;
 mod system_contract_type;
 mod transfer_result;
+mod trie_merkle_proof;
 mod uint;
 mod uref;
 
@@ -32,7 +34,8 @@ pub use api_error::ApiError;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
-pub use contract_ref::ContractRef;
+pub use contract_abi::{ContractAbi, EntryPointAbi, Parameter};
+pub use contract_ref::{ContractRef, DEFAULT_ENTRY_POINT};
 pub use key::{
     Key, KEY_ACCOUNT_LENGTH, KEY_HASH_LENGTH, KEY_LOCAL_LENGTH, KEY_UREF_SERIALIZED_LENGTH,
     LOCAL_SEED_LENGTH,
@@ -42,4 +45,5 @@ pub use protocol_version::{ProtocolVersion, VersionCheckResult};
 pub use semver::SemVer;
 pub use system_contract_type::SystemContractType;
 pub use transfer_result::{TransferResult, TransferredTo};
+pub use trie_merkle_proof::{TrieMerkleProof, TrieMerkleProofError, TRIE_HASH_LENGTH};
 pub use uref::{URef, UREF_ADDR_LENGTH, UREF_SERIALIZED_LENGTH};
@@ -0,0 +1,328 @@
+//! Home of [`TrieMerkleProof`](crate::TrieMerkleProof), a proof that a [`Key`](crate::Key) maps to
+//! a given value (or to no value at all) under a global state root hash.
+//!
+//! Proofs are produced by the execution engine's trie store and can be checked here without access
+//! to global state, so that light clients and bridges need not trust the node which answered a
+//! query.
+
+use alloc::vec::Vec;
+
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
+use failure::Fail;
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    Key,
+};
+
+/// The length in bytes of a trie node hash, and hence of a global state root hash.
+pub const TRIE_HASH_LENGTH: usize = 32;
+
+const TRIE_RADIX: usize = 256;
+
+const LEAF_TAG: u32 = 0;
+const NODE_TAG: u32 = 1;
+const EXTENSION_TAG: u32 = 2;
+
+/// Creates a 32-byte BLAKE2b hash digest from a given a piece of data
+fn hash(bytes: &[u8]) -> [u8; TRIE_HASH_LENGTH] {
+    let mut ret = [0u8; TRIE_HASH_LENGTH];
+    // Safe to unwrap here because our digest length is constant and valid
+    let mut hasher = VarBlake2b::new(TRIE_HASH_LENGTH).unwrap();
+    hasher.input(bytes);
+    hasher.variable_result(|hash| ret.clone_from_slice(hash));
+    ret
+}
+
+/// Errors which can occur while verifying a [`TrieMerkleProof`].
+#[derive(Debug, Fail, PartialEq, Eq, Clone)]
+pub enum TrieMerkleProofError {
+    #[fail(display = "Proof contains no trie nodes")]
+    EmptyProof,
+
+    #[fail(display = "First trie node of proof does not hash to the given root")]
+    RootHashMismatch,
+
+    #[fail(
+        display = "Trie node at position {} of proof does not match its parent",
+        _0
+    )]
+    ChildHashMismatch(usize),
+
+    #[fail(display = "Proof ends before reaching a leaf or a missing branch")]
+    IncompleteProof,
+
+    #[fail(display = "Proof contains trie nodes past its terminal node")]
+    TrailingNodes,
+
+    #[fail(display = "Trie node path is longer than the given key")]
+    PathTooLong,
+
+    #[fail(display = "{}", _0)]
+    Serialization(#[fail(cause)] bytesrepr::Error),
+}
+
+impl From<bytesrepr::Error> for TrieMerkleProofError {
+    fn from(error: bytesrepr::Error) -> Self {
+        TrieMerkleProofError::Serialization(error)
+    }
+}
+
+/// A Merkle proof of the value (or absence of a value) stored under a key in global state.
+///
+/// The proof consists of the serialized trie nodes visited while reading the key, ordered from the
+/// root down to the terminal node.  The terminal node is either the leaf holding the key, or the
+/// node, extension or leaf at which the path to the key diverges from the trie.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProof {
+    nodes: Vec<Vec<u8>>,
+}
+
+impl TrieMerkleProof {
+    /// Constructs a proof from the serialized trie nodes on the path to a key, root first.
+    pub fn new(nodes: Vec<Vec<u8>>) -> Self {
+        TrieMerkleProof { nodes }
+    }
+
+    /// Returns the serialized trie nodes making up this proof, root first.
+    pub fn nodes(&self) -> &[Vec<u8>] {
+        &self.nodes
+    }
+
+    /// Verifies this proof for `key` against `state_root`.
+    ///
+    /// Returns `Ok(Some(value_bytes))` with the serialized value stored under `key` if the proof
+    /// shows that `key` is present, or `Ok(None)` if the proof shows that `key` is absent.
+    pub fn verify(
+        &self,
+        state_root: &[u8; TRIE_HASH_LENGTH],
+        key: &Key,
+    ) -> Result<Option<&[u8]>, TrieMerkleProofError> {
+        let path = key.to_bytes()?;
+        self.verify_path(state_root, &path)
+    }
+
+    /// Verifies this proof for the key with the serialized form `path` against `state_root`.
+    ///
+    /// The serialization of the key type must be prefix-free, as is the case for [`Key`].
+    pub fn verify_path(
+        &self,
+        state_root: &[u8; TRIE_HASH_LENGTH],
+        path: &[u8],
+    ) -> Result<Option<&[u8]>, TrieMerkleProofError> {
+        let (root, children) = self
+            .nodes
+            .split_first()
+            .ok_or(TrieMerkleProofError::EmptyProof)?;
+        if hash(root) != *state_root {
+            return Err(TrieMerkleProofError::RootHashMismatch);
+        }
+
+        let mut children = children.iter().enumerate();
+        let mut current: &[u8] = root;
+        let mut depth: usize = 0;
+
+        loop {
+            let (tag, rem): (u32, &[u8]) = FromBytes::from_bytes(current)?;
+            let maybe_child_hash = match tag {
+                LEAF_TAG => {
+                    if children.next().is_some() {
+                        return Err(TrieMerkleProofError::TrailingNodes);
+                    }
+                    // Keys may not match in the case of a compressed path from a node directly to
+                    // a leaf.
+                    return if rem.starts_with(path) {
+                        Ok(Some(&rem[path.len()..]))
+                    } else {
+                        Ok(None)
+                    };
+                }
+                NODE_TAG => {
+                    let index = *path.get(depth).ok_or(TrieMerkleProofError::PathTooLong)?;
+                    depth += 1;
+                    pointer_block_entry(rem, index.into())?
+                }
+                EXTENSION_TAG => {
+                    let (affix, rem): (Vec<u8>, &[u8]) = FromBytes::from_bytes(rem)?;
+                    let sub_path = path
+                        .get(depth..depth + affix.len())
+                        .ok_or(TrieMerkleProofError::PathTooLong)?;
+                    if sub_path == affix.as_slice() {
+                        depth += affix.len();
+                        let (pointer_hash, _) = pointer(rem)?;
+                        Some(pointer_hash)
+                    } else {
+                        None
+                    }
+                }
+                _ => return Err(bytesrepr::Error::FormattingError.into()),
+            };
+
+            match maybe_child_hash {
+                Some(child_hash) => {
+                    let (position, child) = children
+                        .next()
+                        .ok_or(TrieMerkleProofError::IncompleteProof)?;
+                    if hash(child) != child_hash {
+                        return Err(TrieMerkleProofError::ChildHashMismatch(position + 1));
+                    }
+                    current = child;
+                }
+                None => {
+                    if children.next().is_some() {
+                        return Err(TrieMerkleProofError::TrailingNodes);
+                    }
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
+
+/// Parses a serialized `Pointer`, returning the hash it points to.
+fn pointer(bytes: &[u8]) -> Result<([u8; TRIE_HASH_LENGTH], &[u8]), bytesrepr::Error> {
+    let (_tag, rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+    FromBytes::from_bytes(rem)
+}
+
+/// Parses a serialized `PointerBlock`, returning the hash pointed to by the entry at `index`.
+fn pointer_block_entry(
+    bytes: &[u8],
+    index: usize,
+) -> Result<Option<[u8; TRIE_HASH_LENGTH]>, bytesrepr::Error> {
+    let (length, mut rem): (u32, &[u8]) = FromBytes::from_bytes(bytes)?;
+    if length as usize != TRIE_RADIX {
+        return Err(bytesrepr::Error::FormattingError);
+    }
+    for current_index in 0..=index {
+        let (option_tag, option_rem): (u8, &[u8]) = FromBytes::from_bytes(rem)?;
+        let entry = match option_tag {
+            0 => {
+                rem = option_rem;
+                None
+            }
+            1 => {
+                let (pointer_hash, pointer_rem) = pointer(option_rem)?;
+                rem = pointer_rem;
+                Some(pointer_hash)
+            }
+            _ => return Err(bytesrepr::Error::FormattingError),
+        };
+        if current_index == index {
+            return Ok(entry);
+        }
+    }
+    unreachable!("index is always < {}", TRIE_RADIX)
+}
+
+impl ToBytes for TrieMerkleProof {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.nodes.to_bytes()
+    }
+
+    fn into_bytes(self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.nodes.into_bytes()
+    }
+}
+
+impl FromBytes for TrieMerkleProof {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (nodes, rem): (Vec<Vec<u8>>, &[u8]) = FromBytes::from_bytes(bytes)?;
+        Ok((TrieMerkleProof::new(nodes), rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    fn leaf_bytes(key: &Key, value: &[u8]) -> Vec<u8> {
+        let mut ret = LEAF_TAG.to_bytes().unwrap();
+        ret.append(&mut key.to_bytes().unwrap());
+        ret.extend_from_slice(value);
+        ret
+    }
+
+    fn node_bytes(index: u8, child_hash: [u8; TRIE_HASH_LENGTH]) -> Vec<u8> {
+        let mut ret = NODE_TAG.to_bytes().unwrap();
+        ret.append(&mut (TRIE_RADIX as u32).to_bytes().unwrap());
+        for current_index in 0..TRIE_RADIX {
+            if current_index == usize::from(index) {
+                ret.push(1);
+                ret.append(&mut LEAF_TAG.to_bytes().unwrap());
+                ret.extend_from_slice(&child_hash);
+            } else {
+                ret.push(0);
+            }
+        }
+        ret
+    }
+
+    #[test]
+    fn should_verify_inclusion() {
+        let key = Key::Hash([3u8; 32]);
+        let leaf = leaf_bytes(&key, b"value");
+        let root = node_bytes(1, hash(&leaf));
+        let proof = TrieMerkleProof::new(vec![root.clone(), leaf]);
+        assert_eq!(
+            proof.verify(&hash(&root), &key),
+            Ok(Some(b"value".as_ref()))
+        );
+    }
+
+    #[test]
+    fn should_verify_exclusion() {
+        let key = Key::Hash([3u8; 32]);
+        let other_key = Key::Account([3u8; 32]);
+        let leaf = leaf_bytes(&key, b"value");
+        let root = node_bytes(1, hash(&leaf));
+
+        // The path to `other_key` diverges at the root.
+        let proof = TrieMerkleProof::new(vec![root.clone()]);
+        assert_eq!(proof.verify(&hash(&root), &other_key), Ok(None));
+    }
+
+    #[test]
+    fn should_reject_tampered_proofs() {
+        let key = Key::Hash([3u8; 32]);
+        let leaf = leaf_bytes(&key, b"value");
+        let root = node_bytes(1, hash(&leaf));
+        let root_hash = hash(&root);
+
+        let forged_leaf = leaf_bytes(&key, b"forged");
+        let proof = TrieMerkleProof::new(vec![root.clone(), forged_leaf]);
+        assert_eq!(
+            proof.verify(&root_hash, &key),
+            Err(TrieMerkleProofError::ChildHashMismatch(1))
+        );
+
+        let proof = TrieMerkleProof::new(vec![root.clone()]);
+        assert_eq!(
+            proof.verify(&root_hash, &key),
+            Err(TrieMerkleProofError::IncompleteProof)
+        );
+
+        let proof = TrieMerkleProof::new(vec![leaf]);
+        assert_eq!(
+            proof.verify(&root_hash, &key),
+            Err(TrieMerkleProofError::RootHashMismatch)
+        );
+
+        let proof = TrieMerkleProof::new(vec![]);
+        assert_eq!(
+            proof.verify(&root_hash, &key),
+            Err(TrieMerkleProofError::EmptyProof)
+        );
+    }
+
+    #[test]
+    fn should_serialize_and_deserialize() {
+        let proof = TrieMerkleProof::new(vec![vec![1, 2, 3], vec![], vec![4]]);
+        bytesrepr::test_serialization_roundtrip(&proof);
+    }
+}